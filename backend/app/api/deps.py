@@ -32,6 +32,10 @@ async def get_current_user_id(token: str = Depends(oauth2_scheme)) -> str:
         
         if user_id is None:
             raise credentials_exception
+
+        # Refresh Token은 API 인증에 사용할 수 없음 (/auth/desktop/refresh 전용)
+        if payload.get("type") == "refresh":
+            raise credentials_exception
             
         return user_id
         
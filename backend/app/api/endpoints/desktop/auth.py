@@ -1,17 +1,23 @@
-from fastapi import APIRouter, Request, HTTPException
+from fastapi import APIRouter, Request, HTTPException, Query
 from fastapi.templating import Jinja2Templates 
 from authlib.integrations.starlette_client import OAuth, OAuthError
 from fastapi.responses import HTMLResponse
+from pydantic import BaseModel
+from bson import ObjectId
 from datetime import datetime, timedelta, timezone
 
 import os
+import base64
+import hashlib
+import hmac
+import secrets
 from dotenv import load_dotenv
 from urllib.parse import urlencode
 
 # mongo 모듈 자체를 import (db 변수 직접 import 시 None 문제 발생 방지)
 from app.db import mongo
 from app.models.user import UserInDB
-from app.core.security import create_access_token, create_refresh_token
+from app.core.security import create_access_token, create_refresh_token, decode_refresh_token
 
 load_dotenv()
 
@@ -24,6 +30,18 @@ GOOGLE_CLIENT_SECRET = os.getenv("GOOGLE_CLIENT_SECRET")
 # [유지] 127.0.0.1 강제 고정 (로컬 테스트용)
 BACKEND_PUBLIC_URL = os.getenv("BACKEND_PUBLIC_URL", "http://127.0.0.1:8000")
 
+# 딥 링크로 전달되는 1회용 로그인 code의 유효 시간
+LOGIN_CODE_EXPIRE_MINUTES = 5
+
+
+def _hash_login_code(code: str) -> str:
+    return hashlib.sha256(code.encode()).hexdigest()
+
+
+def _pkce_s256(code_verifier: str) -> str:
+    digest = hashlib.sha256(code_verifier.encode()).digest()
+    return base64.urlsafe_b64encode(digest).rstrip(b"=").decode()
+
 # --- OAuth 설정 ---
 oauth = OAuth()
 oauth.register(
@@ -38,7 +56,17 @@ oauth.register(
 # 1. 로그인 시작
 # --------------------------------------------------------------------------
 @router.get("/google/login")
-async def login_via_google(request: Request):
+async def login_via_google(
+    request: Request,
+    state: str = Query(..., min_length=16),
+    code_challenge: str = Query(..., min_length=43, max_length=128),
+    code_challenge_method: str = Query("S256"),
+):
+    if code_challenge_method != "S256":
+        raise HTTPException(status_code=400, detail="Only S256 code_challenge_method is supported")
+
+    # 데스크톱 앱이 만든 state / PKCE challenge를 콜백까지 보관 (딥 링크에 state를 되돌려줌)
+    request.session["desktop_login"] = {"state": state, "code_challenge": code_challenge}
 
     redirect_uri = f"{BACKEND_PUBLIC_URL}/api/v1/auth/desktop/google/callback"
     response = await oauth.google.authorize_redirect(request, redirect_uri)
@@ -55,6 +83,10 @@ async def auth_google_callback(request: Request):
         # authlib가 세션에서 자동으로 redirect_uri를 가져오도록
         # (수동으로 넘기면 'multiple values' 에러 발생)
         token = await oauth.google.authorize_access_token(request)
+
+        desktop_login = request.session.pop("desktop_login", None)
+        if not desktop_login:
+            raise HTTPException(status_code=400, detail="Login was not started from the desktop app")
         
         user_info = token.get('userinfo')
         if not user_info:
@@ -92,20 +124,24 @@ async def auth_google_callback(request: Request):
             result = await mongo.db.users.insert_one(new_user.model_dump(by_alias=True))
             user_id_str = str(result.inserted_id)
 
-        # C. 공통 모듈 사용하여 토큰 발급
+        # C. 1회용 로그인 code 발급 (토큰은 앱이 code_verifier와 함께 /token으로 교환)
         if not user_id_str:
              raise HTTPException(status_code=500, detail="User ID generation failed")
-        
-        access_token = create_access_token(user_id_str)
-        refresh_token = create_refresh_token(user_id_str)
+
+        login_code = secrets.token_urlsafe(32)
+        await mongo.db.desktop_login_codes.insert_one({
+            "code_hash": _hash_login_code(login_code),
+            "user_id": user_id_str,
+            "email": email,
+            "code_challenge": desktop_login["code_challenge"],
+            "expires_at": datetime.now(timezone.utc) + timedelta(minutes=LOGIN_CODE_EXPIRE_MINUTES),
+        })
 
         # D. 데스크톱 앱 깨우기 (Deep Link Redirect)
         # URL 파라미터 인코딩 적용 (특수문자 등으로 인한 파싱 오류 방지)
         query_params = {
-            "access_token": access_token,
-            "refresh_token": refresh_token,
-            "email": email,
-            "user_id": user_id_str 
+            "code": login_code,
+            "state": desktop_login["state"],
         }
         query_string = urlencode(query_params)
         
@@ -118,7 +154,79 @@ async def auth_google_callback(request: Request):
             {"request": request, "deep_link": deep_link_url, "email": email}
         )
 
+    except HTTPException as e:
+        return HTMLResponse(content=f"<h1>Authentication Failed</h1><p>{e.detail}</p>", status_code=e.status_code)
     except OAuthError as e:
         return HTMLResponse(content=f"<h1>Authentication Failed</h1><p>{str(e)}</p>", status_code=400)
     except Exception as e:
-        return HTMLResponse(content=f"<h1>Server Error</h1><p>{str(e)}</p>", status_code=500)
\ No newline at end of file
+        return HTMLResponse(content=f"<h1>Server Error</h1><p>{str(e)}</p>", status_code=500)
+
+# --------------------------------------------------------------------------
+# 3. Access Token 갱신 (데스크톱 앱이 401 또는 만료 임박 시 호출)
+# --------------------------------------------------------------------------
+class RefreshTokenBody(BaseModel):
+    refresh_token: str
+
+class TokenPairResponse(BaseModel):
+    access_token: str
+    refresh_token: str
+    token_type: str = "bearer"
+
+@router.post("/refresh", response_model=TokenPairResponse)
+async def refresh_desktop_token(body: RefreshTokenBody):
+    user_id_str = decode_refresh_token(body.refresh_token)
+    if not user_id_str:
+        raise HTTPException(status_code=401, detail="Invalid or expired refresh token")
+
+    if mongo.db is None:
+        raise HTTPException(status_code=500, detail="Database connection failed")
+
+    # 탈퇴/삭제된 사용자의 토큰은 갱신 불가
+    if not ObjectId.is_valid(user_id_str) or await mongo.db.users.find_one({"_id": ObjectId(user_id_str)}) is None:
+        raise HTTPException(status_code=401, detail="User not found")
+
+    # Refresh Token도 함께 재발급 (Rotation)
+    return TokenPairResponse(
+        access_token=create_access_token(user_id_str),
+        refresh_token=create_refresh_token(user_id_str),
+    )
+
+# --------------------------------------------------------------------------
+# 4. 로그인 code -> 토큰 교환 (딥 링크 콜백 이후 데스크톱 앱이 호출, PKCE 검증)
+# --------------------------------------------------------------------------
+class LoginCodeExchangeBody(BaseModel):
+    code: str
+    code_verifier: str
+
+class LoginTokenResponse(TokenPairResponse):
+    email: str
+    user_id: str
+
+@router.post("/token", response_model=LoginTokenResponse)
+async def exchange_desktop_login_code(body: LoginCodeExchangeBody):
+    if mongo.db is None:
+        raise HTTPException(status_code=500, detail="Database connection failed")
+
+    # 1회용: 조회와 동시에 삭제 (재사용 불가)
+    record = await mongo.db.desktop_login_codes.find_one_and_delete(
+        {"code_hash": _hash_login_code(body.code)}
+    )
+    if record is None:
+        raise HTTPException(status_code=400, detail="Invalid or already used login code")
+
+    expires_at = record["expires_at"]
+    if expires_at.tzinfo is None:
+        expires_at = expires_at.replace(tzinfo=timezone.utc)
+    if expires_at <= datetime.now(timezone.utc):
+        raise HTTPException(status_code=400, detail="Login code expired")
+
+    if not hmac.compare_digest(_pkce_s256(body.code_verifier), record["code_challenge"]):
+        raise HTTPException(status_code=400, detail="PKCE verification failed")
+
+    user_id_str = record["user_id"]
+    return LoginTokenResponse(
+        access_token=create_access_token(user_id_str),
+        refresh_token=create_refresh_token(user_id_str),
+        email=record["email"],
+        user_id=user_id_str,
+    )
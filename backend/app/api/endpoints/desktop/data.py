@@ -1,38 +1,87 @@
-from fastapi import APIRouter, Depends, HTTPException
-from typing import List
+from fastapi import APIRouter, Depends, HTTPException, Header, Response
+from fastapi.encoders import jsonable_encoder
+from fastapi.responses import JSONResponse
+from typing import List, Optional
+import hashlib
+import json
 
 from app.crud import tasks as task_crud
 from app.crud import schedules as schedule_crud
+from app.crud import erasure as erasure_crud
 
-from app.schemas.task import TaskRead
+from app.schemas.task import TaskRead, DesktopTaskPush, DesktopTaskPushResult
 from app.schemas.schedule import ScheduleRead
+from app.schemas.user import DataErasureResult
 
 # [데스크톱 전용] JWT 인증 의존성 (웹 세션 대신 토큰 사용)
 from app.api.deps import get_current_user_id
 
 router = APIRouter()
 
+# --------------------------------------------------------------------------
+# 조건부 응답 (ETag / If-None-Match)
+# 응답 본문의 해시를 ETag로 사용 -> 데스크톱 앱이 마지막 ETag를 보내면 변경이 없을 때 304 (본문 없음)
+# --------------------------------------------------------------------------
+def conditional_json(items: list, if_none_match: Optional[str]) -> Response:
+    body = jsonable_encoder(items)
+    digest = hashlib.sha256(
+        json.dumps(body, sort_keys=True, separators=(",", ":"), ensure_ascii=False).encode("utf-8")
+    ).hexdigest()
+    etag = f'"{digest}"'
+
+    if if_none_match and etag in [tag.strip() for tag in if_none_match.split(",")]:
+        return Response(status_code=304, headers={"ETag": etag})
+    return JSONResponse(content=body, headers={"ETag": etag})
+
 # --------------------------------------------------------------------------
 # GET /api/v1/desktop/data/tasks
-# 설명: 로그인한 사용자의 모든 할 일(Task) 목록을 조회
+# 설명: 로그인한 사용자의 모든 할 일(Task) 목록을 조회 (ETag 지원)
 # --------------------------------------------------------------------------
 @router.get("/tasks", response_model=List[TaskRead])
 async def read_my_tasks(
     # 헤더의 JWT 토큰을 검증하고 user_id를 추출
-    user_id: str = Depends(get_current_user_id)
+    user_id: str = Depends(get_current_user_id),
+    if_none_match: Optional[str] = Header(None, alias="If-None-Match"),
 ):
     # CRUD 모듈을 재사용하여 DB 조회
     tasks = await task_crud.get_tasks(user_id)
-    return tasks
+    return conditional_json([TaskRead.model_validate(t) for t in tasks], if_none_match)
+
+# --------------------------------------------------------------------------
+# PUT /api/v1/desktop/data/tasks/{task_id}
+# 설명: 데스크톱 앱에서 오프라인으로 생성/수정한 Task 반영 (없으면 생성)
+#       서버에서도 수정된 경우 더 나중에 수정된 쪽이 이김 (Last-Writer-Wins)
+# --------------------------------------------------------------------------
+@router.put("/tasks/{task_id}", response_model=DesktopTaskPushResult)
+async def push_my_task(
+    task_id: str,
+    task_in: DesktopTaskPush,
+    user_id: str = Depends(get_current_user_id),
+):
+    applied, conflict, task = await task_crud.upsert_task_from_desktop(user_id, task_id, task_in)
+    return DesktopTaskPushResult(applied=applied, conflict=conflict, task=task)
 
 # --------------------------------------------------------------------------
 # GET /api/v1/desktop/data/schedules
-# 설명: 로그인한 사용자의 모든 스케줄 목록을 조회
+# 설명: 로그인한 사용자의 모든 스케줄 목록을 조회 (ETag 지원)
 # --------------------------------------------------------------------------
 @router.get("/schedules", response_model=List[ScheduleRead])
 async def read_my_schedules(
-    user_id: str = Depends(get_current_user_id)
+    user_id: str = Depends(get_current_user_id),
+    if_none_match: Optional[str] = Header(None, alias="If-None-Match"),
 ):
     # CRUD 모듈 재사용
     schedules = await schedule_crud.get_schedules(user_id)
-    return schedules
\ No newline at end of file
+    return conditional_json([ScheduleRead.model_validate(s) for s in schedules], if_none_match)
+
+# --------------------------------------------------------------------------
+# DELETE /api/v1/desktop/data/me
+# 설명: 데스크톱 앱의 '내 데이터 전체 삭제' 요청. 로그인한 사용자의 활동 데이터
+#       (이벤트, 피드백, 세션, 할 일, 스케줄)를 모두 삭제. 계정 자체는 유지
+# --------------------------------------------------------------------------
+@router.delete("/me", response_model=DataErasureResult)
+async def erase_my_data(
+    user_id: str = Depends(get_current_user_id),
+):
+    deleted = await erasure_crud.erase_user_data(user_id)
+    return DataErasureResult(deleted=deleted)
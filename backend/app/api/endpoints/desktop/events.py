@@ -1,33 +1,67 @@
-from fastapi import APIRouter, HTTPException, Depends
-from typing import List
+from fastapi import APIRouter, HTTPException, Depends, Header
+from typing import List, Optional
+from datetime import datetime, timezone
+from pymongo.errors import BulkWriteError
 from app.db import mongo
 from app.models.event import EventInDB
 from app.schemas.event import EventBatchCreate, EventCreateResponse
 from app.api import deps
+from app.api.gzip_route import GzipRoute
 import uuid
 
-router = APIRouter()
+# 데스크톱 앱은 이벤트 배치를 gzip으로 압축해서 보냄 (Content-Encoding: gzip)
+router = APIRouter(route_class=GzipRoute)
+
+# MongoDB 중복 키 에러 코드
+DUPLICATE_KEY_ERROR = 11000
 
 # --------------------------------------------------------------------------
 # 이벤트 배치 업로드 (POST /api/v1/events/batch)
+# - client_event_id 기준 멱등: 이미 저장된 이벤트는 다시 저장하지 않고 accepted_ids에 포함
+# - Idempotency-Key: 같은 키로 재요청 시 저장된 응답을 그대로 반환
 # --------------------------------------------------------------------------
 @router.post("/batch", response_model=EventCreateResponse)
 async def create_events_batch(
     batch: EventBatchCreate,
     # [핵심 수정] 토큰을 검증하고 user_id를 추출하여 주입받음
-    user_id: str = Depends(deps.get_current_user_id) 
+    user_id: str = Depends(deps.get_current_user_id),
+    idempotency_key: Optional[str] = Header(None, alias="Idempotency-Key"),
 ):
     if mongo.db is None:
         raise HTTPException(status_code=500, detail="Database connection failed")
 
     # 1. 데이터가 비어있는지 확인
     if not batch.events:
-        return EventCreateResponse(status="success", count=0)
+        return EventCreateResponse(status="success", count=0, accepted_ids=[])
+
+    # 2. 같은 배치의 재전송이면 이전 응답 반환
+    if idempotency_key:
+        previous = await mongo.db.event_batch_requests.find_one(
+            {"user_id": user_id, "idempotency_key": idempotency_key}
+        )
+        if previous:
+            return EventCreateResponse(**previous["response"])
+
+    # 3. 이미 저장된 client_event_id 제외
+    client_ids = [e.client_event_id for e in batch.events if e.client_event_id]
+    existing_ids = set()
+    if client_ids:
+        cursor = mongo.db.events.find(
+            {"user_id": user_id, "client_event_id": {"$in": client_ids}},
+            {"client_event_id": 1},
+        )
+        existing_ids = {doc["client_event_id"] async for doc in cursor}
 
-    # 2. 저장할 문서 리스트 준비
+    # 4. 저장할 문서 리스트 준비
     documents = []
+    seen_ids = set(existing_ids)
 
     for event_data in batch.events:
+        if event_data.client_event_id:
+            if event_data.client_event_id in seen_ids:
+                continue
+            seen_ids.add(event_data.client_event_id)
+
         # Pydantic 모델(EventCreate) -> DB 모델(EventInDB) 변환
         event_doc = EventInDB(
             id=str(uuid.uuid4()),
@@ -43,11 +77,26 @@ async def create_events_batch(
         # model_dump(by_alias=True)를 통해 id -> _id 매핑
         documents.append(event_doc.model_dump(by_alias=True))
 
-    # 3. MongoDB에 일괄 저장 (Bulk Insert)
+    # 5. MongoDB에 일괄 저장 (Bulk Insert). 동시 요청으로 인한 중복 키는 이미 저장된 것으로 간주
+    inserted_count = 0
     if documents:
-        result = await mongo.db.events.insert_many(documents)
-        inserted_count = len(result.inserted_ids)
+        try:
+            result = await mongo.db.events.insert_many(documents, ordered=False)
+            inserted_count = len(result.inserted_ids)
+        except BulkWriteError as e:
+            errors = e.details.get("writeErrors", [])
+            if any(err.get("code") != DUPLICATE_KEY_ERROR for err in errors):
+                raise HTTPException(status_code=500, detail="Failed to store events")
+            inserted_count = e.details.get("nInserted", 0)
         print(f"Synced {inserted_count} events from desktop.")
-        return EventCreateResponse(status="success", count=inserted_count)
-    
-    return EventCreateResponse(status="success", count=0)
\ No newline at end of file
+
+    response = EventCreateResponse(status="success", count=inserted_count, accepted_ids=list(dict.fromkeys(client_ids)))
+
+    if idempotency_key:
+        await mongo.db.event_batch_requests.update_one(
+            {"user_id": user_id, "idempotency_key": idempotency_key},
+            {"$setOnInsert": {"response": response.model_dump(), "created_at": datetime.now(timezone.utc)}},
+            upsert=True,
+        )
+
+    return response
@@ -0,0 +1,44 @@
+# backend/app/api/gzip_route.py
+
+import zlib
+from typing import Callable
+
+from fastapi import HTTPException, Request, Response
+from fastapi.routing import APIRoute
+
+# 압축 해제 후 허용하는 최대 본문 크기 (압축 폭탄 방지)
+MAX_DECOMPRESSED_BYTES = 20 * 1024 * 1024
+
+
+class GzipRequest(Request):
+    """
+    Content-Encoding: gzip 요청 본문을 풀어서 전달하는 Request
+    """
+    async def body(self) -> bytes:
+        if not hasattr(self, "_body"):
+            body = await super().body()
+            if "gzip" in self.headers.getlist("Content-Encoding"):
+                decompressor = zlib.decompressobj(16 + zlib.MAX_WBITS)
+                try:
+                    body = decompressor.decompress(body, MAX_DECOMPRESSED_BYTES)
+                except zlib.error:
+                    raise HTTPException(status_code=400, detail="Invalid gzip body")
+                if decompressor.unconsumed_tail:
+                    raise HTTPException(status_code=413, detail="Decompressed body too large")
+            self._body = body
+        return self._body
+
+
+class GzipRoute(APIRoute):
+    """
+    router = APIRouter(route_class=GzipRoute)
+    데스크톱 앱의 대용량 업로드(이벤트 배치)용. 압축되지 않은 요청도 그대로 처리
+    """
+    def get_route_handler(self) -> Callable:
+        original_route_handler = super().get_route_handler()
+
+        async def custom_route_handler(request: Request) -> Response:
+            request = GzipRequest(request.scope, request.receive)
+            return await original_route_handler(request)
+
+        return custom_route_handler
@@ -1,8 +1,8 @@
 # backend/app/core/security.py
 
 from datetime import datetime, timedelta, timezone
-from jose import jwt
-from typing import Any, Union
+from jose import jwt, JWTError
+from typing import Any, Optional, Union
 import os
 from dotenv import load_dotenv
 
@@ -29,4 +29,17 @@ def create_refresh_token(subject: Union[str, Any]) -> str:
     expire = datetime.now(timezone.utc) + timedelta(days=REFRESH_TOKEN_EXPIRE_DAYS)
     to_encode = {"exp": expire, "sub": str(subject), "type": "refresh"}
     encoded_jwt = jwt.encode(to_encode, SECRET_KEY, algorithm=ALGORITHM)
-    return encoded_jwt
\ No newline at end of file
+    return encoded_jwt
+
+def decode_refresh_token(token: str) -> Optional[str]:
+    """
+    Refresh Token 검증 후 user_id(sub) 반환
+    (서명 불일치, 만료, access 토큰인 경우 None)
+    """
+    try:
+        payload = jwt.decode(token, SECRET_KEY, algorithms=[ALGORITHM])
+    except JWTError:
+        return None
+    if payload.get("type") != "refresh":
+        return None
+    return payload.get("sub")
@@ -0,0 +1,28 @@
+# backend/app/crud/erasure.py
+
+from typing import Dict
+
+from app.db.mongo import get_db
+
+# 사용자 활동 데이터가 저장되는 컬렉션 (계정 문서 users는 제외: 계정 삭제는 DELETE /users/me)
+USER_DATA_COLLECTIONS = (
+    "events",
+    "event_batch_requests",
+    "user_feedback",
+    "sessions",
+    "tasks",
+    "schedules",
+)
+
+
+async def erase_user_data(user_id: str) -> Dict[str, int]:
+    """
+    사용자의 활동 데이터를 모든 컬렉션에서 삭제하고 컬렉션별 삭제 문서 수를 반환합니다.
+    여러 번 호출해도 안전합니다 (데스크톱 앱이 재시도할 수 있음).
+    """
+    db = get_db()
+    deleted: Dict[str, int] = {}
+    for name in USER_DATA_COLLECTIONS:
+        result = await db[name].delete_many({"user_id": str(user_id)})
+        deleted[name] = result.deleted_count
+    return deleted
@@ -134,6 +134,7 @@ def serialize_session(session) -> SessionRead:
         start_time=session["start_time"],
         end_time=session.get("end_time"),
         duration=session.get("duration"),
+        paused_duration=session.get("paused_duration"),
         status=session.get("status", "active"),
         goal_duration=session.get("goal_duration"),
         interruption_count=session.get("interruption_count", 0),
@@ -226,6 +227,12 @@ async def _cancel_existing_active_sessions(user_id: str) -> None:
 async def start_session(user_id: str, data: SessionCreate) -> SessionRead:
     col = get_sessions_collection()
 
+    # 0) 데스크톱 Outbox 재전송: 같은 client_session_id가 이미 있으면 그대로 반환 (중복 생성 방지)
+    if data.client_session_id:
+        existing = await col.find_one({"user_id": user_id, "client_session_id": data.client_session_id})
+        if existing:
+            return serialize_session(existing)
+
     # 1) 기존 active 자동 종료(정책 적용)
     await _cancel_existing_active_sessions(user_id)
 
@@ -339,10 +346,19 @@ async def update_session(user_id: str, session_id: str, data: SessionUpdate) ->
         ts = getattr(data, "end_time_s", None)
         actual_end_time = datetime.fromtimestamp(ts, tz=timezone.utc) if ts else _utcnow()
 
+    if data.paused_duration is not None and data.paused_duration < 0:
+        raise HTTPException(status_code=400, detail="paused_duration must be >= 0")
+
     if actual_end_time is not None:
         end_time = _ensure_aware_utc(actual_end_time)
         update_doc["end_time"] = end_time
-        update_doc["duration"] = _compute_duration_seconds(existing["start_time"], end_time)
+        total = _compute_duration_seconds(existing["start_time"], end_time)
+        # 일시정지 시간을 제외한 순수 집중 시간
+        paused = data.paused_duration or 0.0
+        update_doc["duration"] = max(total - paused, 0.0)
+
+    if data.paused_duration is not None:
+        update_doc["paused_duration"] = data.paused_duration
 
     # 상태 업데이트 및 기본값 설정
     update_doc["status"] = _strip_or_none(data.status) or "completed"
@@ -2,10 +2,10 @@
 
 from bson import ObjectId
 from bson.errors import InvalidId
-from datetime import datetime
+from datetime import datetime, timedelta, timezone
 from fastapi import HTTPException
 
-from app.schemas.task import TaskCreate, TaskUpdate, TaskRead
+from app.schemas.task import TaskCreate, TaskUpdate, TaskRead, DesktopTaskPush
 
 
 def get_tasks_collection():
@@ -26,6 +26,17 @@ def _safe_object_id(task_id: str) -> ObjectId:
         raise HTTPException(status_code=400, detail="Invalid task_id")
 
 
+def _utc_naive(value: datetime) -> datetime:
+    # MongoDB는 naive UTC로 저장/반환 (밀리초 정밀도)
+    if value.tzinfo is not None:
+        value = value.astimezone(timezone.utc).replace(tzinfo=None)
+    return value.replace(microsecond=value.microsecond // 1000 * 1000)
+
+
+def _utcnow() -> datetime:
+    return _utc_naive(datetime.now(timezone.utc))
+
+
 def serialize_task(task) -> TaskRead:
     return TaskRead(
         id=str(task["_id"]),
@@ -40,6 +51,7 @@ def serialize_task(task) -> TaskRead:
         target_arguments=task.get("target_arguments"),
         # ✅ 추가
         isCustom=task.get("isCustom", False),
+        updated_at=task.get("updated_at"),
     )
 
 
@@ -59,6 +71,7 @@ async def create_task(user_id: str, task_data: TaskCreate) -> TaskRead:
         # 방어: 혹시 payload에 안 왔어도 기본값 보장
         "isCustom": task_data.isCustom,
         "created_at": datetime.now(),
+        "updated_at": _utcnow(),
         "status": "pending",
     }
 
@@ -98,6 +111,8 @@ async def update_task(user_id: str, task_id: str, task_data: TaskUpdate):
     if not update_fields:
         return await get_task(user_id, task_id)
 
+    update_fields["updated_at"] = _utcnow()
+
     result = await tasks_collection.update_one(
         {"_id": oid, "user_id": user_id},
         {"$set": update_fields},
@@ -110,6 +125,66 @@ async def update_task(user_id: str, task_id: str, task_data: TaskUpdate):
     return serialize_task(updated) if updated else None
 
 
+# UPSERT (데스크톱 오프라인 편집, Last-Writer-Wins)
+# 반환: (applied, conflict, 확정된 Task)
+async def upsert_task_from_desktop(user_id: str, task_id: str, data: DesktopTaskPush):
+    tasks_collection = get_tasks_collection()
+    oid = _safe_object_id(task_id)
+    client_updated_at = _utc_naive(data.client_updated_at)
+
+    fields = {
+        "name": data.name,
+        "description": data.description,
+        "target_executable": data.target_executable,
+        "target_arguments": data.target_arguments,
+        "status": data.status,
+    }
+
+    doc = await tasks_collection.find_one({"_id": oid})
+    if doc is None:
+        # 앱에서 오프라인 생성된 Task (같은 ID로 재전송되어도 한 번만 생성)
+        await tasks_collection.update_one(
+            {"_id": oid},
+            {
+                "$setOnInsert": {
+                    "user_id": user_id,
+                    **fields,
+                    "isCustom": True,
+                    "created_at": datetime.now(),
+                    "updated_at": client_updated_at,
+                }
+            },
+            upsert=True,
+        )
+        saved = await tasks_collection.find_one({"_id": oid, "user_id": user_id})
+        if not saved:
+            raise HTTPException(status_code=404, detail="Task not found")
+        return True, False, serialize_task(saved)
+
+    if doc["user_id"] != user_id:
+        raise HTTPException(status_code=404, detail="Task not found")
+
+    server_updated_at = doc.get("updated_at")
+    base_updated_at = _utc_naive(data.base_updated_at) if data.base_updated_at else None
+    conflict = server_updated_at is not None and base_updated_at != server_updated_at
+    applied = not conflict or client_updated_at >= server_updated_at
+
+    if not applied:
+        return False, True, serialize_task(doc)
+
+    # 버전은 항상 증가 (앱 시계가 느려도 다음 충돌 판단이 가능하도록)
+    new_updated_at = client_updated_at
+    if server_updated_at is not None and new_updated_at <= server_updated_at:
+        new_updated_at = server_updated_at + timedelta(milliseconds=1)
+
+    await tasks_collection.update_one(
+        {"_id": oid, "user_id": user_id},
+        {"$set": {**fields, "updated_at": new_updated_at}},
+    )
+    updated = await tasks_collection.find_one({"_id": oid, "user_id": user_id})
+    return True, conflict, serialize_task(updated)
+
+
 # DELETE
 async def delete_task(user_id: str, task_id: str) -> bool:
     tasks_collection = get_tasks_collection()
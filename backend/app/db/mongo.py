@@ -11,6 +11,25 @@ async def connect_to_mongo():
     client = AsyncIOMotorClient(settings.MONGO_URI)
     db = client[settings.MONGO_DB_NAME]
     print("MongoDB Connected!")
+    await ensure_indexes()
+
+async def ensure_indexes():
+    # 데스크톱 이벤트 업로드 멱등성: 사용자별 client_event_id 중복 저장 방지
+    try:
+        await db.events.create_index(
+            [("user_id", 1), ("client_event_id", 1)],
+            unique=True,
+            partialFilterExpression={"client_event_id": {"$type": "string"}},
+            name="uniq_user_client_event_id",
+        )
+        # 배치 멱등성 키는 하루 동안만 보관
+        await db.event_batch_requests.create_index(
+            [("user_id", 1), ("idempotency_key", 1)], unique=True, name="uniq_user_idempotency_key"
+        )
+        await db.event_batch_requests.create_index("created_at", expireAfterSeconds=60 * 60 * 24)
+    except Exception as e:
+        # 기존 중복 데이터 등으로 실패해도 서버 기동은 계속 (애플리케이션 레벨 중복 제거는 동작)
+        print(f"MongoDB index creation failed: {e}")
 
 async def close_mongo_connection():
     global client
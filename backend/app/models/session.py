@@ -23,9 +23,12 @@ class SessionInDB(BaseModel):
     start_time: datetime
     end_time: Optional[datetime] = None
 
-    # 세션 종료 후 계산 (초 단위)
+    # 세션 종료 후 계산 (초 단위, 일시정지 시간 제외)
     duration: Optional[float] = None
 
+    # 일시정지 누적 시간 (초 단위)
+    paused_duration: Optional[float] = None
+
     # active, completed, cancelled 등
     status: str = "active"
 
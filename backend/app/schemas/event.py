@@ -101,3 +101,5 @@ class EventCreateResponse(BaseModel):
     status: str = "success"
     count: Optional[int] = None
     event_id: Optional[str] = None
+    # 배치 생성: 저장되었거나 이미 저장되어 있던 client_event_id (데스크톱 앱은 이 목록만 로컬에서 삭제)
+    accepted_ids: Optional[List[str]] = None
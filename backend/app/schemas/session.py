@@ -64,6 +64,7 @@ class SessionUpdate(BaseModel):
     status: Optional[str] = None  # "completed", "cancelled" 등
     goal_duration: Optional[float] = None
     interruption_count: Optional[int] = None
+    paused_duration: Optional[float] = None  # 일시정지 누적 시간 (초 단위, 데스크탑 앱)
 
     @field_validator("status", mode="before")
     @classmethod
@@ -94,7 +95,8 @@ class SessionRead(BaseModel):
 
     start_time: datetime
     end_time: Optional[datetime] = None
-    duration: Optional[float] = None  # 초 단위
+    duration: Optional[float] = None  # 초 단위 (일시정지 시간 제외)
+    paused_duration: Optional[float] = None  # 초 단위
     status: str
     goal_duration: Optional[float] = None
     interruption_count: int = Field(default=0)
\ No newline at end of file
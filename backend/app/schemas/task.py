@@ -127,7 +127,46 @@ class TaskRead(BaseModel):
     target_executable: Optional[str] = None
     target_arguments: Optional[str] = None
     isCustom: bool
+    # 마지막 수정 시각 (UTC). 데스크톱 오프라인 편집의 충돌 판단 기준
+    updated_at: Optional[datetime] = None
 
     model_config = {
         "from_attributes": True
     }
+
+
+# --- 데스크톱 오프라인 편집 동기화 스키마 ---
+class DesktopTaskPush(BaseModel):
+    """
+    [요청] PUT /desktop/data/tasks/{task_id}
+    데스크톱 앱에서 오프라인으로 생성/수정한 Task. 없으면 생성(task_id는 앱이 만든 ObjectId 형식)
+    """
+    model_config = ConfigDict(str_strip_whitespace=True)
+
+    name: str
+    description: Optional[str] = None
+    target_executable: Optional[str] = None
+    target_arguments: Optional[str] = None
+    status: str
+    client_updated_at: datetime                 # 앱에서 수정한 시각 (Last-Writer-Wins 비교)
+    base_updated_at: Optional[datetime] = None  # 수정 전 앱이 알던 서버 버전
+
+    @field_validator("name")
+    @classmethod
+    def validate_name(cls, v: str) -> str:
+        return _strip_and_reject_blank(v, "name")
+
+    @field_validator("description", "target_executable", "target_arguments", mode="before")
+    @classmethod
+    def validate_optional_strings(cls, v):
+        return _strip_to_none(v)
+
+
+class DesktopTaskPushResult(BaseModel):
+    """
+    [응답] applied=False면 서버 쪽 수정이 더 최근이라 앱의 수정이 버려짐.
+    conflict=True면 앱이 마지막으로 받은 이후 서버에서도 수정됨 (앱이 충돌 기록을 남김)
+    """
+    applied: bool
+    conflict: bool
+    task: TaskRead
@@ -176,3 +176,10 @@ class UserRead(UserBase):
 class SuccessMessage(BaseModel):
     success: bool = True
     message: str = "Operation successful"
+
+
+class DataErasureResult(BaseModel):
+    """
+    [응답] 활동 데이터 전체 삭제 결과 (컬렉션별 삭제 문서 수)
+    """
+    deleted: Dict[str, int]
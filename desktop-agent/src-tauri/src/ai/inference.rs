@@ -133,6 +133,13 @@ impl InferenceEngine {
         println!("🧠 Local Cache Updated: '{}' is now trusted until {:?}", token, expiration);
     }
     
+    /// 학습된 로컬 캐시 전체 삭제 (전체 데이터 삭제 시). 삭제한 항목 수 반환
+    pub fn clear_local_cache(&mut self) -> usize {
+        let count = self.local_cache.len();
+        self.local_cache.clear();
+        count
+    }
+
     /// 메인 추론 함수
     /// input_vector: FeatureExtractor가 만든 6차원 벡터
     /// active_tokens: 현재 활성 창의 토큰 리스트 (Cache 확인용)
@@ -1,7 +1,7 @@
 use crate::utils::api::BackendCommunicator;
 use crate::core::app::AppCore; 
 use crate::ai::inference::InferenceEngine;
-use crate::StorageManagerArcMutex;
+use crate::StorageManagerArc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::{AppHandle, Manager};
@@ -26,7 +26,7 @@ impl ModelUpdateManager {
     }
 
     // 업데이트 확인 및 수행 (Result<bool> 반환: true=업데이트됨)
-    pub async fn check_and_update(&self, token: &str) -> Result<bool, String> {
+    pub async fn check_and_update(&self) -> Result<bool, String> {
         // 1. 필요한 State 가져오기
         let communicator = self.app_handle.try_state::<Arc<BackendCommunicator>>()
             .ok_or("BackendCommunicator state not found")?
@@ -45,7 +45,7 @@ impl ModelUpdateManager {
         let final_scaler_path = model_dir.join(SCALER_FILENAME);
 
         // 3. 버전 확인 (API 호출)
-        let info = communicator.check_latest_model_version(token).await
+        let info = communicator.check_latest_model_version(&self.app_handle).await
             .map_err(|e| format!("Check version failed: {}", e))?;
 
         // TODO: 로컬 버전과 비교 로직 추가 (현재는 무조건 진행)
@@ -54,9 +54,9 @@ impl ModelUpdateManager {
         let temp_model_path = model_dir.join("temp_model.onnx");
         let temp_scaler_path = model_dir.join("temp_scaler.json");
 
-        communicator.download_file(&info.download_urls.model, &temp_model_path, token).await
+        communicator.download_file(&info.download_urls.model, &temp_model_path, &self.app_handle).await
             .map_err(|e| format!("Download model failed: {}", e))?;
-        communicator.download_file(&info.download_urls.scaler, &temp_scaler_path, token).await
+        communicator.download_file(&info.download_urls.scaler, &temp_scaler_path, &self.app_handle).await
             .map_err(|e| format!("Download scaler failed: {}", e))?;
 
         // 5. Atomic Swap & Reload (Critical Section)
@@ -104,20 +104,14 @@ pub fn start_update_loop(app_handle: AppHandle) {
         let manager = ModelUpdateManager::new(app_handle.clone());
 
         loop {
-            let token_opt = if let Some(storage_mutex) = app_handle.try_state::<StorageManagerArcMutex>() {
-                match storage_mutex.lock() {
-                    Ok(storage) => storage.load_auth_token().unwrap_or(None).map(|t| t.0),
-                    Err(_) => {
-                        eprintln!("Failed to lock StorageManager in update loop");
-                        None
-                    }
-                }
-            } else {
-                None
+            // 로그인 상태일 때만 확인 (토큰은 BackendCommunicator가 갱신/주입)
+            let is_logged_in = match app_handle.try_state::<StorageManagerArc>() {
+                Some(storage) => storage.load_auth_token().unwrap_or(None).is_some(),
+                None => false,
             };
 
-            if let Some(token) = token_opt {
-                match manager.check_and_update(&token).await {
+            if is_logged_in {
+                match manager.check_and_update().await {
                     Ok(updated) => {
                         if updated { println!("✨ Background update success."); }
                     },
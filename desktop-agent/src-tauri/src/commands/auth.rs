@@ -2,27 +2,30 @@
 // backend_comm.rs에서 분리된 인증 관련 Tauri 커맨드 (U-3 해결)
 
 use tauri::{command, State};
-use crate::StorageManagerArcMutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::managers::login_flow::PendingLogin;
+use crate::utils::api::get_api_base_url;
+use crate::utils::error::AppResult;
+use crate::{PendingLoginArcMutex, StorageManagerArc};
 
-/// 로그인 커맨드
+/// 구글 로그인 시작: 1회용 state + PKCE verifier를 생성해 보관하고, 브라우저로 열 URL 반환
+/// 이전에 시작한 로그인 시도는 폐기됨 (마지막 시도만 유효)
 #[command]
-pub fn login(
-    access_token: String,
-    refresh_token: String,
-    user_email: String,
-    user_id: String,
-    storage_manager_mutex: State<'_, StorageManagerArcMutex>,
-) -> Result<(), String> {
-    let storage_manager = storage_manager_mutex.lock().map_err(|e| e.to_string())?;
-    storage_manager.save_auth_token(&access_token, &refresh_token, &user_email, &user_id)?;
-    println!("User logged in: [REDACTED]");
-    Ok(())
+pub fn begin_login(pending_login_mutex: State<'_, PendingLoginArcMutex>) -> AppResult<String> {
+    let now_s = SystemTime::now()
+        .duration_since(UNIX_EPOCH)?
+        .as_secs();
+    let login = PendingLogin::new(now_s);
+    let url = login.login_url(&get_api_base_url());
+
+    *pending_login_mutex.lock()? = Some(login);
+    println!("Login started. Waiting for deep link callback.");
+    Ok(url)
 }
 
 /// 로그아웃 커맨드
 #[command]
-pub fn logout(storage_manager_mutex: State<'_, StorageManagerArcMutex>) -> Result<(), String> {
-    let storage_manager = storage_manager_mutex.lock().map_err(|e| e.to_string())?;
+pub fn logout(storage_manager: State<'_, StorageManagerArc>) -> AppResult<()> {
     storage_manager.delete_auth_token()?;
     println!("User logged out.");
     Ok(())
@@ -31,10 +34,9 @@ pub fn logout(storage_manager_mutex: State<'_, StorageManagerArcMutex>) -> Resul
 /// 앱 시작 시 로그인 상태 확인 (Auto-Login)
 #[command]
 pub fn check_auth_status(
-    storage_manager_mutex: State<'_, StorageManagerArcMutex>,
-) -> Result<Option<String>, String> {
-    let storage_manager = storage_manager_mutex.lock().map_err(|e| e.to_string())?;
-    let token_data = storage_manager.load_auth_token().map_err(|e| e.to_string())?;
+    storage_manager: State<'_, StorageManagerArc>,
+) -> AppResult<Option<String>> {
+    let token_data = storage_manager.load_auth_token()?;
 
     if let Some((_, _, email, _)) = token_data {
         println!("Auto-login: Found valid token for [REDACTED]");
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use tauri::{command, State};
 use crate::commands::vision::WindowInfo;
+use crate::utils::error::AppResult;
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct InputStats {
@@ -30,8 +31,8 @@ impl InputStats {
 #[command]
 pub fn get_input_frequency_stats(
     input_stats_arc_mutex: State<'_, crate::InputStatsArcMutex>,
-) -> Result<InputStats, String> {
-    let stats = input_stats_arc_mutex.lock().map_err(|_| "Failed to lock InputStats".to_string())?;
+) -> AppResult<InputStats> {
+    let stats = input_stats_arc_mutex.lock()?;
     Ok((*stats).clone())
 }
 
@@ -54,6 +55,7 @@ mod tests {
                     rect: crate::commands::vision::WinRect { 
                         left: 0, top: 0, right: 1920, bottom: 1080 
                     },
+                    hwnd: 0,
                 }
             ],
         };
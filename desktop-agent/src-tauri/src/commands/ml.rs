@@ -1,11 +1,12 @@
 use tauri::{command, State};
 use crate::ai::model_update::ModelUpdateManager;
+use crate::utils::error::{AppError, AppResult};
 
+/// 인증은 LSN에 저장된 토큰으로 처리 (만료 시 BackendCommunicator가 자동 갱신)
 #[command]
 pub async fn check_model_update(
-    token: String,
-    manager: State<'_, ModelUpdateManager>, 
-) -> Result<bool, String> {
+    manager: State<'_, ModelUpdateManager>,
+) -> AppResult<bool> {
     println!("🖱️ [Command] Manual update requested.");
-    manager.check_and_update(&token).await
+    manager.check_and_update().await.map_err(AppError::Model)
 }
@@ -2,6 +2,8 @@ pub mod auth;
 pub mod input;
 pub mod ml;
 pub mod session;
+pub mod settings;
+pub mod storage;
 pub mod system;
 pub mod task;
 pub mod vision;
@@ -1,20 +1,21 @@
 // 파일 위치: src-tauri/src/commands/session.rs
 // backend_comm.rs에서 분리된 세션 관련 Tauri 커맨드 (U-3 해결)
 
-use tauri::{command, State, AppHandle};
+use tauri::{command, AppHandle, Emitter, Manager, Runtime, State};
 use std::sync::{Arc, Mutex};
 use tokio::spawn;
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 use crate::{
-    ActiveSessionInfo, InputStatsArcMutex, SessionStateArcMutex, StorageManagerArcMutex,
-};
-use crate::utils::api::{
-    BackendCommunicator, FeedbackPayload, SessionStartRequest, SessionEndRequest,
-    get_api_base_url,
+    ActiveSessionInfo, InputStatsArcMutex, SessionStateArcMutex, SettingsArcMutex,
+    StorageManagerArc,
 };
+use crate::utils::api::{BackendCommunicator, FeedbackPayload};
 use crate::core::app::AppCore;
+use crate::core::intent::IntentSummary;
+use crate::managers::history::{build_session_summary, SessionSummary};
+use crate::utils::error::{AppError, AppResult};
 
 /// '개입'에 대한 사용자 피드백을 서버로 전송하고, 즉시 로컬 상태를 리셋하는 커맨드
 #[command]
@@ -22,32 +23,30 @@ pub async fn submit_feedback(
     feedback_type: String,
     comm_state: State<'_, Arc<BackendCommunicator>>,
     session_state_mutex: State<'_, SessionStateArcMutex>,
-    storage_manager_mutex: State<'_, StorageManagerArcMutex>,
+    storage_manager: State<'_, StorageManagerArc>,
     app_core_state: State<'_, Mutex<AppCore>>, 
-) -> Result<(), String> {
+    app_handle: AppHandle,
+) -> AppResult<()> {
     
     let client_event_id = {
-        let app = app_core_state.lock().map_err(|_| "Failed to lock AppCore")?;
+        let app = app_core_state.lock()?;
         app.current_event_id.clone().unwrap_or_else(|| format!("evt-fallback-{}", Uuid::new_v4()))
     };
     
     let _session_id = {
-        let session_state = session_state_mutex.lock().map_err(|e| e.to_string())?;
+        let session_state = session_state_mutex.lock()?;
         session_state.as_ref()
             .map(|s| s.session_id.clone())
             .unwrap_or_else(|| "unknown-session".to_string())
     };
 
     // LSN(로컬 DB)에 저장
-    {
-        let storage_manager = storage_manager_mutex.lock().map_err(|e| e.to_string())?;
-        storage_manager.cache_feedback(&client_event_id, &feedback_type)?;
-        println!("Feedback cached to LSN successfully.");
-    }
+    storage_manager.cache_feedback(&client_event_id, &feedback_type)?;
+    println!("Feedback cached to LSN successfully.");
 
     // FSM 즉시 리셋 (오버레이 해제)
     {
-        let mut app = app_core_state.lock().map_err(|_| "Failed to lock AppCore")?;
+        let mut app = app_core_state.lock()?;
         
         app.state_engine.manual_reset();
         //  초기화 직후 최대 5초간 이전 아웃라이어 값이 반영되어 게이지가 다시 차오르지 않게 Inlier로 리셋
@@ -74,12 +73,9 @@ pub async fn submit_feedback(
     let feedback_type_clone = feedback_type.clone();
     let client_event_id_clone = client_event_id.clone();
 
-    let token = {
-        let storage = storage_manager_mutex.lock().map_err(|e| e.to_string())?;
-        storage.load_auth_token().unwrap_or(None).map(|t| t.0)
-    };
+    let is_logged_in = storage_manager.load_auth_token().unwrap_or(None).is_some();
 
-    if let Some(auth_token) = token {
+    if is_logged_in {
         spawn(async move {
             let payload = FeedbackPayload {
                 client_event_id: client_event_id_clone,
@@ -87,7 +83,7 @@ pub async fn submit_feedback(
                 timestamp: chrono::Utc::now().to_rfc3339(),
             };
             
-            if let Err(e) = comm.send_feedback_batch(vec![payload], &auth_token).await {
+            if let Err(e) = comm.send_feedback_batch(vec![payload], &app_handle).await {
                 eprintln!("Background Feedback Sync Failed: {}", e);
             } else {
                 println!("Background Feedback Sync Success");
@@ -99,50 +95,60 @@ pub async fn submit_feedback(
 }
 
 /// 세션 시작 커맨드
+/// intent: 인텐트 프리셋 id (예: "coding", "research"). None이면 global_map만 사용
 #[command]
+#[allow(clippy::too_many_arguments)]
 pub async fn start_session(
     task_id: Option<String>,
     goal_duration: u32,
-    comm_state: State<'_, Arc<BackendCommunicator>>,
+    intent: Option<String>,
     session_state_mutex: State<'_, SessionStateArcMutex>,
-    storage_manager_mutex: State<'_, StorageManagerArcMutex>,
+    storage_manager: State<'_, StorageManagerArc>,
     input_stats_mutex: State<'_, InputStatsArcMutex>,
     app_core_state: State<'_, Mutex<AppCore>>,
-) -> Result<ActiveSessionInfo, String> {
-    let (info, auth_token) = {
-        let mut session_state = session_state_mutex
-            .lock()
-            .map_err(|e| format!("State lock error: {}", e))?;
-        let storage_manager = storage_manager_mutex
-            .lock()
-            .map_err(|e| format!("Storage lock error: {}", e))?;
-
-        let mut input_stats = input_stats_mutex
-            .lock()
-            .map_err(|e| format!("InputStats lock error: {}", e))?;
+    app_handle: AppHandle,
+) -> AppResult<ActiveSessionInfo> {
+    // 인텐트 검증 (세션 생성 전에 알 수 없는 인텐트 거부)
+    let intent_id = match intent.as_deref() {
+        Some(raw) => {
+            let app_core = app_core_state.lock()?;
+            Some(app_core.resolve_intent(raw).map_err(AppError::InvalidInput)?)
+        }
+        None => None,
+    };
+
+    let info = {
+        let mut session_state = session_state_mutex.lock()?;
+        let mut input_stats = input_stats_mutex.lock()?;
 
         if session_state.is_some() {
-            return Err("Session already active.".to_string());
+            return Err(AppError::InvalidInput("Session already active.".to_string()));
+        }
+        if crate::managers::erasure::is_erasure_in_progress() {
+            return Err(AppError::InvalidInput("Cannot start a session while all data is being deleted.".to_string()));
         }
-
-        let token = storage_manager
-            .load_auth_token()
-            .unwrap_or(None)
-            .map(|t| t.0);
 
         let session_id = format!("local-{}", Uuid::new_v4());
         let start_time_s = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| e.to_string())?
+            .duration_since(UNIX_EPOCH)?
             .as_secs();
 
         let info = ActiveSessionInfo {
             session_id: session_id.clone(),
             task_id: task_id.clone(),
             start_time_s,
+            intent: intent_id.clone(),
+            scheduled_end_s: None,
+            paused_total_s: 0,
+            paused_at_s: None,
+            goal_duration_min: Some(goal_duration).filter(|m| *m > 0), // 0 = 목표 없음
+            goal_reached_s: None,
         };
 
         storage_manager.save_active_session(&info)?;
+        storage_manager.insert_session_history(&info, goal_duration)?;
+        // 서버 동기화는 Outbox를 통해 (오프라인이면 로그인 후 전송)
+        crate::managers::sync::enqueue_session_start(&storage_manager, &info, goal_duration)?;
         *session_state = Some(info.clone());
 
         input_stats.meaningful_input_events = 0;
@@ -154,74 +160,65 @@ pub async fn start_session(
         if let Ok(mut app_core) = app_core_state.lock() {
             app_core.state_engine.manual_reset();
             app_core.last_inference_result = crate::ai::inference::InferenceResult::Inlier;
+            if let Err(e) = app_core.set_active_intent(intent_id.as_deref()) {
+                eprintln!("Failed to apply session intent: {}", e);
+            }
         }
 
-        (info, token)
+        info
     };
 
-    if let Some(token) = auth_token {
-        let comm_state_clone = comm_state.inner().clone();
-        let info_clone = info.clone();
-
-        spawn(async move {
-            let task_id_ref = info_clone.task_id.as_deref();
-            let request_body = SessionStartRequest {
-                task_id: task_id_ref,
-                goal_duration,
-            };
-            let url = format!("{}/sessions/start", get_api_base_url());
-
-            match comm_state_clone
-                .client
-                .post(&url)
-                .bearer_auth(token)
-                .json(&request_body)
-                .send()
-                .await
-            {
-                Ok(response) if response.status().is_success() => {
-                    println!("Background sync: Session synced successfully.");
-                }
-                _ => {
-                    eprintln!("Background sync: Failed to sync session. Will retry later.");
-                }
-            }
-        });
-    }
+    crate::managers::sync::flush_session_outbox(&app_handle);
 
     Ok(info)
 }
 
 /// 세션 종료 커맨드
 #[command]
-pub async fn end_session(
-    app_handle: AppHandle,
-    user_evaluation_score: u8,
-    comm_state: State<'_, Arc<BackendCommunicator>>,
-    session_state_mutex: State<'_, SessionStateArcMutex>,
-    storage_manager_mutex: State<'_, StorageManagerArcMutex>,
-    app_core_state: State<'_, Mutex<AppCore>>,
-) -> Result<(), String> {
-    let (active_session_id, auth_token) = {
-        let mut session_state = session_state_mutex.lock().map_err(|e| e.to_string())?;
-        let storage_manager = storage_manager_mutex.lock().map_err(|e| e.to_string())?;
+pub async fn end_session(app_handle: AppHandle, user_evaluation_score: u8) -> AppResult<()> {
+    end_active_session(&app_handle, user_evaluation_score)
+}
+
+/// 세션 종료 공통 로직 (end_session 커맨드 / 스케줄 자동 종료가 공유)
+/// 로컬 세션 삭제 -> FSM 리셋 -> 오버레이 숨김 -> Outbox(서버 동기화)
+pub fn end_active_session<R: Runtime>(app_handle: &AppHandle<R>, user_evaluation_score: u8) -> AppResult<()> {
+    let session_state_mutex = app_handle.state::<SessionStateArcMutex>();
+    let storage_manager = app_handle.state::<StorageManagerArc>();
+    let app_core_state = app_handle.state::<Mutex<AppCore>>();
 
-        let active_session_id = session_state
+    let active_session_id = {
+        let mut session_state = session_state_mutex.lock()?;
+        let session = session_state
             .as_ref()
-            .map(|s| s.session_id.clone())
-            .ok_or_else(|| "No active session to end.".to_string())?;
+            .ok_or_else(|| AppError::NotFound("No active session to end.".to_string()))?;
+        let active_session_id = session.session_id.clone();
 
-        let token = storage_manager
-            .load_auth_token()
-            .unwrap_or(None)
-            .map(|t| t.0);
+        let now_s = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs();
+        let paused_duration = session.paused_duration_s(now_s);
+        if session.is_paused() {
+            // 일시정지 상태에서 종료하면 진행 중인 구간을 닫음
+            storage_manager.finish_session_pause(&active_session_id, now_s)?;
+        }
 
+        storage_manager.finish_session_history(&active_session_id, now_s, user_evaluation_score, paused_duration)?;
+        crate::managers::sync::enqueue_session_end(
+            &storage_manager,
+            &active_session_id,
+            now_s,
+            user_evaluation_score,
+            paused_duration,
+        )?;
         storage_manager.delete_active_session()?;
         *session_state = None;
 
         if let Ok(mut app_core) = app_core_state.lock() {
             app_core.state_engine.manual_reset();
             app_core.last_inference_result = crate::ai::inference::InferenceResult::Inlier;
+            if let Err(e) = app_core.set_active_intent(None) {
+                eprintln!("Failed to clear session intent: {}", e);
+            }
         }
 
         if let Err(e) = crate::commands::window::hide_overlay(app_handle.clone(), app_core_state) {
@@ -229,38 +226,171 @@ pub async fn end_session(
         }
 
         println!(
-            "Session ID {} successfully ended locally (score: {}).",
-            active_session_id, user_evaluation_score
+            "Session ID {} successfully ended locally (score: {}, paused: {}s).",
+            active_session_id, user_evaluation_score, paused_duration
         );
-        (active_session_id, token)
+        active_session_id
     };
 
-    if let Some(token) = auth_token {
-        let url = format!("{}/sessions/{}", get_api_base_url(), active_session_id);
-        let request_body = SessionEndRequest { user_evaluation_score };
-        let comm_state_clone = comm_state.inner().clone();
+    app_handle.emit("session-ended", &active_session_id).ok();
 
-        spawn(async move {
-            let _ = comm_state_clone
-                .client
-                .put(&url)
-                .bearer_auth(token)
-                .json(&request_body)
-                .send()
-                .await;
-        });
-    }
+    crate::managers::sync::flush_session_outbox(app_handle);
 
     Ok(())
 }
 
+/// 스케줄 세션 연장 커맨드 (종료 경고 알림/위젯에서 호출)
+/// minutes: None이면 사용자 설정(schedule.extend_min) 사용
+#[command]
+pub fn extend_session(
+    app_handle: AppHandle,
+    minutes: Option<u32>,
+    session_state_mutex: State<'_, SessionStateArcMutex>,
+    storage_manager: State<'_, StorageManagerArc>,
+    settings_state: State<'_, SettingsArcMutex>,
+) -> AppResult<ActiveSessionInfo> {
+    let minutes = match minutes {
+        Some(m) => m,
+        None => settings_state.lock()?.schedule.extend_min,
+    };
+    if minutes == 0 {
+        return Err(AppError::InvalidInput("Extension must be at least 1 minute.".to_string()));
+    }
+
+    let info = {
+        let mut session_state = session_state_mutex.lock()?;
+        let session = session_state
+            .as_mut()
+            .ok_or_else(|| AppError::NotFound("No active session to extend.".to_string()))?;
+        let scheduled_end_s = session
+            .scheduled_end_s
+            .ok_or_else(|| AppError::InvalidInput("Session has no scheduled end time.".to_string()))?;
+
+        let now_s = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs();
+
+        // 이미 종료 시각이 지났다면 현재 시각 기준으로 연장
+        session.scheduled_end_s = Some(scheduled_end_s.max(now_s) + minutes as u64 * 60);
+        storage_manager.save_active_session(session)?;
+        session.clone()
+    };
+
+    println!("Session {} extended by {} min.", info.session_id, minutes);
+    app_handle.emit("session-extended", &info).ok();
+
+    Ok(info)
+}
+
+/// 세션 일시정지 커맨드 (커피 브레이크 등)
+/// 일시정지 동안 Core Loop의 FSM/센싱/이벤트 캐싱이 중단되고 타이머가 멈춤
+#[command]
+pub fn pause_session(
+    app_handle: AppHandle,
+    session_state_mutex: State<'_, SessionStateArcMutex>,
+    storage_manager: State<'_, StorageManagerArc>,
+    app_core_state: State<'_, Mutex<AppCore>>,
+) -> AppResult<ActiveSessionInfo> {
+    let info = {
+        let mut session_state = session_state_mutex.lock()?;
+        let session = session_state
+            .as_mut()
+            .ok_or_else(|| AppError::NotFound("No active session to pause.".to_string()))?;
+        if session.is_paused() {
+            return Err(AppError::InvalidInput("Session is already paused.".to_string()));
+        }
+
+        let now_s = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs();
+
+        storage_manager.start_session_pause(&session.session_id, now_s)?;
+        session.paused_at_s = Some(now_s);
+        storage_manager.save_active_session(session)?;
+        session.clone()
+    };
+
+    // 일시정지 중에는 개입하지 않도록 FSM 리셋 + 오버레이 숨김
+    {
+        let mut app_core = app_core_state.lock()?;
+        app_core.state_engine.manual_reset();
+        app_core.last_inference_result = crate::ai::inference::InferenceResult::Inlier;
+    }
+    if let Err(e) = crate::commands::window::hide_overlay(app_handle.clone(), app_core_state) {
+        eprintln!("Warning: Failed to hide overlay on session pause: {}", e);
+    }
+
+    println!("Session {} paused.", info.session_id);
+    app_handle.emit("session-paused", &info).ok();
+
+    Ok(info)
+}
+
+/// 세션 재개 커맨드
+#[command]
+pub fn resume_session(
+    app_handle: AppHandle,
+    session_state_mutex: State<'_, SessionStateArcMutex>,
+    storage_manager: State<'_, StorageManagerArc>,
+    input_stats_mutex: State<'_, InputStatsArcMutex>,
+) -> AppResult<ActiveSessionInfo> {
+    let info = {
+        let mut session_state = session_state_mutex.lock()?;
+        let session = session_state
+            .as_mut()
+            .ok_or_else(|| AppError::NotFound("No active session to resume.".to_string()))?;
+        let paused_at_s = session
+            .paused_at_s
+            .ok_or_else(|| AppError::InvalidInput("Session is not paused.".to_string()))?;
+
+        let now_s = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs();
+
+        storage_manager.finish_session_pause(&session.session_id, now_s)?;
+        session.paused_total_s += now_s.saturating_sub(paused_at_s);
+        session.paused_at_s = None;
+        storage_manager.save_active_session(session)?;
+
+        // 자리를 비운 시간이 Silence(입력 없음)로 집계되지 않도록 입력 타임스탬프 갱신
+        if let Ok(mut input_stats) = input_stats_mutex.lock() {
+            input_stats.last_meaningful_input_timestamp_ms = now_s * 1000;
+            input_stats.last_mouse_move_timestamp_ms = now_s * 1000;
+        }
+
+        session.clone()
+    };
+
+    println!("Session {} resumed (paused total: {}s).", info.session_id, info.paused_total_s);
+    app_handle.emit("session-resumed", &info).ok();
+
+    Ok(info)
+}
+
 /// 타이머 위젯 동기화를 위한 PULL API
 #[command]
 pub fn get_current_session_info(
     session_state_mutex: State<'_, SessionStateArcMutex>,
-) -> Result<Option<ActiveSessionInfo>, String> {
-    let session_state = session_state_mutex
-        .lock()
-        .map_err(|e| format!("State lock error: {}", e))?;
+) -> AppResult<Option<ActiveSessionInfo>> {
+    let session_state = session_state_mutex.lock()?;
     Ok(session_state.clone())
 }
+
+/// 세션 요약 리포트 (FSM 상태별 시간, 개입 횟수, 주요 이탈 앱)
+/// 로컬 기록으로 계산하므로 오프라인 / 이벤트 동기화 이후에도 동작
+#[command]
+pub fn get_session_summary(
+    session_id: String,
+    storage_manager: State<'_, StorageManagerArc>,
+) -> AppResult<SessionSummary> {
+    build_session_summary(&storage_manager, &session_id)
+}
+
+/// 세션 시작 시 선택 가능한 인텐트 프리셋 목록
+#[command]
+pub fn get_intent_presets(
+    app_core_state: State<'_, Mutex<AppCore>>,
+) -> AppResult<Vec<IntentSummary>> {
+    let app_core = app_core_state.lock()?;
+    Ok(app_core.list_intents())
+}
@@ -0,0 +1,47 @@
+// 파일 위치: src-tauri/src/commands/settings.rs
+// 사용자 설정 조회/변경 Tauri 커맨드
+
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Emitter, State};
+
+use crate::core::app::AppCore;
+use crate::managers::settings::UserSettings;
+use crate::utils::error::{AppError, AppResult};
+use crate::{SettingsArcMutex, StorageManagerArc};
+
+/// 현재 적용 중인 사용자 설정 조회
+#[command]
+pub fn get_settings(settings_state: State<'_, SettingsArcMutex>) -> AppResult<UserSettings> {
+    let settings = settings_state.lock()?;
+    Ok(settings.clone())
+}
+
+/// 사용자 설정 변경 커맨드
+/// 검증 -> LSN 저장 -> 실행 중인 StateEngine에 즉시 적용 (재시작 불필요)
+#[command]
+pub fn update_settings(
+    app_handle: AppHandle,
+    settings: UserSettings,
+    settings_state: State<'_, SettingsArcMutex>,
+    storage_manager: State<'_, StorageManagerArc>,
+    app_core_state: State<'_, Mutex<AppCore>>,
+) -> AppResult<UserSettings> {
+    settings.validate().map_err(AppError::InvalidInput)?;
+
+    storage_manager.save_user_settings(&settings)?;
+
+    {
+        let mut app_core = app_core_state.lock()?;
+        app_core.apply_base_state_config(settings.fsm.clone()).map_err(AppError::InvalidInput)?;
+    }
+
+    {
+        let mut current = settings_state.lock()?;
+        *current = settings.clone();
+    }
+
+    println!("Settings updated and applied.");
+    app_handle.emit("settings-changed", &settings).ok();
+
+    Ok(settings)
+}
@@ -0,0 +1,68 @@
+// 파일 위치: src-tauri/src/commands/storage.rs
+// 로컬 저장 공간 사용량 조회 / 데이터 내보내기 / 전체 삭제 Tauri 커맨드
+
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{command, AppHandle, Manager, State};
+
+use crate::managers::erasure::{self, ErasureReport};
+use crate::managers::export::{self, ExportFormat, ExportReport};
+use crate::managers::storage::TableUsage;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::logging;
+use crate::StorageManagerArc;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageUsage {
+    pub tables: Vec<TableUsage>,
+    pub db_file_bytes: u64,
+    pub log_files: u64,
+    pub log_bytes: u64,
+}
+
+/// 테이블별 행 수/크기 + DB 파일 크기 + JSONL 로그 사용량
+#[command]
+pub fn get_storage_usage(
+    app_handle: AppHandle,
+    storage_manager: State<'_, StorageManagerArc>,
+) -> AppResult<StorageUsage> {
+    let tables = storage_manager.get_table_usage()?;
+    let db_file_bytes = storage_manager.get_db_size_bytes()?;
+
+    let log_dir = logging::get_log_dir(&app_handle).map_err(AppError::Platform)?;
+    let (log_files, log_bytes) = logging::log_dir_usage(&log_dir).map_err(AppError::Platform)?;
+
+    Ok(StorageUsage { tables, db_file_bytes, log_files, log_bytes })
+}
+
+/// 기간(로컬 날짜, 양 끝 포함) 데이터를 JSONL/CSV로 내보내기
+/// out_dir 미지정 시 app_data_dir/exports/<from>_<to>
+#[command]
+pub fn export_data(
+    app_handle: AppHandle,
+    from_date: String,
+    to_date: String,
+    format: ExportFormat,
+    out_dir: Option<String>,
+    storage_manager: State<'_, StorageManagerArc>,
+) -> AppResult<ExportReport> {
+    let (from_s, to_s) = export::parse_date_range(&from_date, &to_date)?;
+
+    let out_dir = match out_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| AppError::Platform(format!("Failed to get app_data_dir: {}", e)))?
+            .join("exports")
+            .join(format!("{}_{}", from_date, to_date)),
+    };
+
+    export::export_data(&storage_manager, from_s, to_s, format, &out_dir)
+}
+
+/// 내 데이터 전체 삭제 (LSN 전체, 로그, 내보내기 파일, 개인화 모델 + 서버 삭제 요청)
+#[command]
+pub async fn delete_all_my_data(app_handle: AppHandle) -> AppResult<ErasureReport> {
+    erasure::delete_all_my_data(&app_handle).await
+}
@@ -3,6 +3,8 @@ use std::sync::Mutex;
 use sysinfo::System;
 use tauri::{command, State};
 
+use crate::utils::error::AppResult;
+
 // --- 공유 상태 관리 ---
 pub struct SysinfoState(pub Mutex<System>);
 
@@ -15,8 +17,8 @@ pub struct ProcessSummary {
 #[command]
 pub fn get_all_processes_summary(
     sys_state: State<'_, SysinfoState>,
-) -> Result<Vec<ProcessSummary>, String> {
-    let mut sys_guard = sys_state.0.lock().map_err(|_| "Failed to lock SysinfoState".to_string())?;
+) -> AppResult<Vec<ProcessSummary>> {
+    let mut sys_guard = sys_state.0.lock()?;
     sys_guard.refresh_all();
     let mut processes_summary = Vec::new();
     for (_pid, process) in sys_guard.processes() {
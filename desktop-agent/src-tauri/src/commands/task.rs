@@ -1,27 +1,27 @@
 // 파일 위치: src-tauri/src/commands/task.rs
 // backend_comm.rs에서 분리된 Task 관련 Tauri 커맨드 (U-3 해결)
 
-use tauri::{command, State};
-use crate::{StorageManagerArcMutex, Task};
+use tauri::{command, AppHandle, Emitter, State};
+use crate::managers::storage::{LocalTask, StorageManager};
+use crate::managers::sync::COLLECTION_TASKS;
+use crate::utils::error::{AppError, AppResult};
+use crate::{StorageManagerArc, Task};
+
+// 서버(Task 기본 상태)와 동일한 값
+const TASK_STATUS_PENDING: &str = "pending";
+const TASK_STATUS_COMPLETED: &str = "completed";
 
 /// Task / LSN 데이터 연동
 #[command]
 pub fn get_tasks(
-    storage_manager_mutex: State<'_, StorageManagerArcMutex>,
-) -> Result<Vec<Task>, String> {
-    let storage_manager = storage_manager_mutex.lock().map_err(|e| e.to_string())?;
-
-    let user_id = match storage_manager
-        .load_auth_token()
-        .map_err(|e| e.to_string())?
-    {
+    storage_manager: State<'_, StorageManagerArc>,
+) -> AppResult<Vec<Task>> {
+    let user_id = match storage_manager.load_auth_token()? {
         Some((_, _, _, uid)) => uid,
         None => return Ok(vec![]),
     };
 
-    let local_tasks = storage_manager
-        .get_tasks_by_user(&user_id)
-        .map_err(|e| e.to_string())?;
+    let local_tasks = storage_manager.get_tasks_by_user(&user_id)?;
 
     println!(
         "get_tasks: Found {} tasks for user {}",
@@ -29,24 +29,136 @@ pub fn get_tasks(
         user_id
     );
 
-    let tasks: Vec<Task> = local_tasks
-        .into_iter()
-        .map(|t| Task {
-            id: t.id,
-            user_id: t.user_id,
-            task_name: t.task_name,
-            description: t.description.unwrap_or_default(),
-            due_date: "".to_string(),
-            status: t.status,
-            target_executable: t.target_executable.unwrap_or_default(),
-            target_arguments: t
-                .target_arguments
-                .map(|s| s.split_whitespace().map(|s| s.to_string()).collect())
-                .unwrap_or_default(),
-            created_at: "".to_string(),
-            updated_at: "".to_string(),
-        })
-        .collect();
+    let tasks: Vec<Task> = local_tasks.into_iter().map(to_frontend_task).collect();
 
     Ok(tasks)
 }
+
+/// 로컬 Task 생성 (오프라인 가능, 다음 동기화 때 서버에 생성됨)
+#[command]
+pub fn create_task(
+    app_handle: AppHandle,
+    storage_manager: State<'_, StorageManagerArc>,
+    task_name: String,
+    description: Option<String>,
+    target_executable: Option<String>,
+    target_arguments: Option<String>,
+) -> AppResult<Task> {
+    let task_name = task_name.trim().to_string();
+    if task_name.is_empty() {
+        return Err(AppError::InvalidInput("Task name must not be blank".to_string()));
+    }
+
+    let user_id = logged_in_user_id(&storage_manager)?;
+
+    let task = LocalTask {
+        id: new_task_id()?,
+        user_id,
+        task_name,
+        description: non_blank(description),
+        target_executable: non_blank(target_executable),
+        target_arguments: non_blank(target_arguments),
+        status: TASK_STATUS_PENDING.to_string(),
+        updated_at: None,
+    };
+    storage_manager.save_local_task(&task)?;
+
+    app_handle.emit("data-changed", [COLLECTION_TASKS]).ok();
+    Ok(to_frontend_task(task))
+}
+
+/// 로컬 Task 수정 (전달된 필드만 변경)
+#[command]
+pub fn update_task(
+    app_handle: AppHandle,
+    storage_manager: State<'_, StorageManagerArc>,
+    task_id: String,
+    task_name: Option<String>,
+    description: Option<String>,
+    target_executable: Option<String>,
+    target_arguments: Option<String>,
+) -> AppResult<Task> {
+    let mut task = owned_task(&storage_manager, &task_id)?;
+
+    if let Some(name) = task_name {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::InvalidInput("Task name must not be blank".to_string()));
+        }
+        task.task_name = name;
+    }
+    // 빈 문자열은 값 삭제
+    if let Some(v) = description {
+        task.description = non_blank(Some(v));
+    }
+    if let Some(v) = target_executable {
+        task.target_executable = non_blank(Some(v));
+    }
+    if let Some(v) = target_arguments {
+        task.target_arguments = non_blank(Some(v));
+    }
+    storage_manager.save_local_task(&task)?;
+
+    app_handle.emit("data-changed", [COLLECTION_TASKS]).ok();
+    Ok(to_frontend_task(task))
+}
+
+/// 로컬 Task 완료 처리
+#[command]
+pub fn complete_task(
+    app_handle: AppHandle,
+    storage_manager: State<'_, StorageManagerArc>,
+    task_id: String,
+) -> AppResult<Task> {
+    let mut task = owned_task(&storage_manager, &task_id)?;
+
+    task.status = TASK_STATUS_COMPLETED.to_string();
+    storage_manager.save_local_task(&task)?;
+
+    app_handle.emit("data-changed", [COLLECTION_TASKS]).ok();
+    Ok(to_frontend_task(task))
+}
+
+fn logged_in_user_id(storage_manager: &StorageManager) -> AppResult<String> {
+    match storage_manager.load_auth_token()? {
+        Some((_, _, _, uid)) => Ok(uid),
+        None => Err(AppError::Unauthorized("Login required to edit tasks".to_string())),
+    }
+}
+
+fn owned_task(storage_manager: &StorageManager, task_id: &str) -> AppResult<LocalTask> {
+    let user_id = logged_in_user_id(storage_manager)?;
+    match storage_manager.get_task_by_id(task_id)? {
+        Some(task) if task.user_id == user_id => Ok(task),
+        _ => Err(AppError::NotFound(format!("Task not found: {}", task_id))),
+    }
+}
+
+// 서버(MongoDB ObjectId)와 같은 형식의 ID (24자리 hex) -> 서버가 그대로 _id로 사용, 재전송해도 중복 생성 없음
+fn new_task_id() -> AppResult<String> {
+    let mut bytes = [0u8; 12];
+    getrandom::getrandom(&mut bytes).map_err(|e| AppError::Internal(format!("Failed to generate task id: {}", e)))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value.map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn to_frontend_task(t: LocalTask) -> Task {
+    Task {
+        id: t.id,
+        user_id: t.user_id,
+        task_name: t.task_name,
+        description: t.description.unwrap_or_default(),
+        due_date: "".to_string(),
+        status: t.status,
+        target_executable: t.target_executable.unwrap_or_default(),
+        target_arguments: t
+            .target_arguments
+            .map(|s| s.split_whitespace().map(|s| s.to_string()).collect())
+            .unwrap_or_default(),
+        created_at: "".to_string(),
+        updated_at: t.updated_at.unwrap_or_default(),
+    }
+}
@@ -1,31 +1,19 @@
 use active_win_pos_rs::get_active_window;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::command;
+use tauri::{command, State};
+
+use crate::core::sensor::WindowSensor;
+use crate::utils::error::{AppError, AppResult};
+use crate::WindowSensorArc;
 
 #[cfg(target_os = "windows")]
-use std::ffi::OsString;
-#[cfg(target_os = "windows")]
-use std::os::windows::ffi::OsStringExt;
-
-use windows::core::PWSTR;
-use windows::Win32::Foundation::{
-    BOOL, HWND, LPARAM, MAX_PATH, RECT, TRUE,
-};
-use windows::Win32::Graphics::Gdi::{
-    CombineRgn, CreateRectRgn, CreateRectRgnIndirect, DeleteObject, GetRgnBox, HGDIOBJ, HRGN,
-    NULLREGION, RGN_DIFF, RGN_OR,
-};
-
-use windows::Win32::System::Threading::{
-    OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_FORMAT, PROCESS_QUERY_LIMITED_INFORMATION,
-};
-use windows::Win32::UI::WindowsAndMessaging::{
-    EnumWindows, GetForegroundWindow, GetWindow, GetWindowRect, GetWindowTextLengthW,
-    GetWindowTextW, GetWindowThreadProcessId, IsIconic, IsWindowVisible, GW_OWNER,
-    ShowWindow, SetWindowPos, SW_MINIMIZE, SW_RESTORE, SWP_NOZORDER, SWP_SHOWWINDOW
-};
+pub mod win32;
+#[cfg(target_os = "linux")]
+pub mod x11;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ActiveWindowInfo {
@@ -41,7 +29,7 @@ pub struct ActiveWindowInfo {
     pub height: f64,
 }
 
-pub fn _get_active_window_info_internal() -> Result<ActiveWindowInfo, String> {
+pub fn _get_active_window_info_internal() -> AppResult<ActiveWindowInfo> {
     let timestamp_ms = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_else(|_| std::time::Duration::from_secs(0))
@@ -68,19 +56,19 @@ pub fn _get_active_window_info_internal() -> Result<ActiveWindowInfo, String> {
                 height: active_window.position.height,
             })
         }
-        Err(e) => Err(format!("Failed to get active window info: {:?}", e)),
+        Err(e) => Err(AppError::Platform(format!("Failed to get active window info: {:?}", e))),
     }
 }
 
 #[command]
-pub fn get_current_active_window_info() -> Result<ActiveWindowInfo, String> {
-    _get_active_window_info_internal()
+pub fn get_current_active_window_info(sensor: State<'_, WindowSensorArc>) -> AppResult<ActiveWindowInfo> {
+    sensor.active_window()
 }
 
 const MIN_VISIBLE_WIDTH: i32 = 120;
 const MIN_VISIBLE_HEIGHT: i32 = 100;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WinRect {
     pub left: i32,
     pub top: i32,
@@ -103,262 +91,58 @@ pub struct WorkspaceSnapshot {
     pub windows: Vec<WindowInfo>,
 }
 
-#[cfg(target_os = "windows")]
-fn get_windows_system_paths() -> Vec<String> {
-    let sys_root = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
-    let program_files = std::env::var("ProgramW6432").unwrap_or_else(|_| "C:\\Program Files".to_string());
-    vec![
-        format!("{}\\{}", sys_root, "SYSTEM32").to_lowercase(),
-        format!("{}\\{}", sys_root, "SYSTEMAPPS").to_lowercase(),
-        format!("{}\\{}", sys_root, "EXPLORER.EXE").to_lowercase(),
-        format!("{}\\{}", program_files, "WINDOWSAPPS").to_lowercase(),
-    ]
-}
-
-#[cfg(target_os = "windows")]
-const IGNORED_TITLES: &[&str] = &[
-    "Shell Handwriting Canvas",
-    "Microsoft Text Input Application",
-    "Program Manager",
-    "Settings", 
-];
-
-#[cfg(target_os = "windows")]
-fn get_process_path_from_pid(pid: u32) -> Option<String> {
-    if pid == 0 {
-        return None;
-    }
-
-    unsafe {
-        let handle_result = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid);
-
-        if let Ok(handle) = handle_result {
-            let mut buffer = [0u16; MAX_PATH as usize];
-            let mut size = MAX_PATH;
+static SENSOR_UNAVAILABLE_LOGGED: AtomicBool = AtomicBool::new(false);
 
-            let success = QueryFullProcessImageNameW(
-                handle,
-                PROCESS_NAME_FORMAT(0),
-                PWSTR(buffer.as_mut_ptr()),
-                &mut size,
-            );
-
-            let _ = windows::Win32::Foundation::CloseHandle(handle);
-
-            if success.is_ok() {
-                return OsString::from_wide(&buffer[..size as usize])
-                    .into_string()
-                    .ok();
-            }
+/// 보이는 창 목록. 센서를 쓸 수 없는 환경(Wayland 전용, 헤드리스 등)에서는 빈 목록
+/// (5초마다 반복되지 않도록 실패는 한 번만 기록)
+pub fn visible_windows_or_empty(sensor: &dyn WindowSensor) -> Vec<WindowInfo> {
+    sensor.visible_windows().unwrap_or_else(|e| {
+        if !SENSOR_UNAVAILABLE_LOGGED.swap(true, Ordering::Relaxed) {
+            eprintln!("Vision: Visible window sensor unavailable ({}). Reporting no windows.", e);
         }
-        None
-    }
-}
-
-#[cfg(target_os = "windows")]
-struct EnumContext {
-    windows: Vec<WindowInfo>,
-    foreground_hwnd: HWND,
-    covered_rgn: HRGN,
+        vec![]
+    })
 }
 
-#[cfg(target_os = "windows")]
-unsafe extern "system" fn enum_window_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
-    let context = &mut *(lparam.0 as *mut EnumContext);
-
-    if IsIconic(hwnd).as_bool() {
-        return TRUE;
-    }
-
-    let has_owner = match GetWindow(hwnd, GW_OWNER) {
-        Ok(handle) => handle.0 != std::ptr::null_mut(),
-        Err(_) => false,
-    };
-
-    if IsWindowVisible(hwnd).as_bool() && !has_owner {
-        let mut rect = RECT::default();
-        let _ = GetWindowRect(hwnd, &mut rect);
-        let width = rect.right - rect.left;
-        let height = rect.bottom - rect.top;
-
-        if width < MIN_VISIBLE_WIDTH || height < MIN_VISIBLE_HEIGHT {
-            return TRUE;
-        }
+/// 스냅샷 복구: 스냅샷에 없는 새로운 창(이탈 앱)은 최소화, 저장된 업무 창은 다시 표시하고 위치/크기 복원
+pub fn restore_snapshot(sensor: &dyn WindowSensor, snapshot: &WorkspaceSnapshot) -> AppResult<()> {
+    let saved_hwnds: HashSet<isize> = snapshot.windows.iter().map(|w| w.hwnd).collect();
 
-        let length = GetWindowTextLengthW(hwnd);
-        if length > 0 {
-            let mut buffer = vec![0u16; (length + 1) as usize];
-            let copied_len = GetWindowTextW(hwnd, &mut buffer);
-
-            if copied_len > 0 {
-                let title_raw = OsString::from_wide(&buffer[..copied_len as usize]);
-                if let Ok(title) = title_raw.into_string() {
-                    let trimmed_title = title.trim();
-
-                    if IGNORED_TITLES.contains(&trimmed_title) {
-                        return TRUE;
-                    }
-
-                    let mut pid: u32 = 0;
-                    GetWindowThreadProcessId(hwnd, Some(&mut pid));
-
-                    let mut app_name = String::from("Unknown");
-                    let mut is_system = false;
-
-                    let sys_paths = get_windows_system_paths();
-                    if let Some(path) = get_process_path_from_pid(pid) {
-                        let p = path.to_lowercase();
-                        is_system = sys_paths.iter().any(|sys| p.starts_with(sys));
-
-                        if !is_system {
-                            if let Some(name) = Path::new(&path).file_name() {
-                                app_name = name.to_string_lossy().into_owned();
-                            }
-                        }
-                    }
-
-                    if !is_system {
-                        let current_win_rgn = CreateRectRgnIndirect(&rect);
-                        let visible_part_rgn = CreateRectRgn(0, 0, 0, 0);
-
-                        let region_type = CombineRgn(
-                            visible_part_rgn,
-                            current_win_rgn,
-                            context.covered_rgn,
-                            RGN_DIFF, 
-                        );
-
-                        let mut is_visually_visible = false;
-
-                        if region_type
-                            != windows::Win32::Graphics::Gdi::GDI_REGION_TYPE(NULLREGION.0 as i32)
-                        {
-                            let mut box_rect = RECT::default();
-                            GetRgnBox(visible_part_rgn, &mut box_rect);
-
-                            let visible_w = box_rect.right - box_rect.left;
-                            let visible_h = box_rect.bottom - box_rect.top;
-
-                            if visible_w >= MIN_VISIBLE_WIDTH && visible_h >= MIN_VISIBLE_HEIGHT {
-                                is_visually_visible = true;
-                            }
-                        }
-
-                        if is_visually_visible {
-                            context.windows.push(WindowInfo {
-                                app_name,
-                                title: trimmed_title.to_string(),
-                                is_visible_on_screen: true,
-                                rect: WinRect {
-                                    left: rect.left,
-                                    top: rect.top,
-                                    right: rect.right,
-                                    bottom: rect.bottom,
-                                },
-                                hwnd: hwnd.0 as isize,
-                            });
-
-                            CombineRgn(
-                                context.covered_rgn,
-                                context.covered_rgn,
-                                current_win_rgn,
-                                RGN_OR, 
-                            );
-                        }
-
-                        DeleteObject(HGDIOBJ(current_win_rgn.0));
-                        DeleteObject(HGDIOBJ(visible_part_rgn.0));
-                    }
-                }
+    // 1. 스냅샷에 없는 창 최소화 (우리 앱의 창은 센서가 제외, 실패한 창은 건너뛰고 나머지 계속)
+    for current in sensor.visible_windows()? {
+        if !saved_hwnds.contains(&current.hwnd) {
+            if let Err(e) = sensor.minimize(current.hwnd) {
+                eprintln!("Vision: Failed to minimize '{}': {}", current.title, e);
             }
         }
     }
-    TRUE
-}
-
-pub fn _get_all_visible_windows_internal() -> Vec<WindowInfo> {
-    #[cfg(target_os = "windows")]
-    {
-        unsafe {
-            let foreground_hwnd = GetForegroundWindow();
-            let covered_rgn = CreateRectRgn(0, 0, 0, 0);
 
-            let mut context = EnumContext {
-                windows: Vec::new(),
-                foreground_hwnd,
-                covered_rgn,
-            };
-
-            let lparam = LPARAM(&mut context as *mut _ as isize);
-            let _ = EnumWindows(Some(enum_window_callback), lparam);
-
-            DeleteObject(HGDIOBJ(context.covered_rgn.0));
-
-            context.windows
+    // 2. 스냅샷에 있던 창 복구 (그 사이 닫힌 창은 건너뜀)
+    for saved in &snapshot.windows {
+        if let Err(e) = sensor.restore(saved.hwnd, &saved.rect) {
+            eprintln!("Vision: Failed to restore '{}': {}", saved.title, e);
         }
     }
-
-    #[cfg(not(target_os = "windows"))]
-    {
-        vec![]
-    }
+    Ok(())
 }
 
 #[command]
-pub fn get_visible_windows() -> Result<Vec<WindowInfo>, String> {
-    let windows = _get_all_visible_windows_internal();
-    Ok(windows)
+pub fn get_visible_windows(sensor: State<'_, WindowSensorArc>) -> AppResult<Vec<WindowInfo>> {
+    sensor.visible_windows()
 }
 
 #[command]
-pub fn restore_workspace(state: tauri::State<'_, std::sync::Mutex<crate::core::app::AppCore>>) -> Result<(), String> {
-    let mut core = state.lock().map_err(|e| e.to_string())?;
+pub fn restore_workspace(
+    state: State<'_, std::sync::Mutex<crate::core::app::AppCore>>,
+    sensor: State<'_, WindowSensorArc>,
+) -> AppResult<()> {
+    let core = state.lock()?;
     // 스냅샷을 가져옵니다 (1회용 복구일 수 있지만, 재사용할 수도 있으므로 복사)
     let snapshot = core.last_snapshot.clone();
     drop(core); // 락 해제
 
     if let Some(snap) = snapshot {
-        #[cfg(target_os = "windows")]
-        {
-            let current_windows = _get_all_visible_windows_internal();
-            let saved_hwnds: std::collections::HashSet<isize> = snap.windows.iter().map(|w| w.hwnd).collect();
-            let my_pid = std::process::id();
-
-            // 1. 스냅샷에 없는 새로운 창(이탈 앱) 최소화
-            for cw in current_windows {
-                if !saved_hwnds.contains(&cw.hwnd) {
-                    unsafe {
-                        let mut target_pid: u32 = 0;
-                        windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId(HWND(cw.hwnd as *mut _), Some(&mut target_pid));
-                        
-                        // 우리 앱의 창(오버레이, 메인 윈도우 등)은 강제로 최소화하지 않음
-                        if target_pid != my_pid {
-                            let _ = ShowWindow(HWND(cw.hwnd as *mut _), SW_MINIMIZE);
-                        }
-                    }
-                }
-            }
-
-            // 2. 스냅샷에 있던 업무 창들 위치 복구 및 표시
-            for sw in snap.windows {
-                unsafe {
-                    let hwnd = HWND(sw.hwnd as *mut _);
-                    let width = sw.rect.right - sw.rect.left;
-                    let height = sw.rect.bottom - sw.rect.top;
-                    
-                    let _ = ShowWindow(hwnd, SW_RESTORE);
-                    let _ = SetWindowPos(
-                        hwnd,
-                        HWND(std::ptr::null_mut()),
-                        sw.rect.left,
-                        sw.rect.top,
-                        width,
-                        height,
-                        SWP_NOZORDER | SWP_SHOWWINDOW
-                    );
-                }
-            }
-        }
+        restore_snapshot(sensor.as_ref(), &snap)?;
     }
     Ok(())
 }
@@ -398,4 +182,62 @@ mod tests {
         
         assert_eq!(tokens, expected, "Semantic tokens should not contain duplicates");
     }
+
+    #[test]
+    fn test_restore_snapshot_minimizes_new_windows_and_restores_saved_rects() {
+        use crate::core::sensor::fake::{FakeSensor, SensorCall};
+
+        let rect = |left, top, right, bottom| WinRect { left, top, right, bottom };
+        let editor = FakeSensor::window(1, "Editor", rect(0, 0, 800, 600));
+        let terminal = FakeSensor::window(2, "Terminal", rect(800, 0, 1280, 600));
+        let snapshot = WorkspaceSnapshot { timestamp_ms: 0, windows: vec![editor.clone(), terminal.clone()] };
+
+        // FOCUS 이후 유튜브 창이 맨 위로 오고, Editor는 옮겨졌으며 Terminal은 최소화됨
+        let mut moved_editor = editor.clone();
+        moved_editor.rect = rect(100, 100, 500, 400);
+        let sensor = FakeSensor::new(vec![vec![FakeSensor::window(3, "YouTube", rect(0, 0, 1280, 800)), moved_editor]]);
+
+        restore_snapshot(&sensor, &snapshot).unwrap();
+
+        assert_eq!(
+            sensor.calls(),
+            vec![
+                SensorCall::Minimize(3),
+                SensorCall::Restore(1, rect(0, 0, 800, 600)),
+                SensorCall::Restore(2, rect(800, 0, 1280, 600)),
+            ]
+        );
+        let windows = visible_windows_or_empty(&sensor);
+        assert_eq!(windows.iter().map(|w| w.hwnd).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(windows[0].rect, rect(0, 0, 800, 600));
+        assert_eq!(sensor.active_window().unwrap().title, "Editor");
+    }
+
+    #[test]
+    fn test_restore_snapshot_continues_after_failed_minimize() {
+        use crate::core::sensor::fake::{FakeSensor, SensorCall};
+
+        let rect = |left, top, right, bottom| WinRect { left, top, right, bottom };
+        let editor = FakeSensor::window(1, "Editor", rect(0, 0, 800, 600));
+        let snapshot = WorkspaceSnapshot { timestamp_ms: 0, windows: vec![editor.clone()] };
+
+        // 관리자 권한 창(2)은 최소화 실패 -> 유튜브 창(3) 최소화와 Editor 복구는 계속 진행
+        let sensor = FakeSensor::new(vec![vec![
+            FakeSensor::window(2, "Admin", rect(0, 0, 400, 300)),
+            FakeSensor::window(3, "YouTube", rect(0, 0, 1280, 800)),
+            editor,
+        ]]);
+        sensor.fail_window(2);
+
+        restore_snapshot(&sensor, &snapshot).unwrap();
+
+        assert_eq!(
+            sensor.calls(),
+            vec![
+                SensorCall::Minimize(2),
+                SensorCall::Minimize(3),
+                SensorCall::Restore(1, rect(0, 0, 800, 600)),
+            ]
+        );
+    }
 }
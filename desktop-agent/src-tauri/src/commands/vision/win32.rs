@@ -0,0 +1,259 @@
+// Windows 시각 센서
+// EnumWindows(위 -> 아래 순서)로 창을 훑으며, 위에 있는 창들이 덮은 영역(GDI Region)을 빼고 남은 부분이
+// 최소 크기 이상인 창만 보이는 창으로 판정한다.
+
+use std::ffi::OsString;
+use std::os::windows::ffi::OsStringExt;
+use std::path::Path;
+
+use windows::core::PWSTR;
+use windows::Win32::Foundation::{
+    BOOL, HWND, LPARAM, MAX_PATH, RECT, TRUE,
+};
+use windows::Win32::Graphics::Gdi::{
+    CombineRgn, CreateRectRgn, CreateRectRgnIndirect, DeleteObject, GetRgnBox, HGDIOBJ, HRGN,
+    NULLREGION, RGN_DIFF, RGN_OR,
+};
+
+use windows::Win32::System::Threading::{
+    OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_FORMAT, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    EnumWindows, GetForegroundWindow, GetWindow, GetWindowRect, GetWindowTextLengthW,
+    GetWindowTextW, GetWindowThreadProcessId, IsIconic, IsWindowVisible, GW_OWNER,
+    ShowWindow, SetWindowPos, SW_MINIMIZE, SW_RESTORE, SWP_NOZORDER, SWP_SHOWWINDOW
+};
+
+use super::{ActiveWindowInfo, WinRect, WindowInfo, MIN_VISIBLE_HEIGHT, MIN_VISIBLE_WIDTH};
+use crate::core::sensor::WindowSensor;
+use crate::utils::error::{AppError, AppResult};
+
+pub struct Win32Sensor;
+
+impl WindowSensor for Win32Sensor {
+    fn active_window(&self) -> AppResult<ActiveWindowInfo> {
+        super::_get_active_window_info_internal()
+    }
+
+    fn visible_windows(&self) -> AppResult<Vec<WindowInfo>> {
+        unsafe {
+            let foreground_hwnd = GetForegroundWindow();
+            let covered_rgn = CreateRectRgn(0, 0, 0, 0);
+
+            let mut context = EnumContext {
+                windows: Vec::new(),
+                foreground_hwnd,
+                covered_rgn,
+            };
+
+            let lparam = LPARAM(&mut context as *mut _ as isize);
+            let _ = EnumWindows(Some(enum_window_callback), lparam);
+
+            DeleteObject(HGDIOBJ(context.covered_rgn.0));
+
+            Ok(context.windows)
+        }
+    }
+
+    fn minimize(&self, window: isize) -> AppResult<()> {
+        unsafe {
+            let hwnd = HWND(window as *mut _);
+            let mut target_pid: u32 = 0;
+            GetWindowThreadProcessId(hwnd, Some(&mut target_pid));
+
+            // 우리 앱의 창(오버레이, 메인 윈도우 등)은 강제로 최소화하지 않음
+            if target_pid != std::process::id() {
+                let _ = ShowWindow(hwnd, SW_MINIMIZE);
+            }
+        }
+        Ok(())
+    }
+
+    fn restore(&self, window: isize, rect: &WinRect) -> AppResult<()> {
+        unsafe {
+            let hwnd = HWND(window as *mut _);
+            let width = rect.right - rect.left;
+            let height = rect.bottom - rect.top;
+
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+            SetWindowPos(
+                hwnd,
+                HWND(std::ptr::null_mut()),
+                rect.left,
+                rect.top,
+                width,
+                height,
+                SWP_NOZORDER | SWP_SHOWWINDOW
+            )
+            .map_err(|e| AppError::Platform(format!("Failed to restore window {}: {}", window, e)))
+        }
+    }
+}
+
+fn get_windows_system_paths() -> Vec<String> {
+    let sys_root = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
+    let program_files = std::env::var("ProgramW6432").unwrap_or_else(|_| "C:\\Program Files".to_string());
+    vec![
+        format!("{}\\{}", sys_root, "SYSTEM32").to_lowercase(),
+        format!("{}\\{}", sys_root, "SYSTEMAPPS").to_lowercase(),
+        format!("{}\\{}", sys_root, "EXPLORER.EXE").to_lowercase(),
+        format!("{}\\{}", program_files, "WINDOWSAPPS").to_lowercase(),
+    ]
+}
+
+const IGNORED_TITLES: &[&str] = &[
+    "Shell Handwriting Canvas",
+    "Microsoft Text Input Application",
+    "Program Manager",
+    "Settings", 
+];
+
+fn get_process_path_from_pid(pid: u32) -> Option<String> {
+    if pid == 0 {
+        return None;
+    }
+
+    unsafe {
+        let handle_result = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid);
+
+        if let Ok(handle) = handle_result {
+            let mut buffer = [0u16; MAX_PATH as usize];
+            let mut size = MAX_PATH;
+
+            let success = QueryFullProcessImageNameW(
+                handle,
+                PROCESS_NAME_FORMAT(0),
+                PWSTR(buffer.as_mut_ptr()),
+                &mut size,
+            );
+
+            let _ = windows::Win32::Foundation::CloseHandle(handle);
+
+            if success.is_ok() {
+                return OsString::from_wide(&buffer[..size as usize])
+                    .into_string()
+                    .ok();
+            }
+        }
+        None
+    }
+}
+
+struct EnumContext {
+    windows: Vec<WindowInfo>,
+    foreground_hwnd: HWND,
+    covered_rgn: HRGN,
+}
+
+unsafe extern "system" fn enum_window_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    let context = &mut *(lparam.0 as *mut EnumContext);
+
+    if IsIconic(hwnd).as_bool() {
+        return TRUE;
+    }
+
+    let has_owner = match GetWindow(hwnd, GW_OWNER) {
+        Ok(handle) => handle.0 != std::ptr::null_mut(),
+        Err(_) => false,
+    };
+
+    if IsWindowVisible(hwnd).as_bool() && !has_owner {
+        let mut rect = RECT::default();
+        let _ = GetWindowRect(hwnd, &mut rect);
+        let width = rect.right - rect.left;
+        let height = rect.bottom - rect.top;
+
+        if width < MIN_VISIBLE_WIDTH || height < MIN_VISIBLE_HEIGHT {
+            return TRUE;
+        }
+
+        let length = GetWindowTextLengthW(hwnd);
+        if length > 0 {
+            let mut buffer = vec![0u16; (length + 1) as usize];
+            let copied_len = GetWindowTextW(hwnd, &mut buffer);
+
+            if copied_len > 0 {
+                let title_raw = OsString::from_wide(&buffer[..copied_len as usize]);
+                if let Ok(title) = title_raw.into_string() {
+                    let trimmed_title = title.trim();
+
+                    if IGNORED_TITLES.contains(&trimmed_title) {
+                        return TRUE;
+                    }
+
+                    let mut pid: u32 = 0;
+                    GetWindowThreadProcessId(hwnd, Some(&mut pid));
+
+                    let mut app_name = String::from("Unknown");
+                    let mut is_system = false;
+
+                    let sys_paths = get_windows_system_paths();
+                    if let Some(path) = get_process_path_from_pid(pid) {
+                        let p = path.to_lowercase();
+                        is_system = sys_paths.iter().any(|sys| p.starts_with(sys));
+
+                        if !is_system {
+                            if let Some(name) = Path::new(&path).file_name() {
+                                app_name = name.to_string_lossy().into_owned();
+                            }
+                        }
+                    }
+
+                    if !is_system {
+                        let current_win_rgn = CreateRectRgnIndirect(&rect);
+                        let visible_part_rgn = CreateRectRgn(0, 0, 0, 0);
+
+                        let region_type = CombineRgn(
+                            visible_part_rgn,
+                            current_win_rgn,
+                            context.covered_rgn,
+                            RGN_DIFF, 
+                        );
+
+                        let mut is_visually_visible = false;
+
+                        if region_type
+                            != windows::Win32::Graphics::Gdi::GDI_REGION_TYPE(NULLREGION.0 as i32)
+                        {
+                            let mut box_rect = RECT::default();
+                            GetRgnBox(visible_part_rgn, &mut box_rect);
+
+                            let visible_w = box_rect.right - box_rect.left;
+                            let visible_h = box_rect.bottom - box_rect.top;
+
+                            if visible_w >= MIN_VISIBLE_WIDTH && visible_h >= MIN_VISIBLE_HEIGHT {
+                                is_visually_visible = true;
+                            }
+                        }
+
+                        if is_visually_visible {
+                            context.windows.push(WindowInfo {
+                                app_name,
+                                title: trimmed_title.to_string(),
+                                is_visible_on_screen: true,
+                                rect: WinRect {
+                                    left: rect.left,
+                                    top: rect.top,
+                                    right: rect.right,
+                                    bottom: rect.bottom,
+                                },
+                                hwnd: hwnd.0 as isize,
+                            });
+
+                            CombineRgn(
+                                context.covered_rgn,
+                                context.covered_rgn,
+                                current_win_rgn,
+                                RGN_OR, 
+                            );
+                        }
+
+                        DeleteObject(HGDIOBJ(current_win_rgn.0));
+                        DeleteObject(HGDIOBJ(visible_part_rgn.0));
+                    }
+                }
+            }
+        }
+    }
+    TRUE
+}
@@ -0,0 +1,411 @@
+// Linux(X11) 시각 센서
+// _NET_CLIENT_LIST_STACKING(아래 -> 위 순서)을 위에서부터 훑으며, Windows 구현(GDI Region)과 같은 기준으로
+// "위에 있는 창들에 가려지고 남은 영역"이 최소 크기 이상인 창만 보이는 창으로 판정한다.
+// 창 관리자(EWMH)가 없으면 루트 창의 자식 목록(query_tree, 같은 쌓임 순서)을 대신 사용한다.
+// Wayland 세션에서는 XWayland로 실행된 창만 보인다.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ClientMessageEvent, ConfigureWindowAux, ConnectionExt, EventMask, MapState, Window,
+};
+use x11rb::rust_connection::RustConnection;
+
+use super::{ActiveWindowInfo, WinRect, WindowInfo, MIN_VISIBLE_HEIGHT, MIN_VISIBLE_WIDTH};
+use crate::core::sensor::WindowSensor;
+use crate::utils::error::AppResult;
+
+x11rb::atom_manager! {
+    pub Atoms: AtomsCookie {
+        _NET_CLIENT_LIST_STACKING,
+        _NET_WM_NAME,
+        _NET_WM_PID,
+        _NET_WM_STATE,
+        _NET_WM_STATE_HIDDEN,
+        _NET_WM_WINDOW_TYPE,
+        _NET_WM_WINDOW_TYPE_DESKTOP,
+        _NET_WM_WINDOW_TYPE_DOCK,
+        WM_CHANGE_STATE,
+        UTF8_STRING,
+    }
+}
+
+// ICCCM WM_CHANGE_STATE 요청 값 (최소화)
+const ICONIC_STATE: u32 = 3;
+// 문자열 속성은 4KB, 창 목록은 4096개까지만 읽음 (단위: 32비트)
+const MAX_STRING_LONGS: u32 = 1024;
+const MAX_LIST_LONGS: u32 = 4096;
+
+struct X11Session {
+    conn: RustConnection,
+    root: Window,
+    atoms: Atoms,
+}
+
+impl X11Session {
+    fn connect(display: Option<&str>) -> AppResult<Self> {
+        let (conn, screen_num) = x11rb::connect(display)?;
+        let root = conn.setup().roots[screen_num].root;
+        let atoms = Atoms::new(&conn)?.reply()?;
+        Ok(X11Session { conn, root, atoms })
+    }
+}
+
+/// X11 창 센서. 연결은 처음 사용할 때 맺고 재사용하며, 호출이 실패하면 끊고 다음 호출에서 다시 연결 (X 서버 재시작 대비)
+#[derive(Default)]
+pub struct X11Sensor {
+    display: Option<String>,
+    session: Mutex<Option<X11Session>>,
+}
+
+impl X11Sensor {
+    /// DISPLAY 환경 변수의 X 서버
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 지정한 디스플레이 (예: ":99")
+    pub fn with_display(display: &str) -> Self {
+        X11Sensor { display: Some(display.to_string()), session: Mutex::new(None) }
+    }
+
+    fn with_session<T>(&self, f: impl FnOnce(&X11Session) -> AppResult<T>) -> AppResult<T> {
+        let mut session = self.session.lock()?;
+        if session.is_none() {
+            *session = Some(X11Session::connect(self.display.as_deref())?);
+        }
+        let result = f(session.as_ref().expect("session connected above"));
+        if result.is_err() {
+            *session = None;
+        }
+        result
+    }
+}
+
+impl WindowSensor for X11Sensor {
+    fn active_window(&self) -> AppResult<ActiveWindowInfo> {
+        super::_get_active_window_info_internal()
+    }
+
+    fn visible_windows(&self) -> AppResult<Vec<WindowInfo>> {
+        self.with_session(|s| collect_visible_windows(&s.conn, s.root, &s.atoms))
+    }
+
+    fn minimize(&self, window: isize) -> AppResult<()> {
+        self.with_session(|s| minimize_window(&s.conn, s.root, &s.atoms, window as Window))
+    }
+
+    fn restore(&self, window: isize, rect: &WinRect) -> AppResult<()> {
+        self.with_session(|s| restore_window(&s.conn, window as Window, rect))
+    }
+}
+
+pub(crate) fn collect_visible_windows<C: Connection>(conn: &C, root: Window, atoms: &Atoms) -> AppResult<Vec<WindowInfo>> {
+    let mut stacking = property32(conn, root, atoms._NET_CLIENT_LIST_STACKING, AtomEnum::WINDOW)?;
+    if stacking.is_empty() {
+        stacking = conn.query_tree(root)?.reply()?.children;
+    }
+
+    let mut windows = Vec::new();
+    let mut covered: Vec<WinRect> = Vec::new();
+
+    // 위에 있는 창부터 (목록은 아래 -> 위 순서)
+    for &window in stacking.iter().rev() {
+        // 이미 닫힌 창 등 개별 창 조회 실패는 건너뜀
+        let Ok(Some((title, rect))) = candidate(conn, root, atoms, window) else {
+            continue;
+        };
+
+        if visible_bounds(&rect, &covered).is_some() {
+            windows.push(WindowInfo {
+                app_name: app_name(conn, atoms, window).unwrap_or_else(|| String::from("Unknown")),
+                title,
+                is_visible_on_screen: true,
+                rect: rect.clone(),
+                hwnd: window as isize,
+            });
+            covered.push(rect);
+        }
+    }
+    Ok(windows)
+}
+
+/// 창 관리자에 최소화 요청 (우리 앱의 창은 제외)
+fn minimize_window<C: Connection>(conn: &C, root: Window, atoms: &Atoms, window: Window) -> AppResult<()> {
+    if window_pid(conn, atoms, window).ok().flatten() == Some(std::process::id()) {
+        return Ok(());
+    }
+    let event = ClientMessageEvent::new(32, window, atoms.WM_CHANGE_STATE, [ICONIC_STATE, 0, 0, 0, 0]);
+    conn.send_event(
+        false,
+        root,
+        EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+        event,
+    )?;
+    conn.flush()?;
+    Ok(())
+}
+
+/// 창을 다시 표시하고 위치/크기 복원 (그 사이 닫힌 창의 오류는 비동기로 무시됨)
+fn restore_window<C: Connection>(conn: &C, window: Window, rect: &WinRect) -> AppResult<()> {
+    conn.map_window(window)?;
+    conn.configure_window(
+        window,
+        &ConfigureWindowAux::new()
+            .x(rect.left)
+            .y(rect.top)
+            .width((rect.right - rect.left).max(1) as u32)
+            .height((rect.bottom - rect.top).max(1) as u32),
+    )?;
+    conn.flush()?;
+    Ok(())
+}
+
+/// 보이는 창 후보 (제목, 화면 좌표). 숨김/최소화, 패널·데스크톱, 소유 창(대화상자), 너무 작은 창, 제목 없는 창은 제외
+fn candidate<C: Connection>(conn: &C, root: Window, atoms: &Atoms, window: Window) -> AppResult<Option<(String, WinRect)>> {
+    if conn.get_window_attributes(window)?.reply()?.map_state != MapState::VIEWABLE {
+        return Ok(None);
+    }
+    if property32(conn, window, atoms._NET_WM_STATE, AtomEnum::ATOM)?.contains(&atoms._NET_WM_STATE_HIDDEN) {
+        return Ok(None);
+    }
+    let window_types = property32(conn, window, atoms._NET_WM_WINDOW_TYPE, AtomEnum::ATOM)?;
+    if window_types.contains(&atoms._NET_WM_WINDOW_TYPE_DESKTOP) || window_types.contains(&atoms._NET_WM_WINDOW_TYPE_DOCK) {
+        return Ok(None);
+    }
+    if !property32(conn, window, AtomEnum::WM_TRANSIENT_FOR.into(), AtomEnum::WINDOW)?.is_empty() {
+        return Ok(None);
+    }
+
+    let geometry = conn.get_geometry(window)?.reply()?;
+    if i32::from(geometry.width) < MIN_VISIBLE_WIDTH || i32::from(geometry.height) < MIN_VISIBLE_HEIGHT {
+        return Ok(None);
+    }
+
+    let title = match property_string(conn, window, atoms._NET_WM_NAME, atoms.UTF8_STRING)? {
+        Some(title) => title,
+        None => property_string(conn, window, AtomEnum::WM_NAME.into(), AtomEnum::STRING)?.unwrap_or_default(),
+    };
+    let title = title.trim();
+    if title.is_empty() {
+        return Ok(None);
+    }
+
+    // 재부모화(창 관리자 프레임) 여부와 관계없이 루트 기준 좌표로 변환
+    let origin = conn.translate_coordinates(window, root, 0, 0)?.reply()?;
+    let (left, top) = (i32::from(origin.dst_x), i32::from(origin.dst_y));
+    let rect = WinRect {
+        left,
+        top,
+        right: left + i32::from(geometry.width),
+        bottom: top + i32::from(geometry.height),
+    };
+    Ok(Some((title.to_string(), rect)))
+}
+
+/// 실행 파일 이름 (/proc/<pid>/exe), 실패하면 WM_CLASS의 class 이름
+fn app_name<C: Connection>(conn: &C, atoms: &Atoms, window: Window) -> Option<String> {
+    let from_pid = window_pid(conn, atoms, window)
+        .ok()
+        .flatten()
+        .and_then(|pid| std::fs::read_link(format!("/proc/{}/exe", pid)).ok())
+        .and_then(|path| Path::new(&path).file_name().map(|name| name.to_string_lossy().into_owned()));
+    if from_pid.is_some() {
+        return from_pid;
+    }
+
+    // WM_CLASS = "instance\0class\0"
+    let class = property_string(conn, window, AtomEnum::WM_CLASS.into(), AtomEnum::STRING).ok().flatten()?;
+    class.split('\0').rfind(|s| !s.is_empty()).map(str::to_string)
+}
+
+fn window_pid<C: Connection>(conn: &C, atoms: &Atoms, window: Window) -> AppResult<Option<u32>> {
+    Ok(property32(conn, window, atoms._NET_WM_PID, AtomEnum::CARDINAL)?.first().copied())
+}
+
+fn property32<C: Connection>(conn: &C, window: Window, property: Atom, type_: impl Into<Atom>) -> AppResult<Vec<u32>> {
+    let reply = conn.get_property(false, window, property, type_, 0, MAX_LIST_LONGS)?.reply()?;
+    Ok(reply.value32().map(|values| values.collect()).unwrap_or_default())
+}
+
+fn property_string<C: Connection>(conn: &C, window: Window, property: Atom, type_: impl Into<Atom>) -> AppResult<Option<String>> {
+    let reply = conn.get_property(false, window, property, type_, 0, MAX_STRING_LONGS)?.reply()?;
+    Ok(reply.value8().map(|bytes| String::from_utf8_lossy(&bytes.collect::<Vec<u8>>()).into_owned()))
+}
+
+/// rect에서 covered 영역들을 뺀 나머지의 외곽 사각형 (GetRgnBox와 동일). 최소 크기 미만이면 None
+pub(crate) fn visible_bounds(rect: &WinRect, covered: &[WinRect]) -> Option<WinRect> {
+    let mut parts = vec![rect.clone()];
+    for cover in covered {
+        parts = parts.iter().flat_map(|part| subtract(part, cover)).collect();
+        if parts.is_empty() {
+            return None;
+        }
+    }
+
+    let bounds = WinRect {
+        left: parts.iter().map(|r| r.left).min()?,
+        top: parts.iter().map(|r| r.top).min()?,
+        right: parts.iter().map(|r| r.right).max()?,
+        bottom: parts.iter().map(|r| r.bottom).max()?,
+    };
+    let big_enough = bounds.right - bounds.left >= MIN_VISIBLE_WIDTH && bounds.bottom - bounds.top >= MIN_VISIBLE_HEIGHT;
+    big_enough.then_some(bounds)
+}
+
+/// rect - cover 를 겹치지 않는 최대 4개의 사각형으로 (위/아래 띠 + 좌/우 조각)
+fn subtract(rect: &WinRect, cover: &WinRect) -> Vec<WinRect> {
+    let (left, right) = (rect.left.max(cover.left), rect.right.min(cover.right));
+    let (top, bottom) = (rect.top.max(cover.top), rect.bottom.min(cover.bottom));
+    if left >= right || top >= bottom {
+        return vec![rect.clone()]; // 겹치지 않음
+    }
+
+    let pieces = [
+        WinRect { left: rect.left, top: rect.top, right: rect.right, bottom: top },
+        WinRect { left: rect.left, top: bottom, right: rect.right, bottom: rect.bottom },
+        WinRect { left: rect.left, top, right: left, bottom },
+        WinRect { left: right, top, right: rect.right, bottom },
+    ];
+    pieces.into_iter().filter(|r| r.left < r.right && r.top < r.bottom).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use x11rb::protocol::xproto::{CreateWindowAux, PropMode, WindowClass};
+    use x11rb::rust_connection::RustConnection;
+    use x11rb::wrapper::ConnectionExt as _;
+    use x11rb::COPY_DEPTH_FROM_PARENT;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> WinRect {
+        WinRect { left, top, right, bottom }
+    }
+
+    #[test]
+    fn test_visible_bounds_subtracts_covering_windows() {
+        let window = rect(0, 0, 800, 600);
+
+        assert!(visible_bounds(&window, &[]).is_some());
+        assert!(visible_bounds(&window, &[rect(-10, -10, 900, 700)]).is_none()); // 완전히 가려짐
+
+        // 오른쪽 700px이 가려지면 남은 폭 100px < 최소 폭
+        assert!(visible_bounds(&window, &[rect(100, 0, 800, 600)]).is_none());
+
+        // 가운데가 가려져도 외곽 사각형은 그대로 (GetRgnBox 기준)
+        let bounds = visible_bounds(&window, &[rect(200, 200, 600, 400)]).unwrap();
+        assert_eq!((bounds.left, bounds.top, bounds.right, bounds.bottom), (0, 0, 800, 600));
+
+        // 두 창이 나눠서 가리고 아래쪽 띠만 남음
+        let bounds = visible_bounds(&window, &[rect(0, 0, 400, 450), rect(400, 0, 800, 450)]).unwrap();
+        assert_eq!((bounds.top, bounds.bottom), (450, 600));
+    }
+
+    /// 테스트용 Xvfb (-displayfd로 빈 디스플레이 번호를 받음). 실행할 수 없으면 None
+    struct Xvfb(Child);
+
+    impl Xvfb {
+        fn start() -> Option<(Self, String)> {
+            let mut child = Command::new("Xvfb")
+                .args(["-displayfd", "1", "-screen", "0", "1280x800x24", "-nolisten", "tcp"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut line = String::new();
+            BufReader::new(child.stdout.take()?).read_line(&mut line).ok()?;
+            Some((Xvfb(child), format!(":{}", line.trim())))
+        }
+    }
+
+    impl Drop for Xvfb {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    fn spawn_window(conn: &RustConnection, root: Window, atoms: &Atoms, title: &str, r: WinRect) -> Window {
+        let window = conn.generate_id().unwrap();
+        conn.create_window(
+            COPY_DEPTH_FROM_PARENT,
+            window,
+            root,
+            r.left as i16,
+            r.top as i16,
+            (r.right - r.left) as u16,
+            (r.bottom - r.top) as u16,
+            0,
+            WindowClass::INPUT_OUTPUT,
+            0,
+            &CreateWindowAux::new(),
+        )
+        .unwrap();
+        conn.change_property8(PropMode::REPLACE, window, atoms._NET_WM_NAME, atoms.UTF8_STRING, title.as_bytes())
+            .unwrap();
+        conn.change_property32(PropMode::REPLACE, window, atoms._NET_WM_PID, AtomEnum::CARDINAL, &[std::process::id()])
+            .unwrap();
+        conn.map_window(window).unwrap();
+        window
+    }
+
+    // Xvfb가 필요하므로 기본 실행에서 제외 (skipped로 표시): cargo test -- --ignored
+    #[test]
+    #[ignore = "requires Xvfb"]
+    fn test_xvfb_enumerates_visible_windows_in_stacking_order() {
+        let (_xvfb, display) = Xvfb::start().expect("Failed to start Xvfb (is it installed?)");
+        let (conn, screen_num) = x11rb::connect(Some(&display)).unwrap();
+        let root = conn.setup().roots[screen_num].root;
+        let atoms = Atoms::new(&conn).unwrap().reply().unwrap();
+
+        let browser = spawn_window(&conn, root, &atoms, "Browser", rect(0, 0, 600, 400));
+        let editor = spawn_window(&conn, root, &atoms, "Editor", rect(0, 0, 640, 480));
+        let terminal = spawn_window(&conn, root, &atoms, "Terminal", rect(700, 100, 1200, 500));
+        let sliver = spawn_window(&conn, root, &atoms, "Sliver", rect(600, 0, 740, 300)); // 2.에서는 Editor/Terminal에 대부분 가려짐
+        let tiny = spawn_window(&conn, root, &atoms, "Tiny", rect(900, 600, 960, 660));
+        let hidden = spawn_window(&conn, root, &atoms, "Hidden", rect(0, 500, 400, 800));
+        conn.unmap_window(hidden).unwrap();
+
+        // 1. 창 관리자 없음: query_tree 순서 (나중에 만든 창이 위)
+        conn.sync().unwrap();
+        let titles = |windows: Vec<WindowInfo>| windows.into_iter().map(|w| w.title).collect::<Vec<_>>();
+        let windows = collect_visible_windows(&conn, root, &atoms).unwrap();
+        assert_eq!(titles(windows.clone()), vec!["Sliver", "Terminal", "Editor"]); // Browser는 Editor에 완전히 가려짐
+        assert_eq!(windows[2].hwnd, editor as isize);
+        assert_eq!((windows[2].rect.right, windows[2].rect.bottom), (640, 480));
+        assert!(!windows[0].app_name.is_empty());
+
+        // 2. _NET_CLIENT_LIST_STACKING이 있으면 그 순서를 따름 (Browser를 맨 위, Sliver를 Terminal 아래로)
+        conn.change_property32(
+            PropMode::REPLACE,
+            root,
+            atoms._NET_CLIENT_LIST_STACKING,
+            AtomEnum::WINDOW,
+            &[tiny, sliver, terminal, editor, browser],
+        )
+        .unwrap();
+        conn.sync().unwrap();
+        let windows = collect_visible_windows(&conn, root, &atoms).unwrap();
+        assert_eq!(titles(windows), vec!["Browser", "Editor", "Terminal"]);
+
+        // 3. 센서로 숨겨진 창 복원 (맨 위로 쌓인 것으로 설정)
+        let sensor = X11Sensor::with_display(&display);
+        sensor.restore(hidden as isize, &rect(0, 500, 400, 800)).unwrap();
+        conn.change_property32(
+            PropMode::REPLACE,
+            root,
+            atoms._NET_CLIENT_LIST_STACKING,
+            AtomEnum::WINDOW,
+            &[tiny, sliver, terminal, editor, browser, hidden],
+        )
+        .unwrap();
+        conn.sync().unwrap();
+        let windows = sensor.visible_windows().unwrap();
+        assert_eq!(windows[0].title, "Hidden");
+        assert_eq!(windows[0].rect, rect(0, 500, 400, 800));
+    }
+}
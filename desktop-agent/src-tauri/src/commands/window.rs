@@ -1,24 +1,25 @@
 use tauri::{AppHandle, Manager, Runtime, State};
 use std::sync::Mutex;
 use crate::core::app::AppCore;
+use crate::utils::error::{AppError, AppResult};
 
 /// 'overlay' 창을 숨기고, FSM 상태를 리셋하는 Tauri 커맨드
 #[tauri::command]
 pub fn hide_overlay<R: Runtime>(
     app_handle: AppHandle<R>,
     state: State<Mutex<AppCore>>, // [New] AppCore 주입
-) -> Result<(), String> {
+) -> AppResult<()> {
     
     // 1. [Logic] FSM 상태 강제 리셋 (이게 없으면 1초 뒤에 창이 또 뜸!)
     {
-        let mut app = state.lock().map_err(|_| "Failed to lock AppCore")?;
+        let mut app = state.lock()?;
         app.state_engine.manual_reset();
         println!("GUI: Overlay hidden, State reset.");
     }
 
     // 2. 창 숨기기
     if let Some(overlay_window) = app_handle.get_webview_window("overlay") {
-        overlay_window.hide().map_err(|e| e.to_string())?;
+        overlay_window.hide()?;
         // [안전장치] 숨길 때 차단 모드(false)로 복구해둬야 다음 번에 클릭 가능
         let _ = overlay_window.set_ignore_cursor_events(false); 
         Ok(())
@@ -32,13 +33,13 @@ pub fn hide_overlay<R: Runtime>(
 
 /// 1. 오버레이 표시 (강제 최상단)
 #[tauri::command]
-pub fn show_overlay<R: Runtime>(app_handle: AppHandle<R>) -> Result<(), String> {
+pub fn show_overlay<R: Runtime>(app_handle: AppHandle<R>) -> AppResult<()> {
     if let Some(overlay_window) = app_handle.get_webview_window("overlay") {
-        overlay_window.show().map_err(|e| e.to_string())?;
-        overlay_window.set_always_on_top(true).map_err(|e| e.to_string())?;
+        overlay_window.show()?;
+        overlay_window.set_always_on_top(true)?;
         Ok(())
     } else {
-        Err("Overlay window not found".to_string())
+        Err(AppError::Platform("Overlay window not found".to_string()))
     }
 }
 
@@ -49,13 +50,12 @@ pub fn show_overlay<R: Runtime>(app_handle: AppHandle<R>) -> Result<(), String>
 pub fn set_overlay_ignore_cursor_events<R: Runtime>(
     app_handle: AppHandle<R>,
     ignore: bool,
-) -> Result<(), String> {
+) -> AppResult<()> {
     if let Some(overlay_window) = app_handle.get_webview_window("overlay") {
         // Tauri v2 API: set_ignore_cursor_events
-        overlay_window.set_ignore_cursor_events(ignore)
-            .map_err(|e| e.to_string())?;
+        overlay_window.set_ignore_cursor_events(ignore)?;
         Ok(())
     } else {
-        Err("Overlay window not found".to_string())
+        Err(AppError::Platform("Overlay window not found".to_string()))
     }
 }
\ No newline at end of file
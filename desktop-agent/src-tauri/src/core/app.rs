@@ -1,11 +1,18 @@
 // 파일 위치: src-tauri/src/app_core.rs
 
 use crate::{
-    commands::{self},
-    core::state::{StateEngine, InterventionTrigger},
+    commands::{self, input::InputStats, vision::ActiveWindowInfo},
+    core::goal,
+    core::sensor::WindowSensor,
+    core::intent::{self, IntentMap, IntentSummary},
+    managers::settings::GoalReachedAction,
+    managers::storage::StorageManager,
+    core::state::{StateConfig, StateEngine, InterventionTrigger},
     InputStatsArcMutex,
     SessionStateArcMutex,
-    StorageManagerArcMutex,
+    SettingsArcMutex,
+    StorageManagerArc,
+    WindowSensorArc,
     ai::inference::InferenceEngine
 };
 use std::thread;
@@ -13,6 +20,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager, Runtime, WebviewUrl, WebviewWindowBuilder};
 use tauri::path::BaseDirectory;
+use tauri_plugin_notification::NotificationExt;
 use uuid::Uuid;
 use std::collections::{HashMap, VecDeque};
 
@@ -49,6 +57,43 @@ pub struct AppCore {
 
     // 최근 평가 대상 창의 토큰 (오버레이 클릭 시 활성 창이 오버레이인 문제 해결용)
     pub last_evaluated_tokens: String,
+
+    // 직전 센싱 시점의 활성 앱 (컨텍스트 스위칭 감지용, 휴식 권장 타이밍)
+    pub last_active_app: String,
+
+    // 인텐트 프리셋 (intent_map.json) 및 현재 세션의 인텐트
+    pub intent_map: IntentMap,
+    pub active_intent: Option<String>,
+
+    // 사용자 설정의 FSM 기본값 (인텐트 배속 정책 적용 전)
+    pub base_state_config: StateConfig,
+}
+
+// 번들 리소스(resources/models)에 포함된 기본 ML 아티팩트
+const BUNDLED_MODEL_FILES: [&str; 4] = [
+    "personal_model.onnx",
+    "scaler_params.json",
+    "global_map.json",
+    "intent_map.json",
+];
+
+/// 번들 기본 모델로 model_dir의 아티팩트를 덮어쓰기 (복사한 파일명 반환)
+/// 개발 모드 시작 시 / 전체 데이터 삭제 후 개인화 모델 초기화에 사용
+pub fn restore_bundled_models<R: Runtime>(app_handle: &AppHandle<R>, model_dir: &std::path::Path) -> Vec<String> {
+    let mut restored = Vec::new();
+    for file_name in BUNDLED_MODEL_FILES {
+        let Ok(src) = app_handle
+            .path()
+            .resolve(format!("resources/models/{}", file_name), BaseDirectory::Resource)
+        else {
+            continue;
+        };
+        match std::fs::copy(&src, model_dir.join(file_name)) {
+            Ok(_) => restored.push(file_name.to_string()),
+            Err(e) => eprintln!("⚠️ [AppCore] Failed to restore bundled {}: {}", file_name, e),
+        }
+    }
+    restored
 }
 
 impl AppCore {
@@ -66,23 +111,23 @@ impl AppCore {
         let model_path = model_dir.join("personal_model.onnx");
         let scaler_path = model_dir.join("scaler_params.json");
         let map_path = model_dir.join("global_map.json");
-
-        // 1. 번들 리소스 경로 해석
-        let bundled_model = app_handle.path().resolve("resources/models/personal_model.onnx", BaseDirectory::Resource).ok();
-        let bundled_scaler = app_handle.path().resolve("resources/models/scaler_params.json", BaseDirectory::Resource).ok();
-        let bundled_map = app_handle.path().resolve("resources/models/global_map.json", BaseDirectory::Resource).ok();
+        let intent_map_path = model_dir.join("intent_map.json");
 
         // 2. [핵심 해결] 개발 모드(Debug)에서는 무조건 덮어쓰기, 배포 모드(Release)에서는 없을 때만 복사
         #[cfg(debug_assertions)]
         {
             println!("🛠️ [Dev Mode] Forcing overwrite of ML artifacts to ensure latest base model.");
-            if let Some(src) = &bundled_model { let _ = std::fs::copy(src, &model_path); }
-            if let Some(src) = &bundled_scaler { let _ = std::fs::copy(src, &scaler_path); }
-            if let Some(src) = &bundled_map { let _ = std::fs::copy(src, &map_path); }
+            restore_bundled_models(app_handle, &model_dir);
         }
 
         #[cfg(not(debug_assertions))]
         {
+            // 번들 리소스 경로 해석
+            let bundled_model = app_handle.path().resolve("resources/models/personal_model.onnx", BaseDirectory::Resource).ok();
+            let bundled_scaler = app_handle.path().resolve("resources/models/scaler_params.json", BaseDirectory::Resource).ok();
+            let bundled_map = app_handle.path().resolve("resources/models/global_map.json", BaseDirectory::Resource).ok();
+            let bundled_intent_map = app_handle.path().resolve("resources/models/intent_map.json", BaseDirectory::Resource).ok();
+
             if !model_path.exists() {
                 if let Some(src) = &bundled_model { let _ = std::fs::copy(src, &model_path); }
             }
@@ -92,6 +137,10 @@ impl AppCore {
             if !map_path.exists() {
                 if let Some(src) = &bundled_map { let _ = std::fs::copy(src, &map_path); }
             }
+            // 인텐트 맵은 사용자가 직접 편집할 수 있으므로 없을 때만 복사
+            if !intent_map_path.exists() {
+                if let Some(src) = &bundled_intent_map { let _ = std::fs::copy(src, &intent_map_path); }
+            }
         }
 
         // 3. 글로벌 맵 로딩
@@ -101,6 +150,10 @@ impl AppCore {
             std::collections::HashMap::new()
         };
 
+        // 3-1. 인텐트 프리셋 로딩
+        let intent_map = intent::load_intent_map(&intent_map_path);
+        println!("🎯 [AppCore] Loaded {} intent presets.", intent_map.len());
+
         // 4. ML 엔진 로드
         let inference_engine = match InferenceEngine::new(
             model_path.to_str().unwrap_or(""), 
@@ -113,6 +166,14 @@ impl AppCore {
             }
         };
 
+        Self::with_parts(inference_engine, global_map, intent_map)
+    }
+
+    fn with_parts(
+        inference_engine: Option<InferenceEngine>,
+        global_map: HashMap<String, f64>,
+        intent_map: IntentMap,
+    ) -> Self {
         Self {
             inference_engine,
             state_engine: StateEngine::new(),
@@ -124,36 +185,146 @@ impl AppCore {
             previous_state: crate::core::state::FSMState::IDLE,
             last_snapshot: None,
             last_evaluated_tokens: String::new(),
+            last_active_app: String::new(),
+            intent_map,
+            active_intent: None,
+            base_state_config: StateConfig::default(),
+        }
+    }
+
+    /// 사용자 설정(FSM 기본값)을 저장하고, 현재 인텐트 정책을 덧씌워 엔진에 적용
+    pub fn apply_base_state_config(&mut self, config: StateConfig) -> Result<(), String> {
+        let effective = self.effective_state_config(&config, self.active_intent.as_deref());
+        self.state_engine.apply_config(effective)?;
+        self.base_state_config = config;
+        Ok(())
+    }
+
+    /// 인텐트 id 검증 및 정규화 (알 수 없는 인텐트는 에러)
+    pub fn resolve_intent(&self, intent: &str) -> Result<String, String> {
+        let intent_id = intent::normalize_intent_id(intent);
+        if self.intent_map.contains_key(&intent_id) {
+            Ok(intent_id)
+        } else {
+            Err(format!("Unknown intent preset: {}", intent))
+        }
+    }
+
+    /// 세션 인텐트 전환 (None = 인텐트 없음, global_map만 사용)
+    pub fn set_active_intent(&mut self, intent: Option<&str>) -> Result<(), String> {
+        let intent_id = match intent {
+            Some(raw) => Some(self.resolve_intent(raw)?),
+            None => None,
+        };
+
+        let effective = self.effective_state_config(&self.base_state_config, intent_id.as_deref());
+        self.state_engine.apply_config(effective)?;
+        self.active_intent = intent_id;
+        Ok(())
+    }
+
+    /// 선택 가능한 인텐트 목록 (이름순)
+    pub fn list_intents(&self) -> Vec<IntentSummary> {
+        let mut intents: Vec<IntentSummary> = self
+            .intent_map
+            .iter()
+            .map(|(id, profile)| IntentSummary { id: id.clone(), label: profile.label.clone() })
+            .collect();
+        intents.sort_by(|a, b| a.id.cmp(&b.id));
+        intents
+    }
+
+    fn effective_state_config(&self, base: &StateConfig, intent: Option<&str>) -> StateConfig {
+        match intent.and_then(|id| self.intent_map.get(id)) {
+            Some(profile) => profile.apply_to(base),
+            None => base.clone(),
         }
     }
 
     // 동적 로드된 맵을 기반으로 점수 계산 (Spec: Simple Tokenization & Exact Match)
+    // 세션 인텐트가 있으면 해당 프리셋의 토큰 가중치를 global_map 위에 덧씌움
     fn calculate_context_score(&self, app_name: &str, title: &str) -> f64 {
-        let full_text = format!("{} {}", app_name, title).to_lowercase();
-        
-        let mut score = 0.0;
-        let mut count = 0.0;
-        let mut found = false;
-        
-        // Split by non-alphanumeric (Spec-compliant)
-        let tokens: Vec<&str> = full_text.split(|c: char| !c.is_alphanumeric()).collect();
-
-        for token in tokens {
-            if token.is_empty() { continue; }
-
-            // Exact Match Lookup
-            if let Some(&val) = self.global_map.get(token) {
-                score += val;
-                count += 1.0;
-                found = true;
+        let overlay = self
+            .active_intent
+            .as_ref()
+            .and_then(|id| self.intent_map.get(id))
+            .map(|profile| &profile.token_weights);
+
+        intent::score_context(app_name, title, &self.global_map, overlay)
+    }
+
+    /// [Slow Path] 창 센싱: 활성 창/보이는 창 수집 + 시맨틱 태깅, 자연스러운 멈춤(앱 전환/입력 공백) 감지
+    /// 활성 창을 얻지 못하면 None (이번 주기 이벤트 저장/추론 생략)
+    pub fn observe_windows(
+        &mut self,
+        sensor: &dyn WindowSensor,
+        input_stats: &mut InputStats,
+        silence_sec: f64,
+    ) -> Option<WindowObservation> {
+        let window_info = sensor.active_window().ok()?;
+
+        // 시각 센서 (Visible Windows) 수집
+        let mut visible_windows_raw = commands::vision::visible_windows_or_empty(sensor);
+
+        // 시맨틱 태깅 (Semantic Tagging)
+        // 원본 제목을 '토큰화 + 숫자 필터링'된 문자열로 세탁
+        for window in &mut visible_windows_raw {
+            let tokens = commands::vision::get_semantic_tokens(&window.app_name, &window.title);
+            if !tokens.is_empty() {
+                window.title = tokens.join(" ");
+            } else {
+                window.title = String::new(); // 개인정보 보호
             }
         }
-        
-        if !found { return 0.0; } // Neutral (Unknown) - Spec says 0.0
-        if count == 0.0 { return 0.0; }
-        
-        score / count
+
+        // 활성 창(Active Window) 태깅
+        // 활성 창 역시 동일한 로직으로 토큰을 추출합니다.
+        let active_tokens = commands::vision::get_semantic_tokens(&window_info.app_name, &window_info.title);
+        let sanitized_active_title = active_tokens.join(" ");
+
+        // [버그 수정] 현재 창이 우리 에이전트 프로세스(오버레이 등)가 아니라면 타겟 토큰으로 기억
+        let my_pid = std::process::id() as u64;
+        if window_info.process_id != my_pid {
+            self.last_evaluated_tokens = sanitized_active_title.clone();
+        }
+
+        // InputStats에 시각 데이터 업데이트
+        // [!] ML 모델을 위해 '전경 여부'도 포함할 수 있지만, 현재는 title만 저장
+        input_stats.visible_windows = visible_windows_raw;
+
+        // [Positive Intervention] 휴식 권장 타이밍 포착
+        // 입력 공백 스파이크 또는 앱 컨텍스트 스위칭 = 흐름을 끊지 않는 자연스러운 멈춤
+        let mut is_app_switch = false;
+        if window_info.process_id != my_pid {
+            is_app_switch = !self.last_active_app.is_empty() && self.last_active_app != window_info.app_name;
+            self.last_active_app = window_info.app_name.clone();
+        }
+        if is_app_switch || silence_sec >= self.state_engine.get_config().rest_pause_silence_sec {
+            self.state_engine.mark_natural_pause();
+        }
+
+        Some(WindowObservation { window_info, active_tokens, sanitized_active_title })
     }
+
+    /// FOCUS 상태로 전이된 틱이면 현재 창 배치를 스냅샷으로 캡처 (restore_workspace 복구 기준)
+    pub fn capture_snapshot_on_focus(&mut self, sensor: &dyn WindowSensor, now_ms: u64) {
+        let current_state = self.state_engine.get_state();
+        if current_state == crate::core::state::FSMState::FOCUS && self.previous_state != crate::core::state::FSMState::FOCUS {
+            println!("📸 [Snapshot] FOCUS 상태 진입: 현재 창 배치를 캡처합니다.");
+            self.last_snapshot = Some(crate::commands::vision::WorkspaceSnapshot {
+                timestamp_ms: now_ms,
+                windows: commands::vision::visible_windows_or_empty(sensor),
+            });
+        }
+        self.previous_state = current_state;
+    }
+}
+
+/// 창 센싱 결과 (활성 창 원본 + 시맨틱 태깅된 토큰)
+pub struct WindowObservation {
+    pub window_info: ActiveWindowInfo,
+    pub active_tokens: Vec<String>,
+    pub sanitized_active_title: String,
 }
 
 // ================================================================
@@ -162,8 +333,9 @@ impl AppCore {
 pub fn start_core_loop<R: Runtime>(
     app_handle: AppHandle<R>,
     session_state_mutex: SessionStateArcMutex,
-    storage_manager_mutex: StorageManagerArcMutex,
+    storage_manager: StorageManagerArc,
     input_stats_mutex: InputStatsArcMutex,
+    window_sensor: WindowSensorArc,
 ) {
     let app_handle_clone = app_handle.clone();
 
@@ -206,9 +378,33 @@ pub fn start_core_loop<R: Runtime>(
 
             if let Some(active_session) = active_session_opt { // 복제된 데이터를 소유권(Owned) 형태로 사용
 
-                // 타이머 방송
-                let elapsed = now_ts.saturating_sub(active_session.start_time_s);
+                // 타이머 방송 (일시정지 시간을 제외한 순수 집중 시간)
+                let elapsed = active_session.net_elapsed_s(now_ts);
                 app_handle_clone.emit("widget-tick", elapsed).ok(); // 'widget-tick' 이벤트를 '모든' 창에 방송(emit)
+                app_handle_clone.emit("widget-progress", goal::session_progress(&active_session, now_ts)).ok(); // 목표 대비 진행률
+
+                // 목표 시간 도달 (수동 세션, 1회)
+                if goal::is_goal_newly_reached(&active_session, now_ts) {
+                    match handle_goal_reached(&app_handle_clone, &session_state_mutex, &storage_manager, &active_session, now_ts) {
+                        Ok(GoalReachedAction::AutoEnd) => {
+                            // end_active_session이 AppCore 락을 다시 잡으므로 먼저 해제 (교착상태 방지)
+                            drop(core);
+                            if let Err(e) = commands::session::end_active_session(&app_handle_clone, 0) {
+                                eprintln!("Failed to auto-end session at goal: {}", e);
+                            }
+                            tick_counter = 0;
+                            continue;
+                        }
+                        Ok(GoalReachedAction::Overtime) => {}
+                        Err(e) => eprintln!("Failed to handle goal reached: {}", e),
+                    }
+                }
+
+                // 일시정지 중: FSM / 센싱 / 이벤트 캐싱 중단
+                if active_session.is_paused() {
+                    tick_counter = 0;
+                    continue;
+                }
 
                 tick_counter += 1;
 
@@ -234,33 +430,13 @@ pub fn start_core_loop<R: Runtime>(
                 if tick_counter >= 5 {
                     tick_counter = 0; // 카운터 리셋
 
-                    // 1. 활성 창 정보 수집
-                    if let Ok(window_info) = crate::commands::vision::_get_active_window_info_internal() {
-                        
-                        // 시각 센서 (Visible Windows) 수집
-                        let mut visible_windows_raw = commands::vision::_get_all_visible_windows_internal();
-
-                        // 시맨틱 태깅 (Semantic Tagging)
-                        // 원본 제목을 '토큰화 + 숫자 필터링'된 문자열로 세탁
-                        for window in &mut visible_windows_raw {
-                            let tokens = commands::vision::get_semantic_tokens(&window.app_name, &window.title);
-                            if !tokens.is_empty() {
-                                window.title = tokens.join(" ");
-                            } else {
-                                window.title = String::new(); // 개인정보 보호
-                            }
-                        }
-
-                        // 활성 창(Active Window) 태깅
-                        // 활성 창 역시 동일한 로직으로 토큰을 추출합니다.
-                        let active_tokens = commands::vision::get_semantic_tokens(&window_info.app_name, &window_info.title);
-                        let sanitized_active_title = active_tokens.join(" ");
+                    let silence_sec = if input_stats.last_meaningful_input_timestamp_ms > 0 {
+                        (now_ms.saturating_sub(input_stats.last_meaningful_input_timestamp_ms) as f64) / 1000.0
+                    } else { 0.0 };
 
-                        // [버그 수정] 현재 창이 우리 에이전트 프로세스(오버레이 등)가 아니라면 타겟 토큰으로 기억
-                        let my_pid = std::process::id() as u64;
-                        if window_info.process_id != my_pid {
-                            core.last_evaluated_tokens = sanitized_active_title.clone();
-                        }
+                    // 1. 활성 창 정보 수집 (보이는 창 시맨틱 태깅, 자연스러운 멈춤 감지 포함)
+                    if let Some(observation) = core.observe_windows(window_sensor.as_ref(), &mut input_stats, silence_sec) {
+                        let WindowObservation { window_info, active_tokens, sanitized_active_title } = observation;
 
                         // UUID 생성 (Flag 발급)
                         let client_evt_id = format!("evt-{}", Uuid::new_v4());
@@ -268,9 +444,6 @@ pub fn start_core_loop<R: Runtime>(
                         // AppCore 상태에 ID 저장 (피드백 연결용)
                         core.current_event_id = Some(client_evt_id.clone());
 
-                        // InputStats에 시각 데이터 업데이트
-                        // [!] ML 모델을 위해 '전경 여부'도 포함할 수 있지만, 현재는 title만 저장
-                        input_stats.visible_windows = visible_windows_raw;
                         // InputStats를 JSON 문자열로 직렬화 (commands.rs 헬퍼 호출)
                         let activity_vector_json = input_stats.to_activity_vector_json(); // LSN 저장용
 
@@ -284,10 +457,6 @@ pub fn start_core_loop<R: Runtime>(
                         // 50번 이상의 움직임은 어차피 "최고 수준의 몰입 상태"이므로 그 이상은 무의미합니다.
                         let delta_f64 = (raw_delta as f64).min(50.0); 
 
-                        let silence_sec = if input_stats.last_meaningful_input_timestamp_ms > 0 {
-                            (now_ms.saturating_sub(input_stats.last_meaningful_input_timestamp_ms) as f64) / 1000.0
-                        } else { 0.0 };
-                        
                         // 크롬의 경우 Context Score는 0.1 로 정상 계산됨
                         let context_score = core.calculate_context_score(&window_info.app_name, &window_info.title);
 
@@ -336,13 +505,7 @@ pub fn start_core_loop<R: Runtime>(
 
                         // 4. 데이터 저장 (학습용 데이터셋 구축)
                         // LSN에 이벤트를 저장해야 나중에 꺼내서 학습할 수 있습니다.
-                        let storage = match storage_manager_mutex.lock() {
-                            Ok(guard) => guard,
-                            Err(e) => {
-                                eprintln!("Failed to lock storage manager: {}", e);
-                                continue;
-                            }
-                        };
+                        // (writer 큐에 넣고 바로 반환 -> 느린 조회/동기화 중에도 FSM 틱이 밀리지 않음)
                         let raw_json = serde_json::json!({
                             "delta_events": raw_delta,
                             "silence_sec": silence_sec,
@@ -350,16 +513,16 @@ pub fn start_core_loop<R: Runtime>(
                             "ml_vector": ml_vector
                         }).to_string();
 
-                        storage
+                        storage_manager
                             .cache_event(
                                 &active_session.session_id,
                                 &client_evt_id,
                                 &window_info.app_name,
                                 &sanitized_active_title,
                                 &activity_vector_json, // JSON 문자열 전달
+                                &format!("{:?}", core.state_engine.get_state()), // 세션 요약용 FSM 상태
                             )
                             .unwrap_or_else(|e| eprintln!("Failed to cache event: {}", e));
-                        drop(storage);
 
                         // 5. ML 추론 (모델이 준비된 경우)
                         if let Some(engine) = &mut core.inference_engine {
@@ -398,16 +561,14 @@ pub fn start_core_loop<R: Runtime>(
                 );
 
                 // --- [신규] 상태 전이 감지 및 스냅샷 캡처 ---
-                let current_state = core.state_engine.get_state();
-                if current_state == crate::core::state::FSMState::FOCUS && core.previous_state != crate::core::state::FSMState::FOCUS {
-                    println!("📸 [Snapshot] FOCUS 상태 진입: 현재 창 배치를 캡처합니다.");
-                    let current_windows = commands::vision::_get_all_visible_windows_internal();
-                    core.last_snapshot = Some(crate::commands::vision::WorkspaceSnapshot {
-                        timestamp_ms: now_ms,
-                        windows: current_windows,
-                    });
+                core.capture_snapshot_on_focus(window_sensor.as_ref(), now_ms);
+
+                // 세션 요약용 개입 횟수 기록
+                if trigger != InterventionTrigger::DoNothing {
+                    storage_manager
+                        .record_session_intervention(&active_session.session_id, &trigger)
+                        .unwrap_or_else(|e| eprintln!("Failed to record intervention: {}", e));
                 }
-                core.previous_state = current_state;
 
                 // ------------------------------------------------
                 // [Action] 개입 실행
@@ -455,11 +616,29 @@ pub fn start_core_loop<R: Runtime>(
                             // });
                         }
                     },
+                    InterventionTrigger::TriggerRestRecommendation => {
+                        println!("🌿 [Action] Rest Recommendation (Click-Through)");
+
+                        ensure_overlay_exists(&app_handle_clone);
+
+                        if let Some(overlay_window) = app_handle_clone.get_webview_window("overlay") {
+                            // 휴식 권장은 작업을 막지 않는 부드러운 알림 (Click-Through)
+                            let _ = commands::window::set_overlay_ignore_cursor_events(app_handle_clone.clone(), true);
+                            let _ = commands::window::show_overlay(app_handle_clone.clone());
+
+                            // 차단/경고와 구분되는 별도 이벤트 + 페이로드
+                            let payload = core.state_engine.rest_recommendation_payload();
+                            overlay_window.emit("rest-recommendation", payload).ok();
+                        }
+                    },
                     InterventionTrigger::DoNothing => {
                         // [Fix] 게이지가 줄어들어 FOCUS 상태(30 미만)로 돌아오면 오버레이 숨김
                         // (기존에는 0.0일 때만 숨겨서 29초여도 오버레이가 안 꺼지는 문제 발생)
-                        let should_hide = core.state_engine.get_gauge_ratio() <= 0.0 
-                            || core.state_engine.get_state() == crate::core::state::FSMState::FOCUS;
+                        // 휴식 권장 중에는 게이지가 0이어도 오버레이 유지 (휴식 종료 시 FOCUS 복귀와 함께 숨김)
+                        let is_resting = core.state_engine.get_state() == crate::core::state::FSMState::REST_RECOMMENDED;
+                        let should_hide = !is_resting
+                            && (core.state_engine.get_gauge_ratio() <= 0.0
+                                || core.state_engine.get_state() == crate::core::state::FSMState::FOCUS);
 
                         if should_hide {
                              if let Some(window) = app_handle_clone.get_webview_window("overlay") {
@@ -494,11 +673,69 @@ pub fn start_core_loop<R: Runtime>(
                 
                 // (혹시 모를 UI 동기화를 위해 0 전송은 유지)
                 app_handle_clone.emit("widget-tick", 0).ok();
+                app_handle_clone.emit("widget-progress", goal::SessionProgressPayload {
+                    elapsed_sec: 0,
+                    goal_sec: None,
+                    progress_ratio: 0.0,
+                    overtime_sec: 0,
+                }).ok();
             }
         }
     });
 }
 
+// [Helper] 목표 달성 기록 + 'session-goal-reached' 방송 + 알림. 설정된 후속 동작을 반환
+fn handle_goal_reached<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    session_state_mutex: &SessionStateArcMutex,
+    storage_manager: &StorageManager,
+    active_session: &crate::ActiveSessionInfo,
+    now_ts: u64,
+) -> Result<GoalReachedAction, String> {
+    let goal_settings = match app_handle.try_state::<SettingsArcMutex>() {
+        Some(settings) => settings.lock().map_err(|e| e.to_string())?.goal.clone(),
+        None => Default::default(),
+    };
+
+    // 달성 시각을 LSN에 기록 (재시작 후 중복 알림 방지)
+    {
+        let mut session_state = session_state_mutex.lock().map_err(|e| e.to_string())?;
+        let session = match session_state.as_mut() {
+            Some(s) if s.session_id == active_session.session_id => s,
+            _ => return Ok(GoalReachedAction::Overtime), // 그 사이 세션이 종료/교체됨
+        };
+        session.goal_reached_s = Some(now_ts);
+        storage_manager.save_active_session(session)?;
+    }
+
+    let goal_duration_min = active_session.goal_duration_min.unwrap_or(0);
+    println!(
+        "🎯 [Goal] Session {} reached goal ({}min). Action: {:?}",
+        active_session.session_id, goal_duration_min, goal_settings.on_goal_reached
+    );
+
+    app_handle
+        .emit(
+            "session-goal-reached",
+            goal::SessionGoalReachedPayload {
+                session_id: active_session.session_id.clone(),
+                goal_duration_min,
+                action: goal_settings.on_goal_reached,
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    if goal_settings.notify_enabled {
+        let body = match goal_settings.on_goal_reached {
+            GoalReachedAction::Overtime => format!("목표 {}분을 달성했습니다. 계속하면 초과 시간으로 기록됩니다.", goal_duration_min),
+            GoalReachedAction::AutoEnd => format!("목표 {}분을 달성하여 집중 모드를 종료합니다.", goal_duration_min),
+        };
+        let _ = app_handle.notification().builder().title("집중 목표 달성").body(body).show();
+    }
+
+    Ok(goal_settings.on_goal_reached)
+}
+
 // [Helper] 오버레이 생성 도우미 (표시는 show_overlay에 위임)
 fn ensure_overlay_exists<R: Runtime>(app_handle: &AppHandle<R>) {
     if app_handle.get_webview_window("overlay").is_none() {
@@ -516,4 +753,75 @@ fn ensure_overlay_exists<R: Runtime>(app_handle: &AppHandle<R>) {
         .visible(false)     // 일단 숨김 상태로 생성
         .build().ok();
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::vision::WinRect;
+    use crate::core::sensor::fake::{FakeSensor, SensorCall};
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> WinRect {
+        WinRect { left, top, right, bottom }
+    }
+
+    fn hwnds(windows: &[crate::commands::vision::WindowInfo]) -> Vec<isize> {
+        windows.iter().map(|w| w.hwnd).collect()
+    }
+
+    #[test]
+    fn test_core_loop_sensing_with_scripted_sensor() {
+        let editor = FakeSensor::window(1, "Editor", rect(0, 0, 800, 600));
+        let terminal = FakeSensor::window(2, "Terminal", rect(800, 0, 1280, 600));
+        let youtube = FakeSensor::window(3, "YouTube", rect(0, 0, 1280, 800));
+        let sensor = FakeSensor::new(vec![vec![editor.clone(), terminal], vec![youtube, editor]]);
+
+        let mut core = AppCore::with_parts(None, HashMap::new(), IntentMap::new());
+        let mut input_stats = InputStats::default();
+
+        // 1. 세션 시작 -> FOCUS 진입 틱에 스냅샷 캡처 (이후 틱에서는 다시 캡처하지 않음)
+        core.state_engine.manual_reset();
+        core.capture_snapshot_on_focus(&sensor, 1_000);
+        core.capture_snapshot_on_focus(&sensor, 2_000);
+        let snapshot = core.last_snapshot.clone().unwrap();
+        assert_eq!(snapshot.timestamp_ms, 1_000);
+        assert_eq!(hwnds(&snapshot.windows), vec![1, 2]);
+
+        // 2. 첫 센싱 주기: 활성 창 토큰 기억, 보이는 창 제목은 토큰으로 세탁
+        let observation = core.observe_windows(&sensor, &mut input_stats, 0.0).unwrap();
+        assert_eq!(observation.window_info.title, "Editor");
+        assert_eq!(observation.sanitized_active_title, "editor exe");
+        assert_eq!(core.last_evaluated_tokens, "editor exe");
+        assert_eq!(core.last_active_app, "editor.exe");
+        assert_eq!(hwnds(&input_stats.visible_windows), vec![1, 2]);
+        assert_eq!(input_stats.visible_windows[1].title, "terminal exe");
+
+        // 3. 다음 주기: 유튜브가 맨 위로 -> 활성 앱 전환
+        sensor.advance();
+        let observation = core.observe_windows(&sensor, &mut input_stats, 0.0).unwrap();
+        assert_eq!(observation.active_tokens, vec!["youtube", "exe"]);
+        assert_eq!(core.last_active_app, "youtube.exe");
+        assert_eq!(hwnds(&input_stats.visible_windows), vec![3, 1]);
+
+        // 4. 캡처한 스냅샷으로 복구: 유튜브 최소화, 업무 창 위치 복원
+        crate::commands::vision::restore_snapshot(&sensor, &snapshot).unwrap();
+        assert_eq!(
+            sensor.calls(),
+            vec![
+                SensorCall::Minimize(3),
+                SensorCall::Restore(1, rect(0, 0, 800, 600)),
+                SensorCall::Restore(2, rect(800, 0, 1280, 600)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_core_loop_skips_sensing_when_sensor_fails() {
+        let sensor = FakeSensor::new(vec![]); // 활성 창 없음 -> active_window 실패
+        let mut core = AppCore::with_parts(None, HashMap::new(), IntentMap::new());
+        let mut input_stats = InputStats::default();
+
+        assert!(core.observe_windows(&sensor, &mut input_stats, 0.0).is_none());
+        assert!(input_stats.visible_windows.is_empty());
+        assert!(core.last_active_app.is_empty());
+    }
+}
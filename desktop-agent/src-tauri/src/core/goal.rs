@@ -0,0 +1,108 @@
+// 목표 집중 시간(goal_duration) 모듈
+// Core Loop가 매 틱 위젯 진행률을 계산하고, 목표 도달 시점을 판정한다.
+
+use serde::Serialize;
+
+use crate::managers::settings::GoalReachedAction;
+use crate::ActiveSessionInfo;
+
+/// 'widget-progress' 이벤트 페이로드 (widget-tick과 함께 매초 방송)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionProgressPayload {
+    pub elapsed_sec: u64,       // 일시정지를 제외한 순수 집중 시간
+    pub goal_sec: Option<u64>,  // 목표 없음 = None
+    pub progress_ratio: f64,    // 0.0 ~ 1.0 (목표 없음 = 0.0)
+    pub overtime_sec: u64,      // 목표 초과 시간
+}
+
+/// 'session-goal-reached' 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionGoalReachedPayload {
+    pub session_id: String,
+    pub goal_duration_min: u32,
+    pub action: GoalReachedAction,
+}
+
+pub fn session_progress(session: &ActiveSessionInfo, now_s: u64) -> SessionProgressPayload {
+    let elapsed_sec = session.net_elapsed_s(now_s);
+    let goal_sec = session.goal_duration_min.map(|m| m as u64 * 60);
+
+    match goal_sec {
+        Some(goal) if goal > 0 => SessionProgressPayload {
+            elapsed_sec,
+            goal_sec,
+            progress_ratio: (elapsed_sec as f64 / goal as f64).min(1.0),
+            overtime_sec: elapsed_sec.saturating_sub(goal),
+        },
+        _ => SessionProgressPayload {
+            elapsed_sec,
+            goal_sec: None,
+            progress_ratio: 0.0,
+            overtime_sec: 0,
+        },
+    }
+}
+
+/// 이번 틱에 목표 도달 처리를 해야 하는지 판정
+/// (스케줄 세션은 end_time 자동 종료가 담당하므로 제외, 이미 달성한 세션은 1회만)
+pub fn is_goal_newly_reached(session: &ActiveSessionInfo, now_s: u64) -> bool {
+    if session.scheduled_end_s.is_some() || session.goal_reached_s.is_some() {
+        return false;
+    }
+    match session.goal_duration_min {
+        Some(goal_min) if goal_min > 0 => session.net_elapsed_s(now_s) >= goal_min as u64 * 60,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(goal_duration_min: Option<u32>) -> ActiveSessionInfo {
+        ActiveSessionInfo {
+            session_id: "s-1".to_string(),
+            task_id: None,
+            start_time_s: 1000,
+            intent: None,
+            scheduled_end_s: None,
+            paused_total_s: 0,
+            paused_at_s: None,
+            goal_duration_min,
+            goal_reached_s: None,
+        }
+    }
+
+    #[test]
+    fn test_goal_reached_excludes_paused_time() {
+        let mut info = session(Some(10));
+        info.paused_total_s = 60;
+
+        // 경과 600초 중 60초 일시정지 -> 순수 540초, 아직 미달성
+        assert!(!is_goal_newly_reached(&info, 1600));
+        let progress = session_progress(&info, 1600);
+        assert_eq!(progress.goal_sec, Some(600));
+        assert!((progress.progress_ratio - 0.9).abs() < 1e-9);
+
+        assert!(is_goal_newly_reached(&info, 1660));
+
+        // 달성 이후에는 다시 발생하지 않고 overtime만 증가
+        info.goal_reached_s = Some(1660);
+        assert!(!is_goal_newly_reached(&info, 1760));
+        let progress = session_progress(&info, 1760);
+        assert_eq!(progress.progress_ratio, 1.0);
+        assert_eq!(progress.overtime_sec, 100);
+    }
+
+    #[test]
+    fn test_no_goal_or_scheduled_session() {
+        let info = session(None);
+        assert!(!is_goal_newly_reached(&info, 100_000));
+        assert_eq!(session_progress(&info, 1300).progress_ratio, 0.0);
+
+        let mut scheduled = session(Some(10));
+        scheduled.scheduled_end_s = Some(1600);
+        assert!(!is_goal_newly_reached(&scheduled, 2000));
+        assert_eq!(session_progress(&scheduled, 2000).progress_ratio, 1.0);
+    }
+}
@@ -0,0 +1,209 @@
+// 인텐트 프리셋 (Intent-Based Context Profiling) 모듈
+// 문서 08-02: 세션의 의도(Coding / Research / Design 등)에 따라
+// context_score 토큰 가중치와 게이지 배속 정책을 세션 단위로 재구성한다.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::core::state::StateConfig;
+
+/// 인텐트별 게이지 배속 보정 (사용자 설정 StateConfig의 이탈 배속에 곱해짐)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MultiplierPolicy {
+    pub strong_outlier: f64,
+    pub weak_outlier: f64,
+}
+
+impl Default for MultiplierPolicy {
+    fn default() -> Self {
+        Self { strong_outlier: 1.0, weak_outlier: 1.0 }
+    }
+}
+
+/// intent_map.json의 프로파일 1개
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentProfile {
+    #[serde(default)]
+    pub label: String,
+    // global_map 위에 덧씌워지는 토큰 가중치 (같은 토큰이면 인텐트 값 우선)
+    #[serde(default)]
+    pub token_weights: HashMap<String, f64>,
+    #[serde(default)]
+    pub multiplier_policy: MultiplierPolicy,
+}
+
+impl IntentProfile {
+    pub fn validate(&self) -> Result<(), String> {
+        let policy = &self.multiplier_policy;
+        for v in [policy.strong_outlier, policy.weak_outlier] {
+            if !v.is_finite() || v < 0.0 {
+                return Err("multiplier_policy values must be finite and not negative".to_string());
+            }
+        }
+        if self.token_weights.values().any(|w| !w.is_finite()) {
+            return Err("token_weights must be finite numbers".to_string());
+        }
+        Ok(())
+    }
+
+    /// 사용자 기본 설정 위에 인텐트 배속 정책을 적용한 StateConfig
+    pub fn apply_to(&self, base: &StateConfig) -> StateConfig {
+        StateConfig {
+            multiplier_strong_outlier: base.multiplier_strong_outlier * self.multiplier_policy.strong_outlier,
+            multiplier_weak_outlier: base.multiplier_weak_outlier * self.multiplier_policy.weak_outlier,
+            ..base.clone()
+        }
+    }
+}
+
+// Key: 인텐트 id (소문자, 예: "research")
+pub type IntentMap = HashMap<String, IntentProfile>;
+
+/// 프론트엔드 선택 목록용 요약
+#[derive(Debug, Clone, Serialize)]
+pub struct IntentSummary {
+    pub id: String,
+    pub label: String,
+}
+
+/// 인텐트 id 정규화 ("Research " -> "research")
+pub fn normalize_intent_id(id: &str) -> String {
+    id.trim().to_lowercase()
+}
+
+/// intent_map.json 파싱 (id/토큰은 소문자로 정규화, 잘못된 프로파일은 제외)
+pub fn parse_intent_map(json: &str) -> Result<IntentMap, String> {
+    let raw: HashMap<String, IntentProfile> =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse intent map: {}", e))?;
+
+    let mut intent_map = IntentMap::new();
+    for (id, mut profile) in raw {
+        if let Err(e) = profile.validate() {
+            eprintln!("⚠️ [Intent] Skipping invalid preset '{}': {}", id, e);
+            continue;
+        }
+        profile.token_weights = profile
+            .token_weights
+            .into_iter()
+            .map(|(token, weight)| (token.to_lowercase(), weight))
+            .collect();
+        if profile.label.is_empty() {
+            profile.label = id.clone();
+        }
+        intent_map.insert(normalize_intent_id(&id), profile);
+    }
+    Ok(intent_map)
+}
+
+/// 파일에서 인텐트 맵 로드 (없거나 손상된 경우 빈 맵 = 인텐트 기능 비활성)
+pub fn load_intent_map(path: &Path) -> IntentMap {
+    match std::fs::read_to_string(path) {
+        Ok(data) => parse_intent_map(&data).unwrap_or_else(|e| {
+            eprintln!("⚠️ [Intent] {}", e);
+            IntentMap::new()
+        }),
+        Err(_) => IntentMap::new(),
+    }
+}
+
+/// 토큰 단위 context_score 계산 (Spec: Simple Tokenization & Exact Match)
+/// overlay(인텐트 토큰 가중치)가 있으면 global_map보다 우선 적용
+pub fn score_context(
+    app_name: &str,
+    title: &str,
+    global_map: &HashMap<String, f64>,
+    overlay: Option<&HashMap<String, f64>>,
+) -> f64 {
+    let full_text = format!("{} {}", app_name, title).to_lowercase();
+
+    let mut score = 0.0;
+    let mut count = 0.0;
+
+    // Split by non-alphanumeric (Spec-compliant)
+    for token in full_text.split(|c: char| !c.is_alphanumeric()) {
+        if token.is_empty() { continue; }
+
+        // Exact Match Lookup (Intent Overlay -> Global Map)
+        let weight = overlay
+            .and_then(|map| map.get(token))
+            .or_else(|| global_map.get(token));
+
+        if let Some(&val) = weight {
+            score += val;
+            count += 1.0;
+        }
+    }
+
+    if count == 0.0 { return 0.0; } // Neutral (Unknown) - Spec says 0.0
+
+    score / count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_global_map() -> HashMap<String, f64> {
+        HashMap::from([
+            ("youtube".to_string(), -0.9),
+            ("chrome".to_string(), 0.1),
+            ("code".to_string(), 0.9),
+        ])
+    }
+
+    fn test_intent_map() -> IntentMap {
+        parse_intent_map(r#"{
+            "Research": {
+                "token_weights": { "YouTube": 0.5 },
+                "multiplier_policy": { "weak_outlier": 0.5 }
+            },
+            "broken": {
+                "multiplier_policy": { "strong_outlier": -1.0 }
+            }
+        }"#).expect("valid intent map")
+    }
+
+    #[test]
+    fn test_parse_normalizes_ids_and_skips_invalid_presets() {
+        let map = test_intent_map();
+
+        assert!(map.contains_key("research"));
+        assert!(!map.contains_key("broken"));
+        assert_eq!(map["research"].label, "Research");
+        assert_eq!(map["research"].token_weights.get("youtube"), Some(&0.5));
+    }
+
+    #[test]
+    fn test_intent_overlay_reshapes_context_score() {
+        let global_map = test_global_map();
+        let map = test_intent_map();
+        let research = &map["research"].token_weights;
+
+        // 기본(Coding 등): YouTube는 페널티
+        let base = score_context("chrome.exe", "YouTube", &global_map, None);
+        // Research: YouTube는 업무 도구
+        let overlaid = score_context("chrome.exe", "YouTube", &global_map, Some(research));
+
+        assert!(base < 0.0);
+        assert!(overlaid > 0.0);
+
+        // 오버레이에 없는 토큰은 global_map 값 유지
+        let untouched = score_context("code", "", &global_map, Some(research));
+        assert!((untouched - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_multiplier_policy_applies_on_top_of_base_config() {
+        let map = test_intent_map();
+        let base = StateConfig::default();
+
+        let effective = map["research"].apply_to(&base);
+
+        assert_eq!(effective.multiplier_weak_outlier, base.multiplier_weak_outlier * 0.5);
+        assert_eq!(effective.multiplier_strong_outlier, base.multiplier_strong_outlier);
+        assert_eq!(effective.threshold_block_sec, base.threshold_block_sec);
+        assert!(effective.validate().is_ok());
+    }
+}
@@ -1,3 +1,6 @@
 pub mod app;
 pub mod state;
 pub mod input;
+pub mod intent;
+pub mod goal;
+pub mod sensor;
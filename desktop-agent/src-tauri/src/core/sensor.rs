@@ -0,0 +1,179 @@
+// 파일 위치: src-tauri/src/core/sensor.rs
+// 창 센서 (활성 창 / 보이는 창 / 최소화 / 복원)
+// OS별 구현은 commands/vision/ 아래에 있고, Core Loop와 vision 커맨드는 이 trait만 사용한다.
+// - Windows: Win32 (EnumWindows + GDI Region)
+// - Linux: X11 (_NET_CLIENT_LIST_STACKING)
+// - 그 외: 활성 창만 (active-win-pos-rs)
+
+use std::sync::Arc;
+
+use crate::commands::vision::{self, ActiveWindowInfo, WinRect, WindowInfo};
+use crate::utils::error::AppResult;
+
+pub trait WindowSensor: Send + Sync {
+    /// 현재 포커스된 창
+    fn active_window(&self) -> AppResult<ActiveWindowInfo>;
+
+    /// 화면에 실제로 보이는 창 목록 (위 -> 아래 순서, 가려진 창 제외)
+    fn visible_windows(&self) -> AppResult<Vec<WindowInfo>>;
+
+    /// 창 최소화. 우리 앱의 창(오버레이, 메인 윈도우 등)은 최소화하지 않음
+    fn minimize(&self, window: isize) -> AppResult<()>;
+
+    /// 창을 다시 표시하고 위치/크기를 rect로 복원
+    fn restore(&self, window: isize, rect: &WinRect) -> AppResult<()>;
+}
+
+/// 현재 OS의 창 센서
+pub fn platform_sensor() -> Arc<dyn WindowSensor> {
+    #[cfg(target_os = "windows")]
+    {
+        Arc::new(vision::win32::Win32Sensor)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Arc::new(vision::x11::X11Sensor::new())
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        Arc::new(fallback::ActiveWindowOnlySensor)
+    }
+}
+
+/// 보이는 창 목록/창 제어를 지원하지 않는 OS: 활성 창만 제공
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+mod fallback {
+    use super::*;
+    use crate::utils::error::AppError;
+
+    pub struct ActiveWindowOnlySensor;
+
+    impl WindowSensor for ActiveWindowOnlySensor {
+        fn active_window(&self) -> AppResult<ActiveWindowInfo> {
+            vision::_get_active_window_info_internal()
+        }
+
+        fn visible_windows(&self) -> AppResult<Vec<WindowInfo>> {
+            Ok(vec![])
+        }
+
+        fn minimize(&self, _window: isize) -> AppResult<()> {
+            Err(AppError::Platform("Window control is not supported on this OS".to_string()))
+        }
+
+        fn restore(&self, _window: isize, _rect: &WinRect) -> AppResult<()> {
+            Err(AppError::Platform("Window control is not supported on this OS".to_string()))
+        }
+    }
+}
+
+/// 테스트용 센서: 미리 정한 화면 구성(프레임)을 advance()마다 하나씩 넘기고 (마지막 프레임은 유지),
+/// minimize/restore 호출을 기록하며 현재 프레임에 반영한다. 활성 창 = 현재 프레임의 맨 위 창
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use crate::utils::error::AppError;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum SensorCall {
+        Minimize(isize),
+        Restore(isize, WinRect),
+    }
+
+    pub struct FakeSensor {
+        state: Mutex<FakeState>,
+    }
+
+    struct FakeState {
+        frames: VecDeque<Vec<WindowInfo>>,
+        current: Vec<WindowInfo>,
+        calls: Vec<SensorCall>,
+        failing: Vec<isize>,
+    }
+
+    impl FakeSensor {
+        pub fn new(frames: Vec<Vec<WindowInfo>>) -> Self {
+            let mut frames: VecDeque<_> = frames.into();
+            let current = frames.pop_front().unwrap_or_default();
+            FakeSensor { state: Mutex::new(FakeState { frames, current, calls: Vec::new(), failing: Vec::new() }) }
+        }
+
+        /// 다음 프레임으로 (Core Loop의 다음 센싱 주기)
+        pub fn advance(&self) {
+            let mut state = self.state.lock().unwrap();
+            if let Some(next) = state.frames.pop_front() {
+                state.current = next;
+            }
+        }
+
+        /// 이 창의 minimize/restore를 실패시킴 (권한이 높은 프로세스의 창 등)
+        pub fn fail_window(&self, hwnd: isize) {
+            self.state.lock().unwrap().failing.push(hwnd);
+        }
+
+        /// 테스트용 창 (hwnd와 제목, 위치만 지정)
+        pub fn window(hwnd: isize, title: &str, rect: WinRect) -> WindowInfo {
+            WindowInfo {
+                title: title.to_string(),
+                app_name: format!("{}.exe", title.to_lowercase()),
+                is_visible_on_screen: true,
+                rect,
+                hwnd,
+            }
+        }
+
+        pub fn calls(&self) -> Vec<SensorCall> {
+            self.state.lock().unwrap().calls.clone()
+        }
+    }
+
+    impl WindowSensor for FakeSensor {
+        fn active_window(&self) -> AppResult<ActiveWindowInfo> {
+            let state = self.state.lock()?;
+            let top = state.current.first().ok_or_else(|| AppError::Platform("No window on screen".to_string()))?;
+            Ok(ActiveWindowInfo {
+                timestamp_ms: 0,
+                title: top.title.clone(),
+                process_path: format!("/fake/{}", top.app_name),
+                app_name: top.app_name.clone(),
+                window_id: top.hwnd.to_string(),
+                process_id: 0,
+                x: f64::from(top.rect.left),
+                y: f64::from(top.rect.top),
+                width: f64::from(top.rect.right - top.rect.left),
+                height: f64::from(top.rect.bottom - top.rect.top),
+            })
+        }
+
+        fn visible_windows(&self) -> AppResult<Vec<WindowInfo>> {
+            Ok(self.state.lock()?.current.clone())
+        }
+
+        fn minimize(&self, window: isize) -> AppResult<()> {
+            let mut state = self.state.lock()?;
+            state.calls.push(SensorCall::Minimize(window));
+            if state.failing.contains(&window) {
+                return Err(AppError::Platform(format!("Failed to minimize window {}", window)));
+            }
+            state.current.retain(|w| w.hwnd != window);
+            Ok(())
+        }
+
+        fn restore(&self, window: isize, rect: &WinRect) -> AppResult<()> {
+            let mut state = self.state.lock()?;
+            state.calls.push(SensorCall::Restore(window, rect.clone()));
+            if state.failing.contains(&window) {
+                return Err(AppError::Platform(format!("Failed to restore window {}", window)));
+            }
+            match state.current.iter_mut().find(|w| w.hwnd == window) {
+                Some(existing) => existing.rect = rect.clone(),
+                None => state.current.push(FakeSensor::window(window, "Restored", rect.clone())),
+            }
+            Ok(())
+        }
+    }
+}
@@ -2,10 +2,107 @@ use serde::{Deserialize, Serialize};
 use crate::ai::inference::InferenceResult;
 
 // --- 1. 설정값 (시간 단위: 초) ---
-// 문서 Phase 4-2.A: 상태 정의 및 임계값
-const THRESHOLD_NOTIFY_SEC: f64 = 30.0;  // 30초: DRIFT 진입 (알림)
-const THRESHOLD_BLOCK_SEC: f64 = 60.0;   // 60초: DISTRACTED 진입 (차단)
-const SNOOZE_SEC: f64 = 10.0;            // 개입 후 10초간 대기 (피로도 관리)
+// 문서 Phase 4-2.A: 상태 정의 및 임계값 (사용자 설정이 없을 때의 기본값)
+pub const DEFAULT_THRESHOLD_NOTIFY_SEC: f64 = 30.0;  // 30초: DRIFT 진입 (알림)
+pub const DEFAULT_THRESHOLD_BLOCK_SEC: f64 = 60.0;   // 60초: DISTRACTED 진입 (차단)
+pub const DEFAULT_SNOOZE_SEC: f64 = 10.0;            // 개입 후 10초간 대기 (피로도 관리)
+
+// 게이지 상한 = 차단 임계값 + 여유분 (복귀 시 즉시 풀리지 않도록)
+const GAUGE_HEADROOM_SEC: f64 = 30.0;
+
+// [Positive Intervention] 휴식 권장 기본값
+pub const DEFAULT_REST_RECOMMEND_AFTER_SEC: f64 = 50.0 * 60.0; // 연속 몰입 50분
+pub const DEFAULT_REST_BREAK_SEC: f64 = 5.0 * 60.0;            // 권장 휴식 5분
+pub const DEFAULT_REST_PAUSE_SILENCE_SEC: f64 = 10.0;          // 입력 공백 10초 이상 = 자연스러운 멈춤
+
+// 자연스러운 멈춤이 끝내 오지 않으면, 권장 시점 + 10분 후에는 그냥 권장
+const REST_PAUSE_GRACE_SEC: f64 = 10.0 * 60.0;
+
+// --- 1-1. 런타임 설정 (managers::settings에서 주입) ---
+/// FSM 임계값과 InferenceResult별 시간 가중치
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StateConfig {
+    pub threshold_notify_sec: f64,
+    pub threshold_block_sec: f64,
+    pub snooze_sec: f64,
+
+    // 문서 Phase 4-2.B: 상황별 게이지 배속
+    pub multiplier_strong_outlier: f64,
+    pub multiplier_weak_outlier: f64,
+    pub active_thinking_factor: f64, // Safety 2: WeakOutlier + 마우스만 움직일 때 추가 감속 비율
+    pub multiplier_inlier: f64,      // Fast Recovery (음수)
+
+    // [Positive Intervention] 연속 FOCUS 시간 기반 휴식 권장
+    pub rest_recommendation_enabled: bool,
+    pub rest_recommend_after_sec: f64,
+    pub rest_break_sec: f64,
+    pub rest_pause_silence_sec: f64,
+}
+
+impl Default for StateConfig {
+    fn default() -> Self {
+        Self {
+            threshold_notify_sec: DEFAULT_THRESHOLD_NOTIFY_SEC,
+            threshold_block_sec: DEFAULT_THRESHOLD_BLOCK_SEC,
+            snooze_sec: DEFAULT_SNOOZE_SEC,
+            multiplier_strong_outlier: 1.0,
+            multiplier_weak_outlier: 0.5,
+            active_thinking_factor: 0.5,
+            multiplier_inlier: -2.0,
+            rest_recommendation_enabled: true,
+            rest_recommend_after_sec: DEFAULT_REST_RECOMMEND_AFTER_SEC,
+            rest_break_sec: DEFAULT_REST_BREAK_SEC,
+            rest_pause_silence_sec: DEFAULT_REST_PAUSE_SILENCE_SEC,
+        }
+    }
+}
+
+impl StateConfig {
+    /// 설정값 검증 (잘못된 값이 FSM에 들어가면 개입이 멈추거나 폭주함)
+    pub fn validate(&self) -> Result<(), String> {
+        let all_values = [
+            self.threshold_notify_sec,
+            self.threshold_block_sec,
+            self.snooze_sec,
+            self.multiplier_strong_outlier,
+            self.multiplier_weak_outlier,
+            self.active_thinking_factor,
+            self.multiplier_inlier,
+            self.rest_recommend_after_sec,
+            self.rest_break_sec,
+            self.rest_pause_silence_sec,
+        ];
+        if all_values.iter().any(|v| !v.is_finite()) {
+            return Err("FSM settings must be finite numbers".to_string());
+        }
+        if self.threshold_notify_sec <= 0.0 {
+            return Err("threshold_notify_sec must be greater than 0".to_string());
+        }
+        if self.threshold_block_sec <= self.threshold_notify_sec {
+            return Err("threshold_block_sec must be greater than threshold_notify_sec".to_string());
+        }
+        if self.snooze_sec < 0.0 {
+            return Err("snooze_sec must not be negative".to_string());
+        }
+        if self.multiplier_strong_outlier < 0.0 || self.multiplier_weak_outlier < 0.0 {
+            return Err("Outlier multipliers must not be negative".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.active_thinking_factor) {
+            return Err("active_thinking_factor must be between 0.0 and 1.0".to_string());
+        }
+        if self.multiplier_inlier > 0.0 {
+            return Err("multiplier_inlier must not be positive (recovery drains the gauge)".to_string());
+        }
+        if self.rest_recommend_after_sec <= 0.0 || self.rest_break_sec <= 0.0 {
+            return Err("Rest recommendation periods must be greater than 0".to_string());
+        }
+        if self.rest_pause_silence_sec < 0.0 {
+            return Err("rest_pause_silence_sec must not be negative".to_string());
+        }
+        Ok(())
+    }
+}
 
 // --- 2. 개입 트리거 (3단계) ---
 #[derive(Debug, Clone, PartialEq)]
@@ -13,15 +110,25 @@ pub enum InterventionTrigger {
     DoNothing,          // 평화
     TriggerNotification, // 주의 환기
     TriggerOverlay,      // 강제 차단
+    TriggerRestRecommendation, // 휴식 권장 (긍정적 개입)
+}
+
+/// 휴식 권장 오버레이로 전달되는 페이로드 ('rest-recommendation' 이벤트)
+#[derive(Debug, Clone, Serialize)]
+pub struct RestRecommendationPayload {
+    pub continuous_focus_sec: u64,
+    pub suggested_break_sec: u64,
 }
 
 // --- 3. FSM 상태 정의 ---
+#[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum FSMState {
     IDLE,       // (초기 상태)
     FOCUS,      // 몰입 상태 (게이지 < 30)
     DRIFT,      // 유예 상태 (30 <= 게이지 < 60)
     DISTRACTED, // 이탈 상태 (게이지 >= 60)
+    REST_RECOMMENDED, // 휴식 권장 상태 (장시간 FOCUS 후 자연스러운 멈춤 시점)
 }
 
 // --- 4. 상태 엔진 구조체 ---
@@ -37,18 +144,55 @@ pub struct StateEngine {
     
     // 스누즈(재알림 방지) 타이머 (마지막 개입 시각)
     last_intervention_ts: f64, 
+
+    // 임계값 및 배속 설정 (런타임 교체 가능)
+    config: StateConfig,
+
+    // [Positive Intervention] 연속 FOCUS 누적 시간 / 휴식 권장 경과 시간
+    continuous_focus_sec: f64,
+    rest_elapsed_sec: f64,
+
+    // Core Loop가 감지한 '자연스러운 멈춤' (다음 틱에서 소비)
+    natural_pause_pending: bool,
 }
 
 impl StateEngine {
     pub fn new() -> Self {
+        Self::with_config(StateConfig::default())
+    }
+
+    pub fn with_config(config: StateConfig) -> Self {
         Self {
             current_state: FSMState::IDLE,
             drift_gauge: 0.0,
             last_tick_ts: 0,
             last_intervention_ts: 0.0,
+            config,
+            continuous_focus_sec: 0.0,
+            rest_elapsed_sec: 0.0,
+            natural_pause_pending: false,
         }
     }
 
+    /// 실행 중인 엔진에 새 설정을 적용 (앱 재시작 불필요)
+    /// 게이지는 유지하고, 새 임계값 기준으로 상태만 다시 계산
+    pub fn apply_config(&mut self, config: StateConfig) -> Result<(), String> {
+        config.validate()?;
+        self.config = config;
+
+        let max_gauge = self.config.threshold_block_sec + GAUGE_HEADROOM_SEC;
+        self.drift_gauge = self.drift_gauge.min(max_gauge);
+        if self.current_state != FSMState::IDLE {
+            self.update_state();
+        }
+        println!("⚙️ StateEngine config applied: {:?}", self.config);
+        Ok(())
+    }
+
+    pub fn get_config(&self) -> &StateConfig {
+        &self.config
+    }
+
     /// [Process] 매 틱(Tick)마다 호출되어 상태를 갱신하고 행동을 결정
     /// - inference: ML 모델의 판단
     /// - now_ts: 현재 시스템 시간
@@ -72,10 +216,15 @@ impl StateEngine {
         let now_sec = now_ts as f64;
 
         // 2. 게이지 업데이트 (Time Integration)
-        let multiplier = self.calculate_multiplier(inference, is_mouse_active, has_recent_input);
+        let mut multiplier = self.calculate_multiplier(inference, is_mouse_active, has_recent_input);
+
+        // 휴식 권장 중의 이탈은 '권장된 휴식'이므로 게이지를 채우지 않음 (회복은 허용)
+        if self.current_state == FSMState::REST_RECOMMENDED {
+            multiplier = multiplier.min(0.0);
+        }
         
         // 게이지 누적/감소 (최소 0.0, 최대 차단 임계값 + 여유분까지 허용)
-        let max_gauge = THRESHOLD_BLOCK_SEC + 30.0;
+        let max_gauge = self.config.threshold_block_sec + GAUGE_HEADROOM_SEC;
         self.drift_gauge = (self.drift_gauge + (dt * multiplier)).clamp(0.0, max_gauge);
         
         // (Optional) 디버깅용: 게이지 상태 출력
@@ -83,9 +232,15 @@ impl StateEngine {
 
         // 3. 상태 전이 (Threshold Check)
         self.update_state();
+
+        // 3-1. 연속 몰입 추적 및 휴식 권장 (Positive Intervention)
+        if self.update_rest_cycle(dt) {
+            self.last_intervention_ts = now_sec;
+            return InterventionTrigger::TriggerRestRecommendation;
+        }
         
         // [디버깅용 로그 추가] 현재 게이지 상태 출력
-        println!("🔥 Gauge: {:.1} / 60.0 (State: {:?})", self.drift_gauge, self.current_state);
+        println!("🔥 Gauge: {:.1} / {:.1} (State: {:?})", self.drift_gauge, self.config.threshold_block_sec, self.current_state);
 
         // 4. 행동 결정 (Snooze Logic)
         self.decide_intervention(now_sec)
@@ -100,48 +255,92 @@ impl StateEngine {
     ) -> f64 {
         match inference {
             InferenceResult::StrongOutlier => {
-                // 문서: StrongOutlier는 급박한 이탈 -> 1.0배속 (또는 더 빠르게 설정 가능)
-                1.0 
+                // 문서: StrongOutlier는 급박한 이탈 -> 기본 1.0배속 (설정으로 조정 가능)
+                self.config.multiplier_strong_outlier
             },
             InferenceResult::WeakOutlier => {
                 // 기본 WeakOutlier는 0.5배속 (시간 지연)
-                let mut speed = 0.5;
+                let mut speed = self.config.multiplier_weak_outlier;
                 
                 // [Safety 2: Active Thinking Protection]
                 // 문서: "Input은 0이지만 Mouse는 움직임" -> 속도를 절반으로 줄임
                 if !has_recent_input && is_mouse_active {
-                    speed *= 0.5; // 즉, 0.25배속이 됨
+                    speed *= self.config.active_thinking_factor; // 기본값 기준 0.25배속이 됨
                 }
                 speed
             },
             InferenceResult::Inlier => {
                 // [Fast Recovery]
-                // 문서: 업무 복귀 시 빠르게 게이지 감소 (-2.0배속)
-                -2.0
+                // 문서: 업무 복귀 시 빠르게 게이지 감소 (기본 -2.0배속)
+                self.config.multiplier_inlier
             }
         }
     }
 
     /// [Internal] 게이지 수위에 따른 상태 변경
     fn update_state(&mut self) {
-        let next_state = if self.drift_gauge >= THRESHOLD_BLOCK_SEC {
+        let next_state = if self.drift_gauge >= self.config.threshold_block_sec {
             FSMState::DISTRACTED
-        } else if self.drift_gauge >= THRESHOLD_NOTIFY_SEC {
+        } else if self.drift_gauge >= self.config.threshold_notify_sec {
             FSMState::DRIFT
         } else {
             FSMState::FOCUS
         };
 
+        // 휴식 권장은 게이지와 무관한 상태이므로, 이탈 단계로 올라가지 않는 한 유지
+        if self.current_state == FSMState::REST_RECOMMENDED && next_state == FSMState::FOCUS {
+            return;
+        }
+
         if next_state != self.current_state {
             // println!("🔄 State Transition: {:?} -> {:?}", self.current_state, next_state);
             self.current_state = next_state;
         }
     }
 
+    /// [Internal] 연속 FOCUS 시간 누적 및 휴식 권장 진입/종료 (문서 08-01)
+    /// 반환값: 이번 틱에 REST_RECOMMENDED로 진입했는지 여부
+    fn update_rest_cycle(&mut self, dt: f64) -> bool {
+        let natural_pause = std::mem::take(&mut self.natural_pause_pending);
+
+        match self.current_state {
+            FSMState::FOCUS => {
+                self.continuous_focus_sec += dt;
+                if !self.config.rest_recommendation_enabled {
+                    return false;
+                }
+
+                let is_due = self.continuous_focus_sec >= self.config.rest_recommend_after_sec;
+                let is_overdue = self.continuous_focus_sec >= self.config.rest_recommend_after_sec + REST_PAUSE_GRACE_SEC;
+
+                // 시간이 됐다고 바로 끊지 않고, 입력 공백/앱 전환 같은 자연스러운 멈춤을 기다림
+                if is_due && (natural_pause || is_overdue) {
+                    self.current_state = FSMState::REST_RECOMMENDED;
+                    self.rest_elapsed_sec = 0.0;
+                    return true;
+                }
+            }
+            FSMState::REST_RECOMMENDED => {
+                self.rest_elapsed_sec += dt;
+                if self.rest_elapsed_sec >= self.config.rest_break_sec {
+                    // 휴식 시간 종료 -> 새로운 몰입 사이클 시작
+                    self.current_state = FSMState::FOCUS;
+                    self.continuous_focus_sec = 0.0;
+                    self.rest_elapsed_sec = 0.0;
+                }
+            }
+            _ => {
+                // DRIFT / DISTRACTED / IDLE: 연속 몰입이 끊김
+                self.continuous_focus_sec = 0.0;
+            }
+        }
+        false
+    }
+
     /// [Internal] 개입 여부 결정 (Snooze 적용)
     fn decide_intervention(&mut self, now_sec: f64) -> InterventionTrigger {
-        // 스누즈 체크: 마지막 개입 후 10초가 지났는가?
-        if (now_sec - self.last_intervention_ts) < SNOOZE_SEC {
+        // 스누즈 체크: 마지막 개입 후 snooze_sec(기본 10초)가 지났는가?
+        if (now_sec - self.last_intervention_ts) < self.config.snooze_sec {
             return InterventionTrigger::DoNothing;
         }
 
@@ -161,15 +360,36 @@ impl StateEngine {
     }
 
     /// 사용자 피드백 시 강제로 상태를 초기화하는 메서드
+    /// 세션 시작/일시정지/종료에도 호출되므로 dt 기준 시각도 초기화 (세션 사이 공백이 다음 틱의 dt로 들어가지 않도록)
     pub fn manual_reset(&mut self) {
         self.drift_gauge = 0.0;
+        self.last_tick_ts = 0;
         self.current_state = FSMState::FOCUS;
+        self.continuous_focus_sec = 0.0;
+        self.rest_elapsed_sec = 0.0;
+        self.natural_pause_pending = false;
         println!("✨ State Manually Reset by User Feedback");
     }
     
+    /// Core Loop가 자연스러운 멈춤(입력 공백 스파이크, 앱 전환)을 감지했을 때 호출
+    pub fn mark_natural_pause(&mut self) {
+        self.natural_pause_pending = true;
+    }
+
+    pub fn get_continuous_focus_sec(&self) -> f64 {
+        self.continuous_focus_sec
+    }
+
+    pub fn rest_recommendation_payload(&self) -> RestRecommendationPayload {
+        RestRecommendationPayload {
+            continuous_focus_sec: self.continuous_focus_sec as u64,
+            suggested_break_sec: self.config.rest_break_sec as u64,
+        }
+    }
+
     // UI 표시용 Getter
     pub fn get_gauge_ratio(&self) -> f64 {
-        (self.drift_gauge / THRESHOLD_BLOCK_SEC).min(1.0)
+        (self.drift_gauge / self.config.threshold_block_sec).min(1.0)
     }
 
     // commands.rs 에서 호출하는 헬퍼 메서드 추가
@@ -313,4 +533,181 @@ mod tests {
         simulate_ticks(&mut engine, 15, InferenceResult::Inlier, false, true);
         assert_eq!(engine.current_state, FSMState::FOCUS, "Should downgrade to FOCUS");
     }
+
+    #[test]
+    fn test_custom_config_thresholds_and_multipliers() {
+        let config = StateConfig {
+            threshold_notify_sec: 10.0,
+            threshold_block_sec: 20.0,
+            multiplier_strong_outlier: 2.0,
+            ..StateConfig::default()
+        };
+        let mut engine = StateEngine::with_config(config);
+        engine.last_tick_ts = 1000;
+
+        // 2.0배속 x 5초 = 게이지 10 -> DRIFT
+        let t1 = simulate_ticks(&mut engine, 5, InferenceResult::StrongOutlier, false, false);
+        assert_eq!(engine.current_state, FSMState::DRIFT);
+        assert_eq!(t1, InterventionTrigger::TriggerNotification);
+
+        // 추가 5초 -> 게이지 20 -> DISTRACTED
+        simulate_ticks(&mut engine, 5, InferenceResult::StrongOutlier, false, false);
+        assert_eq!(engine.current_state, FSMState::DISTRACTED);
+    }
+
+    #[test]
+    fn test_apply_config_reevaluates_state_live() {
+        let mut engine = StateEngine::new();
+        engine.last_tick_ts = 1000;
+
+        // 기본 설정: 게이지 20 -> FOCUS
+        simulate_ticks(&mut engine, 20, InferenceResult::StrongOutlier, false, false);
+        assert_eq!(engine.current_state, FSMState::FOCUS);
+
+        // 알림 임계값을 15초로 낮추면 게이지는 유지된 채 즉시 DRIFT로 재평가
+        let config = StateConfig { threshold_notify_sec: 15.0, ..StateConfig::default() };
+        engine.apply_config(config).expect("valid config");
+        assert_eq!(engine.current_state, FSMState::DRIFT);
+        assert!((engine.drift_gauge - 20.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_config_validation_rejects_invalid_values() {
+        let inverted = StateConfig { threshold_notify_sec: 60.0, threshold_block_sec: 30.0, ..StateConfig::default() };
+        assert!(inverted.validate().is_err());
+
+        let positive_recovery = StateConfig { multiplier_inlier: 1.0, ..StateConfig::default() };
+        assert!(positive_recovery.validate().is_err());
+
+        let nan_snooze = StateConfig { snooze_sec: f64::NAN, ..StateConfig::default() };
+        assert!(nan_snooze.validate().is_err());
+
+        // 잘못된 설정은 엔진에 적용되지 않아야 함
+        let mut engine = StateEngine::new();
+        assert!(engine.apply_config(inverted).is_err());
+        assert_eq!(engine.get_config(), &StateConfig::default());
+
+        assert!(StateConfig::default().validate().is_ok());
+    }
+
+    // 휴식 권장 테스트용: 60초 몰입 후 권장, 20초 휴식
+    fn rest_test_config() -> StateConfig {
+        StateConfig {
+            rest_recommend_after_sec: 60.0,
+            rest_break_sec: 20.0,
+            ..StateConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_rest_recommended_at_natural_pause() {
+        let mut engine = StateEngine::with_config(rest_test_config());
+        engine.last_tick_ts = 1000;
+
+        // 1. 권장 시점 이전의 멈춤은 무시
+        simulate_ticks(&mut engine, 30, InferenceResult::Inlier, false, true);
+        engine.mark_natural_pause();
+        let t1 = simulate_ticks(&mut engine, 1, InferenceResult::Inlier, false, true);
+        assert_eq!(t1, InterventionTrigger::DoNothing);
+        assert_eq!(engine.current_state, FSMState::FOCUS);
+
+        // 2. 권장 시점이 지나도 멈춤이 없으면 대기 (몰입 흐름 보호)
+        let t2 = simulate_ticks(&mut engine, 40, InferenceResult::Inlier, false, true);
+        assert_eq!(t2, InterventionTrigger::DoNothing);
+        assert_eq!(engine.current_state, FSMState::FOCUS);
+
+        // 3. 자연스러운 멈춤 발생 -> 휴식 권장
+        engine.mark_natural_pause();
+        let t3 = simulate_ticks(&mut engine, 1, InferenceResult::Inlier, false, true);
+        assert_eq!(t3, InterventionTrigger::TriggerRestRecommendation);
+        assert_eq!(engine.current_state, FSMState::REST_RECOMMENDED);
+        assert!(engine.rest_recommendation_payload().continuous_focus_sec >= 60);
+    }
+
+    #[test]
+    fn test_rest_recommended_after_grace_without_pause() {
+        let mut engine = StateEngine::with_config(rest_test_config());
+        engine.last_tick_ts = 1000;
+
+        // 멈춤이 끝내 없더라도 권장 시점 + 유예 시간이 지나면 권장
+        let total = (60.0 + REST_PAUSE_GRACE_SEC) as u64;
+        let t = simulate_ticks(&mut engine, total, InferenceResult::Inlier, false, true);
+        assert_eq!(t, InterventionTrigger::TriggerRestRecommendation);
+        assert_eq!(engine.current_state, FSMState::REST_RECOMMENDED);
+    }
+
+    #[test]
+    fn test_rest_window_freezes_gauge_and_expires() {
+        let mut engine = StateEngine::with_config(rest_test_config());
+        engine.last_tick_ts = 1000;
+
+        simulate_ticks(&mut engine, 61, InferenceResult::Inlier, false, true);
+        engine.mark_natural_pause();
+        simulate_ticks(&mut engine, 1, InferenceResult::Inlier, false, true);
+        assert_eq!(engine.current_state, FSMState::REST_RECOMMENDED);
+
+        // 휴식 중 딴짓(StrongOutlier)은 게이지를 채우지 않음
+        let t = simulate_ticks(&mut engine, 10, InferenceResult::StrongOutlier, false, false);
+        assert_eq!(t, InterventionTrigger::DoNothing);
+        assert_eq!(engine.drift_gauge, 0.0);
+        assert_eq!(engine.current_state, FSMState::REST_RECOMMENDED);
+
+        // 휴식 시간(20초) 종료 -> FOCUS 복귀, 연속 몰입 시간 초기화
+        simulate_ticks(&mut engine, 10, InferenceResult::Inlier, false, true);
+        assert_eq!(engine.current_state, FSMState::FOCUS);
+        assert!(engine.get_continuous_focus_sec() < 1.0);
+    }
+
+    #[test]
+    fn test_drift_breaks_continuous_focus() {
+        let mut engine = StateEngine::with_config(rest_test_config());
+        engine.last_tick_ts = 1000;
+
+        simulate_ticks(&mut engine, 50, InferenceResult::Inlier, false, true);
+        assert!(engine.get_continuous_focus_sec() >= 49.0);
+
+        // 30초 딴짓 -> DRIFT 진입 시 연속 몰입 시간 초기화
+        simulate_ticks(&mut engine, 30, InferenceResult::StrongOutlier, false, false);
+        assert_eq!(engine.current_state, FSMState::DRIFT);
+        assert_eq!(engine.get_continuous_focus_sec(), 0.0);
+    }
+
+    #[test]
+    fn test_gap_between_sessions_is_not_counted_as_focus() {
+        let mut engine = StateEngine::with_config(rest_test_config());
+        engine.last_tick_ts = 1000;
+
+        // 1. 첫 세션 30초 후 종료
+        simulate_ticks(&mut engine, 30, InferenceResult::Inlier, false, true);
+        engine.manual_reset();
+
+        // 2. 3시간 뒤 새 세션 시작: 첫 틱의 dt는 0 (공백이 연속 몰입 시간에 더해지지 않음)
+        engine.manual_reset();
+        let resumed_at = 1030 + 3 * 3600;
+        let t = engine.process(&InferenceResult::Inlier, resumed_at, false, true);
+        assert_eq!(t, InterventionTrigger::DoNothing);
+        assert_eq!(engine.current_state, FSMState::FOCUS);
+        assert_eq!(engine.get_continuous_focus_sec(), 0.0);
+
+        // 3. 이후 틱은 정상적으로 누적
+        engine.process(&InferenceResult::Inlier, resumed_at + 1, false, true);
+        assert_eq!(engine.get_continuous_focus_sec(), 1.0);
+    }
+
+    #[test]
+    fn test_long_pause_does_not_fire_on_resume() {
+        let mut engine = StateEngine::with_config(rest_test_config());
+        engine.last_tick_ts = 1000;
+        simulate_ticks(&mut engine, 50, InferenceResult::Inlier, false, true);
+
+        // 일시정지(pause_session) 후 1시간 동안 틱 없음
+        engine.manual_reset();
+
+        // 재개 후 첫 틱: 마지막 판단이 StrongOutlier로 남아 있어도 게이지/연속 몰입 시간이 튀지 않음
+        let t = engine.process(&InferenceResult::StrongOutlier, 1050 + 3600, false, false);
+        assert_eq!(t, InterventionTrigger::DoNothing);
+        assert_eq!(engine.current_state, FSMState::FOCUS);
+        assert_eq!(engine.drift_gauge, 0.0);
+        assert_eq!(engine.get_continuous_focus_sec(), 0.0);
+    }
 }
\ No newline at end of file
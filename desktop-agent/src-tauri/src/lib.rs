@@ -27,6 +27,36 @@ pub struct ActiveSessionInfo {
     pub session_id: String,
     pub task_id: Option<String>,
     pub start_time_s: u64, // Unix timestamp (seconds)
+    #[serde(default)]
+    pub intent: Option<String>, // 인텐트 프리셋 id (예: "research")
+    #[serde(default)]
+    pub scheduled_end_s: Option<u64>, // 스케줄 세션의 자동 종료 시각 (Unix timestamp, 수동 세션은 None)
+    #[serde(default)]
+    pub paused_total_s: u64, // 종료된 일시정지 구간의 누적 시간 (초)
+    #[serde(default)]
+    pub paused_at_s: Option<u64>, // 현재 일시정지 시작 시각 (진행 중이면 None)
+    #[serde(default)]
+    pub goal_duration_min: Option<u32>, // 목표 집중 시간 (분, 목표 없음 = None)
+    #[serde(default)]
+    pub goal_reached_s: Option<u64>, // 목표 달성 시각 (미달성 = None, 이후 overtime)
+}
+
+impl ActiveSessionInfo {
+    pub fn is_paused(&self) -> bool {
+        self.paused_at_s.is_some()
+    }
+
+    /// 진행 중인 일시정지를 포함한 누적 일시정지 시간
+    pub fn paused_duration_s(&self, now_s: u64) -> u64 {
+        self.paused_total_s + self.paused_at_s.map_or(0, |p| now_s.saturating_sub(p))
+    }
+
+    /// 일시정지를 제외한 순수 집중 시간 (widget-tick 표시용)
+    pub fn net_elapsed_s(&self, now_s: u64) -> u64 {
+        now_s
+            .saturating_sub(self.start_time_s)
+            .saturating_sub(self.paused_duration_s(now_s))
+    }
 }
 
 //  MainView.tsx가 invoke할 Task 데이터 모델 (handlers.ts 미러링) --- 중간 점검 production
@@ -61,11 +91,21 @@ pub type InputStatsArcMutex = Arc<Mutex<commands::input::InputStats>>;
 pub type StateEngineArcMutex = Arc<Mutex<core::state::StateEngine>>;
 
 // 전역 LSN(StorageManager) 상태 타입
-pub type StorageManagerArcMutex = Arc<Mutex<StorageManager>>;
+// 내부에서 writer 스레드/읽기 연결 풀로 동시성을 처리하므로 바깥 Mutex 없이 공유
+pub type StorageManagerArc = Arc<StorageManager>;
 
 // 전역 세션 상태
 pub type SessionStateArcMutex = Arc<Mutex<Option<ActiveSessionInfo>>>;
 
+// 전역 사용자 설정 상태 (LSN app_settings 테이블의 메모리 사본)
+pub type SettingsArcMutex = Arc<Mutex<managers::settings::UserSettings>>;
+
+// 창 센서 (OS별 구현을 Core Loop와 vision 커맨드가 공유)
+pub type WindowSensorArc = Arc<dyn core::sensor::WindowSensor>;
+
+// 진행 중인 딥 링크 로그인 시도 (CSRF state + PKCE verifier, 1회용)
+pub type PendingLoginArcMutex = Arc<Mutex<Option<managers::login_flow::PendingLogin>>>;
+
 // --- 공통 딥 링크 처리 함수 (핵심 로직 통합) ---
 // Single Instance와 on_open_url 양쪽에서 호출합니다.
 fn handle_deep_link(app: &AppHandle, url: &Url) {
@@ -76,56 +116,94 @@ fn handle_deep_link(app: &AppHandle, url: &Url) {
     let is_host_valid = url.host_str() == Some("auth");
     let is_path_valid = url.path() == "/callback";
 
-    if is_scheme_valid && is_host_valid && is_path_valid {
-        let query_pairs: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
-
-        if let (Some(access), Some(refresh), Some(email), Some(user_id)) = (
-            query_pairs.get("access_token"),
-            query_pairs.get("refresh_token"),
-            query_pairs.get("email"),
-            query_pairs.get("user_id"),
-        ) {
-            println!("Login detected for user: [REDACTED]");
-
-            // 2. LSN 저장 (AppHandle을 통해 State 접근)
-            if let Some(storage_state) = app.try_state::<StorageManagerArcMutex>() {
-                // Mutex Lock
-                match storage_state.lock() {
-                    Ok(storage) => {
-                        if let Err(e) = storage.save_auth_token(access, refresh, email, user_id) {
-                            eprintln!("CRITICAL: Failed to save auth token to LSN: {}", e);
-                        } else {
-                            println!("Auth token saved to LSN successfully.");
-
-                            // 3. 프론트엔드 알림 (화면 전환)
-                            if let Err(e) = app.emit("login-success", email) {
-                                eprintln!("Failed to emit login-success event: {}", e);
-                            }
-
-                            // 4. 메인 창 띄우기 (포커스)
-                            if let Some(window) = app.get_webview_window("main") {
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                                if window.is_minimized().unwrap_or(false) {
-                                    let _ = window.unminimize();
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => eprintln!("CRITICAL: Failed to lock storage manager mutex: {}", e),
-                }
-            } else {
-                eprintln!("CRITICAL: StorageManager state not found in AppHandle.");
-            }
-        } else {
-            eprintln!("Deep Link Error: Missing required query parameters (access/refresh/email)");
-        }
-    } else {
+    if !(is_scheme_valid && is_host_valid && is_path_valid) {
         println!(
             "Deep Link Skipped. Mismatch structure. Host={:?}, Path={}",
             url.host_str(),
             url.path()
         );
+        return;
+    }
+
+    let query_pairs: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
+
+    // 2. state 검증 (앱이 시작한 로그인인지, 만료되지 않았는지)
+    let now_s = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let verified = managers::login_flow::parse_callback_query(&query_pairs).and_then(|(code, state)| {
+        let pending_state = app.state::<PendingLoginArcMutex>();
+        let mut pending = pending_state.lock().unwrap_or_else(|e| e.into_inner());
+        managers::login_flow::validate_callback(&mut pending, &state, now_s).map(|login| (code, login))
+    });
+
+    let (code, login) = match verified {
+        Ok(v) => v,
+        Err(rejection) => {
+            reject_login_callback(app, rejection.as_str(), url);
+            return;
+        }
+    };
+
+    // 3. 1회용 code -> 토큰 교환 (PKCE verifier 동봉)
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let backend = app_handle.state::<Arc<utils::api::BackendCommunicator>>().inner().clone();
+        match backend.exchange_login_code(&code, &login.code_verifier).await {
+            Ok(tokens) => complete_login(&app_handle, &tokens),
+            Err(e) => {
+                eprintln!("Login: {}", e);
+                record_security_event(&app_handle, "login_code_exchange_failed", &e.to_string());
+                app_handle.emit("login-failed", "login_code_exchange_failed").ok();
+            }
+        }
+    });
+}
+
+/// 거부된 로그인 콜백: 보안 이벤트 기록 + UI 알림 (쿼리 값은 토큰이 섞여 있을 수 있어 키만 기록)
+fn reject_login_callback(app: &AppHandle, kind: &str, url: &Url) {
+    let keys: Vec<String> = url.query_pairs().map(|(k, _)| k.into_owned()).collect();
+    eprintln!("SECURITY: Rejected login callback ({}).", kind);
+    record_security_event(app, kind, &format!("query_keys={}", keys.join(",")));
+    app.emit("login-failed", kind).ok();
+}
+
+fn record_security_event(app: &AppHandle, kind: &str, detail: &str) {
+    if let Some(storage) = app.try_state::<StorageManagerArc>() {
+        if let Err(e) = storage.record_security_event(kind, detail) {
+            eprintln!("Failed to record security event: {}", e);
+        }
+    }
+}
+
+/// 교환된 토큰을 LSN에 저장하고 메인 화면으로 전환
+fn complete_login(app: &AppHandle, tokens: &utils::api::LoginTokenResponse) {
+    println!("Login detected for user: [REDACTED]");
+
+    // 1. LSN 저장 (AppHandle을 통해 State 접근)
+    let Some(storage) = app.try_state::<StorageManagerArc>() else {
+        eprintln!("CRITICAL: StorageManager state not found in AppHandle.");
+        return;
+    };
+    if let Err(e) = storage.save_auth_token(&tokens.access_token, &tokens.refresh_token, &tokens.email, &tokens.user_id) {
+        eprintln!("CRITICAL: Failed to save auth token to LSN: {}", e);
+        return;
+    }
+    println!("Auth token saved to LSN successfully.");
+
+    // 2. 프론트엔드 알림 (화면 전환)
+    if let Err(e) = app.emit("login-success", &tokens.email) {
+        eprintln!("Failed to emit login-success event: {}", e);
+    }
+
+    // 3. 메인 창 띄우기 (포커스)
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+        if window.is_minimized().unwrap_or(false) {
+            let _ = window.unminimize();
+        }
     }
 }
 
@@ -174,6 +252,7 @@ pub fn run() {
         .manage(input_stats_manager_state)
         .manage(state_engine_manager_state)
         .manage(backend_communicator_state)
+        .manage(Arc::new(Mutex::new(None)) as PendingLoginArcMutex)
         .setup(move |app| {
             setup_ml_engine(app)?;
             setup_storage_and_session(app)?;
@@ -200,14 +279,27 @@ pub fn run() {
             crate::commands::session::submit_feedback,
             crate::commands::session::start_session,
             crate::commands::session::end_session,
+            crate::commands::session::extend_session,
+            crate::commands::session::pause_session,
+            crate::commands::session::resume_session,
             crate::commands::task::get_tasks,
+            crate::commands::task::create_task,
+            crate::commands::task::update_task,
+            crate::commands::task::complete_task,
             crate::commands::session::get_current_session_info,
-            crate::commands::auth::login,
+            crate::commands::session::get_session_summary,
+            crate::commands::session::get_intent_presets,
+            crate::commands::auth::begin_login,
             crate::commands::auth::logout,
             crate::commands::auth::check_auth_status,
             crate::commands::window::hide_overlay,
             crate::commands::window::show_overlay,                   
             crate::commands::window::set_overlay_ignore_cursor_events, 
+            crate::commands::settings::get_settings,
+            crate::commands::settings::update_settings,
+            crate::commands::storage::get_storage_usage,
+            crate::commands::storage::export_data,
+            crate::commands::storage::delete_all_my_data,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -261,8 +353,11 @@ fn setup_storage_and_session(app: &mut tauri::App) -> Result<(), Box<dyn std::er
         None
     });
 
+    let initial_settings = managers::settings::load_or_default(&storage_manager);
+
     app.manage(Arc::new(Mutex::new(initial_session_state)) as SessionStateArcMutex);
-    app.manage(Arc::new(Mutex::new(storage_manager)) as StorageManagerArcMutex);
+    app.manage(Arc::new(Mutex::new(initial_settings)) as SettingsArcMutex);
+    app.manage(Arc::new(storage_manager) as StorageManagerArc);
 
     Ok(())
 }
@@ -288,19 +383,38 @@ fn start_background_services(app: &mut tauri::App) -> Result<(), Box<dyn std::er
     let app_handle = app.handle().clone();
     
     let session_manager_state = app.state::<SessionStateArcMutex>().inner().clone();
-    let storage_manager_state = app.state::<StorageManagerArcMutex>().inner().clone();
+    let storage_manager_state = app.state::<StorageManagerArc>().inner().clone();
     let input_stats_manager_state = app.state::<InputStatsArcMutex>().inner().clone();
+    let settings_state = app.state::<SettingsArcMutex>().inner().clone();
 
     core::input::start_input_listener(input_stats_manager_state.clone());
 
     use crate::core::app::AppCore;
-    app.manage(std::sync::Mutex::new(AppCore::new(&app_handle)));
+    let mut app_core = AppCore::new(&app_handle);
+    if let Ok(settings) = settings_state.lock() {
+        if let Err(e) = app_core.apply_base_state_config(settings.fsm.clone()) {
+            eprintln!("Failed to apply stored FSM settings: {}", e);
+        }
+    }
+    // 재시작 시 진행 중이던 세션의 인텐트 복원
+    if let Ok(session) = session_manager_state.lock() {
+        if let Some(intent) = session.as_ref().and_then(|s| s.intent.as_deref()) {
+            if let Err(e) = app_core.set_active_intent(Some(intent)) {
+                eprintln!("Failed to restore session intent: {}", e);
+            }
+        }
+    }
+    app.manage(std::sync::Mutex::new(app_core));
+
+    let window_sensor: WindowSensorArc = core::sensor::platform_sensor();
+    app.manage(window_sensor.clone());
 
     core::app::start_core_loop(
         app_handle.clone(),
         session_manager_state.clone(),
         storage_manager_state.clone(),
         input_stats_manager_state.clone(),
+        window_sensor,
     );
 
     managers::tray::setup_tray_menu(&app_handle)?;
@@ -312,6 +426,7 @@ fn start_background_services(app: &mut tauri::App) -> Result<(), Box<dyn std::er
 
     managers::sync::start_sync_loop(app_handle.clone());
     managers::schedule::start_monitor_loop(app_handle.clone());
+    managers::retention::start_retention_loop(app_handle.clone());
 
     Ok(())
 }
@@ -2,5 +2,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // 헤드리스 데이터 내보내기: desktop-agent export --from YYYY-MM-DD --to YYYY-MM-DD [--format csv]
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("export") {
+        std::process::exit(desktop_agent_lib::managers::export::run_cli(&args[2..]));
+    }
+
     desktop_agent_lib::run()
 }
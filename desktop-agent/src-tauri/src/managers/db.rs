@@ -0,0 +1,293 @@
+// LSN SQLite 연결 관리 (WAL 모드)
+// 쓰기는 전용 writer 스레드 1개가 채널로 받은 작업을 순서대로 실행하고, 조회는 별도의 읽기 연결들이 담당한다.
+// -> 내보내기/용량 집계 같은 느린 조회나 동기화 쓰기가 Core Loop(1초 FSM 틱)를 멈추지 않음.
+// Core Loop의 이벤트 INSERT는 응답을 기다리지 않는 배치 작업으로 큐에 쌓이고, writer가 모인 만큼 한 트랜잭션으로 저장한다.
+
+use rusqlite::{Connection, Transaction};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::utils::error::{AppError, AppResult};
+
+// 다른 연결이 쓰는 중이면 대기 (WAL에서는 체크포인트 등 짧은 구간만 해당)
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+// 동시에 조회할 수 있는 연결 수 (커맨드 + sync/schedule/retention 루프)
+pub const READ_CONNECTIONS: usize = 3;
+// 배치 작업을 한 트랜잭션으로 묶는 최대 개수
+const MAX_BATCH: usize = 256;
+
+type Job = Box<dyn FnOnce(&mut Connection) + Send>;
+type BatchedWrite = Box<dyn FnOnce(&Transaction) -> rusqlite::Result<()> + Send>;
+
+enum WriteMsg {
+    Job(Job),
+    Batched(BatchedWrite),
+}
+
+/// DB 위치 (테스트는 연결끼리 공유되는 인메모리 DB)
+#[derive(Debug, Clone)]
+pub enum DbTarget {
+    File(PathBuf),
+    #[cfg(test)]
+    Memory(String),
+}
+
+impl DbTarget {
+    /// 테스트마다 분리된 인메모리 DB 이름
+    #[cfg(test)]
+    pub fn unique_memory() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        DbTarget::Memory(format!("lsn-test-{}-{}", std::process::id(), NEXT_ID.fetch_add(1, Ordering::Relaxed)))
+    }
+
+    fn open(&self) -> rusqlite::Result<Connection> {
+        let conn = match self {
+            DbTarget::File(path) => Connection::open(path)?,
+            #[cfg(test)]
+            DbTarget::Memory(name) => Connection::open_with_flags(
+                format!("file:{}?mode=memory&cache=shared", name),
+                rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE
+                    | rusqlite::OpenFlags::SQLITE_OPEN_CREATE
+                    | rusqlite::OpenFlags::SQLITE_OPEN_URI,
+            )?,
+        };
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(conn)
+    }
+
+    /// 이미 있는 DB를 SQLITE_OPEN_READ_ONLY로 열기 (DB 파일을 새로 만들지 않고, 없으면 에러)
+    fn open_read_only(&self) -> rusqlite::Result<Connection> {
+        let flags = rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY
+            | rusqlite::OpenFlags::SQLITE_OPEN_URI
+            | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX;
+        let conn = match self {
+            DbTarget::File(path) => Connection::open_with_flags(path, flags)?,
+            #[cfg(test)]
+            DbTarget::Memory(name) => {
+                Connection::open_with_flags(format!("file:{}?mode=memory&cache=shared", name), flags)?
+            }
+        };
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(conn)
+    }
+}
+
+/// 쓰기 연결: WAL 모드 + synchronous=NORMAL (WAL에서는 커밋마다 fsync하지 않아도 DB가 손상되지 않음)
+pub fn open_writer(target: &DbTarget) -> AppResult<Connection> {
+    let conn = target.open()?;
+    let mode: String = conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+    // 인메모리 DB(테스트)는 WAL을 지원하지 않아 "memory"로 남음
+    if !mode.eq_ignore_ascii_case("wal") && !mode.eq_ignore_ascii_case("memory") {
+        return Err(AppError::Storage(format!("Failed to enable WAL mode (journal_mode = {})", mode)));
+    }
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    Ok(conn)
+}
+
+/// 읽기 연결: 실수로 쓰기를 하지 않도록 query_only
+fn open_reader(target: &DbTarget) -> AppResult<Connection> {
+    let conn = target.open()?;
+    conn.pragma_update(None, "query_only", true)?;
+    Ok(conn)
+}
+
+fn writer_stopped() -> AppError {
+    AppError::Internal("DB writer thread stopped".to_string())
+}
+
+/// 단일 writer 스레드 (쓰기 연결을 소유)
+pub struct DbWriter {
+    sender: Option<Sender<WriteMsg>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl DbWriter {
+    pub fn spawn(conn: Connection) -> AppResult<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::Builder::new()
+            .name("lsn-writer".to_string())
+            .spawn(move || run_writer(conn, receiver))?;
+        Ok(DbWriter { sender: Some(sender), handle: Some(handle) })
+    }
+
+    /// 쓰기를 받지 않는 writer (읽기 전용으로 연 DB). 모든 쓰기 요청은 에러
+    pub fn disabled() -> Self {
+        DbWriter { sender: None, handle: None }
+    }
+
+    fn send(&self, msg: WriteMsg) -> AppResult<()> {
+        let sender = self
+            .sender
+            .as_ref()
+            .ok_or_else(|| AppError::Storage("Database is opened read-only".to_string()))?;
+        sender.send(msg).map_err(|_| writer_stopped())
+    }
+
+    /// 쓰기 작업 실행 후 결과 대기 (앞서 큐에 들어온 작업이 모두 끝난 뒤 실행됨)
+    pub fn execute<T, F>(&self, job: F) -> AppResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> AppResult<T> + Send + 'static,
+    {
+        let (reply_tx, reply_rx) = mpsc::sync_channel(1);
+        self.send(WriteMsg::Job(Box::new(move |conn| {
+            let _ = reply_tx.send(job(conn));
+        })))?;
+        reply_rx.recv().map_err(|_| writer_stopped())?
+    }
+
+    /// 결과를 기다리지 않는 쓰기 (연속된 배치 작업은 한 트랜잭션으로 묶임). 실패는 writer가 로그로 남김
+    pub fn enqueue<F>(&self, write: F) -> AppResult<()>
+    where
+        F: FnOnce(&Transaction) -> rusqlite::Result<()> + Send + 'static,
+    {
+        self.send(WriteMsg::Batched(Box::new(write)))
+    }
+
+    /// 지금까지 큐에 들어온 쓰기가 모두 반영될 때까지 대기
+    pub fn flush(&self) -> AppResult<()> {
+        self.execute(|_| Ok(()))
+    }
+}
+
+impl Drop for DbWriter {
+    // 채널을 닫으면 writer는 남은 작업을 모두 처리한 뒤 종료
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn run_writer(mut conn: Connection, receiver: Receiver<WriteMsg>) {
+    let mut next = receiver.recv().ok();
+    while let Some(msg) = next.take() {
+        match msg {
+            WriteMsg::Job(job) => job(&mut conn),
+            WriteMsg::Batched(write) => {
+                let mut batch = vec![write];
+                while batch.len() < MAX_BATCH {
+                    match receiver.try_recv() {
+                        Ok(WriteMsg::Batched(write)) => batch.push(write),
+                        Ok(job) => {
+                            next = Some(job);
+                            break;
+                        }
+                        Err(_) => break,
+                    }
+                }
+                if let Err(e) = write_batch(&mut conn, batch) {
+                    eprintln!("LSN writer: batch commit failed: {}", e);
+                }
+            }
+        }
+        if next.is_none() {
+            next = receiver.recv().ok();
+        }
+    }
+}
+
+fn write_batch(conn: &mut Connection, batch: Vec<BatchedWrite>) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    for write in batch {
+        // 한 건이 실패해도 나머지는 저장 (실패한 문장만 롤백됨)
+        if let Err(e) = write(&tx) {
+            eprintln!("LSN writer: batched write failed: {}", e);
+        }
+    }
+    tx.commit()
+}
+
+/// 읽기 전용 연결 풀 (비어 있는 연결을 먼저 사용)
+pub struct ReadPool {
+    connections: Vec<Mutex<Connection>>,
+    next: AtomicUsize,
+}
+
+impl ReadPool {
+    pub fn open(target: &DbTarget, size: usize) -> AppResult<Self> {
+        let connections = (0..size.max(1))
+            .map(|_| open_reader(target).map(Mutex::new))
+            .collect::<AppResult<_>>()?;
+        Ok(ReadPool { connections, next: AtomicUsize::new(0) })
+    }
+
+    /// 읽기 전용 연결만으로 구성 (내보내기 CLI). 쓰기 권한 없이 열리므로 query_only를 풀어도 쓸 수 없음
+    pub fn open_read_only(target: &DbTarget, size: usize) -> AppResult<Self> {
+        let connections = (0..size.max(1))
+            .map(|_| target.open_read_only().map(Mutex::new).map_err(AppError::from))
+            .collect::<AppResult<_>>()?;
+        Ok(ReadPool { connections, next: AtomicUsize::new(0) })
+    }
+
+    pub fn get(&self) -> AppResult<MutexGuard<'_, Connection>> {
+        for conn in &self.connections {
+            match conn.try_lock() {
+                Ok(guard) => return Ok(guard),
+                Err(TryLockError::WouldBlock) => continue,
+                Err(TryLockError::Poisoned(e)) => return Err(e.into()),
+            }
+        }
+        // 모두 사용 중이면 돌아가며 대기
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.connections.len();
+        Ok(self.connections[index].lock()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_test_db() -> (DbWriter, ReadPool) {
+        let target = DbTarget::unique_memory();
+        let conn = open_writer(&target).unwrap();
+        conn.execute_batch("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL)").unwrap();
+        let readers = ReadPool::open(&target, 2).unwrap();
+        (DbWriter::spawn(conn).unwrap(), readers)
+    }
+
+    fn count(readers: &ReadPool) -> i64 {
+        readers.get().unwrap().query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_queued_writes_are_visible_after_flush() {
+        let (writer, readers) = open_test_db();
+        for i in 0..10 {
+            writer
+                .enqueue(move |tx| tx.execute("INSERT INTO items (name) VALUES (?1)", [format!("item-{}", i)]).map(|_| ()))
+                .unwrap();
+        }
+        writer.flush().unwrap();
+        assert_eq!(count(&readers), 10);
+
+        // 실패한 배치 작업은 건너뛰고 나머지는 저장
+        writer.enqueue(|tx| tx.execute("INSERT INTO items (name) VALUES (NULL)", []).map(|_| ())).unwrap();
+        writer.enqueue(|tx| tx.execute("INSERT INTO items (name) VALUES ('ok')", []).map(|_| ())).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(count(&readers), 11);
+    }
+
+    #[test]
+    fn test_execute_returns_result_and_readers_are_query_only() {
+        let (writer, readers) = open_test_db();
+        let id = writer
+            .execute(|conn| {
+                conn.execute("INSERT INTO items (name) VALUES ('a')", [])?;
+                Ok(conn.last_insert_rowid())
+            })
+            .unwrap();
+        assert_eq!(id, 1);
+
+        let err = writer.execute(|conn| conn.execute("INSERT INTO missing VALUES (1)", []).map_err(AppError::from));
+        assert_eq!(err.unwrap_err().code(), "storage");
+
+        assert!(readers.get().unwrap().execute("DELETE FROM items", []).is_err());
+        assert_eq!(count(&readers), 1);
+    }
+}
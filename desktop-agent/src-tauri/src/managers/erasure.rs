@@ -0,0 +1,227 @@
+// 전체 데이터 삭제 (Right to erasure)
+// LSN 전체 테이블, logs/, exports/, models/(개인화 모델)를 지우고 번들 기본 모델로 되돌린다.
+// 로그인 상태였다면 서버 데이터 삭제 요청을 대기열에 넣고 즉시 전송을 시도한다
+// (실패 시 sync 루프가 재시도하며, 전송될 때까지 토큰 행만 남는다).
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::core::app::{self, AppCore};
+use crate::managers::settings::UserSettings;
+use crate::managers::storage::WipedTable;
+use crate::managers::sync::{self, ErasureSend, COLLECTION_SCHEDULES, COLLECTION_TASKS};
+use crate::utils::api::BackendCommunicator;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::logging;
+use crate::{SessionStateArcMutex, SettingsArcMutex, StorageManagerArc};
+
+// 전체 삭제 진행 중 표시 (세션 시작 차단). 세션 상태 락을 잡은 채로 설정/확인
+static ERASURE_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// 전체 삭제가 진행 중인지 (세션을 시작하는 쪽은 세션 상태 락을 잡은 채로 확인할 것)
+pub fn is_erasure_in_progress() -> bool {
+    ERASURE_IN_PROGRESS.load(Ordering::SeqCst)
+}
+
+/// 삭제가 끝나면 (에러로 중단되어도) 진행 중 표시 해제
+struct ErasureInProgress;
+
+impl Drop for ErasureInProgress {
+    fn drop(&mut self) {
+        ERASURE_IN_PROGRESS.store(false, Ordering::SeqCst);
+    }
+}
+
+/// 디렉토리에서 삭제한 파일 수/크기
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FilesRemoved {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelReset {
+    pub removed: FilesRemoved,
+    pub restored_bundled: Vec<String>,
+    pub learned_cache_entries: u64,
+}
+
+/// 서버 데이터 삭제 요청 상태
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ServerErasure {
+    NotLoggedIn,
+    Completed { deleted: HashMap<String, u64> },
+    HandledBySync, // 동기화 루프가 같은 요청을 먼저 전송함 (결과는 동기화 로그 참고)
+    Queued { error: String }, // 일시 오류: 다음 동기화 주기에 재시도
+    Dropped { error: String },
+}
+
+/// delete_all_my_data 응답 (구성 요소별 삭제 결과)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErasureReport {
+    pub database: Vec<WipedTable>,
+    pub logs: FilesRemoved,
+    pub exports: FilesRemoved,
+    pub models: ModelReset,
+    pub settings_reset: bool,
+    pub server: ServerErasure,
+}
+
+/// 디렉토리 안의 파일/하위 디렉토리 전체 삭제 (디렉토리 자체는 유지, 없으면 0)
+pub fn remove_dir_contents(dir: &Path) -> AppResult<FilesRemoved> {
+    let mut removed = FilesRemoved::default();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(removed),
+        Err(e) => return Err(AppError::from(e).context(format!("Failed to read {:?}", dir))),
+    };
+
+    for entry in entries {
+        let path = entry?.path();
+        let meta = fs::symlink_metadata(&path).map_err(|e| AppError::from(e).context(format!("Failed to stat {:?}", path)))?;
+        if meta.is_dir() {
+            let nested = remove_dir_contents(&path)?;
+            removed.files += nested.files;
+            removed.bytes += nested.bytes;
+            fs::remove_dir(&path).map_err(|e| AppError::from(e).context(format!("Failed to remove {:?}", path)))?;
+        } else {
+            fs::remove_file(&path).map_err(|e| AppError::from(e).context(format!("Failed to remove {:?}", path)))?;
+            removed.files += 1;
+            removed.bytes += meta.len();
+        }
+    }
+    Ok(removed)
+}
+
+/// 전체 데이터 삭제 실행 (진행 중인 세션이 있으면 거부, 삭제 중에는 예약/수동 세션 시작 차단)
+pub async fn delete_all_my_data(app: &AppHandle) -> AppResult<ErasureReport> {
+    let session_state = app.state::<SessionStateArcMutex>();
+    let storage = app.state::<StorageManagerArc>().inner().clone();
+    let settings_state = app.state::<SettingsArcMutex>();
+    let app_core_state = app.state::<Mutex<AppCore>>();
+
+    let _in_progress = {
+        let session = session_state.lock()?;
+        if session.is_some() {
+            return Err(AppError::InvalidInput("End the current session before deleting all data.".to_string()));
+        }
+        if ERASURE_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+            return Err(AppError::InvalidInput("Data deletion is already in progress.".to_string()));
+        }
+        ErasureInProgress
+    };
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Platform(format!("Failed to get app_data_dir: {}", e)))?;
+
+    // 1. LSN: 로그인 상태면 서버 삭제 요청을 먼저 기록 (토큰 행은 전송 전까지 유지)
+    let (logged_in, database) = {
+        let logged_in = storage.load_auth_token()?.is_some();
+        if logged_in {
+            let now_s = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            storage.queue_erasure_request(now_s)?;
+        }
+        (logged_in, storage.wipe_all_data(logged_in)?)
+    };
+
+    // 2. 메모리 상태: 설정은 기본값으로
+    {
+        let mut settings = settings_state.lock()?;
+        *settings = UserSettings::default();
+        app.emit("settings-changed", &*settings).ok();
+    }
+
+    // 3. 모델: 엔진을 내려 파일 잠금 해제 -> models/ 비우기 -> 번들 기본 모델 복원 -> AppCore 재생성
+    let learned_cache_entries = {
+        let mut core = app_core_state.lock()?;
+        let cleared = core.inference_engine.as_mut().map_or(0, |engine| engine.clear_local_cache());
+        core.inference_engine = None;
+        cleared as u64
+    };
+    // 파일 락이 풀릴 시간을 짧게 부여 (윈도우 환경 필수)
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let model_dir = app_data_dir.join("models");
+    let removed_models = remove_dir_contents(&model_dir)?;
+    fs::create_dir_all(&model_dir)?;
+    let restored_bundled = app::restore_bundled_models(app, &model_dir);
+    {
+        let mut core = app_core_state.lock()?;
+        *core = AppCore::new(app);
+        core.apply_base_state_config(UserSettings::default().fsm).map_err(AppError::Model)?;
+    }
+
+    // 4. 파일: 활동 로그, 내보내기 결과
+    let logs = remove_dir_contents(&logging::get_log_dir(app).map_err(AppError::Platform)?)?;
+    let exports = remove_dir_contents(&app_data_dir.join("exports"))?;
+
+    // 5. 서버 삭제 요청 즉시 전송 시도
+    let server = if !logged_in {
+        ServerErasure::NotLoggedIn
+    } else {
+        let comm: Arc<BackendCommunicator> = app
+            .try_state::<Arc<BackendCommunicator>>()
+            .ok_or(AppError::Internal("BackendCommunicator state not found".to_string()))?
+            .inner()
+            .clone();
+        match sync::send_pending_erasure(app, &comm, &storage).await {
+            Ok(ErasureSend::Sent(response)) => ServerErasure::Completed { deleted: response.deleted },
+            Ok(ErasureSend::Dropped(error)) => ServerErasure::Dropped { error },
+            Ok(ErasureSend::NotPending) => ServerErasure::HandledBySync,
+            Err(error) => ServerErasure::Queued { error },
+        }
+    };
+
+    app.emit("data-changed", vec![COLLECTION_TASKS, COLLECTION_SCHEDULES]).ok();
+    println!("All local data deleted (server: {:?}).", server);
+
+    Ok(ErasureReport {
+        database,
+        logs,
+        exports,
+        models: ModelReset { removed: removed_models, restored_bundled, learned_cache_entries },
+        settings_reset: true,
+        server,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_dir_contents_keeps_dir() {
+        let dir = std::env::temp_dir().join(format!("ff-erasure-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("a.jsonl"), b"12345").unwrap();
+        fs::write(dir.join("nested/b.onnx"), b"123").unwrap();
+
+        assert_eq!(remove_dir_contents(&dir).unwrap(), FilesRemoved { files: 2, bytes: 8 });
+        assert!(dir.exists());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(remove_dir_contents(&dir).unwrap(), FilesRemoved::default());
+    }
+
+    #[test]
+    fn test_erasure_in_progress_clears_when_dropped() {
+        assert!(!is_erasure_in_progress());
+        ERASURE_IN_PROGRESS.store(true, Ordering::SeqCst);
+        {
+            let _in_progress = ErasureInProgress;
+            assert!(is_erasure_in_progress());
+        }
+        assert!(!is_erasure_in_progress());
+    }
+}
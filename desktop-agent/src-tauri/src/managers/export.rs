@@ -0,0 +1,371 @@
+// 로컬 데이터 내보내기 모듈 (JSON Lines / CSV)
+// 백엔드를 거치지 않고 LSN에 남아 있는 이벤트, 세션 기록, 피드백, 스케줄을 파일로 덤프한다.
+// 창 제목은 저장된 그대로(시맨틱 토큰) 내보내며 원문 복원은 하지 않음.
+// 앱 UI(export_data 커맨드)와 헤드리스 CLI(`desktop-agent export ...`)가 같은 함수를 사용.
+
+use chrono::{Duration as ChronoDuration, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::managers::storage::{
+    CachedEvent, CachedFeedback, LocalSchedule, SessionHistoryRecord, StorageManager,
+};
+use crate::utils::error::{AppError, AppResult};
+
+// tauri.conf.json의 identifier (헤드리스 실행 시 app_data_dir 계산용)
+const APP_IDENTIFIER: &str = "com.force-focus.app";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Jsonl,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "csv" => Ok(ExportFormat::Csv),
+            other => Err(format!("Unknown export format '{}' (expected jsonl or csv)", other)),
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// 데이터셋별 내보내기 결과
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportedFile {
+    pub dataset: String,
+    pub path: String,
+    pub rows: u64,
+}
+
+/// export_data 응답
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportReport {
+    pub from_s: u64,
+    pub to_s: u64,
+    pub format: ExportFormat,
+    pub files: Vec<ExportedFile>,
+}
+
+/// CSV 한 행으로 펼칠 수 있는 레코드 (JSONL은 serde 직렬화 그대로 사용)
+trait ExportRecord: Serialize {
+    const HEADER: &'static [&'static str];
+    fn csv_fields(&self) -> Vec<String>;
+}
+
+fn opt_to_string<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(|v| v.to_string()).unwrap_or_default()
+}
+
+impl ExportRecord for CachedEvent {
+    const HEADER: &'static [&'static str] = &[
+        "id", "session_id", "client_event_id", "timestamp", "app_name", "window_title", "activity_vector",
+    ];
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.session_id.clone(),
+            self.client_event_id.clone(),
+            self.timestamp.to_string(),
+            self.app_name.clone(),
+            self.window_title.clone(),
+            self.activity_vector.clone(),
+        ]
+    }
+}
+
+impl ExportRecord for SessionHistoryRecord {
+    const HEADER: &'static [&'static str] = &[
+        "session_id", "task_id", "intent", "start_time_s", "end_time_s", "goal_duration_min",
+        "user_evaluation_score", "paused_s", "notification_count", "overlay_count", "rest_recommendation_count",
+    ];
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.session_id.clone(),
+            opt_to_string(&self.task_id),
+            opt_to_string(&self.intent),
+            self.start_time_s.to_string(),
+            opt_to_string(&self.end_time_s),
+            self.goal_duration_min.to_string(),
+            opt_to_string(&self.user_evaluation_score),
+            self.paused_s.to_string(),
+            self.notification_count.to_string(),
+            self.overlay_count.to_string(),
+            self.rest_recommendation_count.to_string(),
+        ]
+    }
+}
+
+impl ExportRecord for CachedFeedback {
+    const HEADER: &'static [&'static str] = &["id", "timestamp", "event_id", "feedback_type"];
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.timestamp.to_string(),
+            self.event_id.clone(),
+            self.feedback_type.clone(),
+        ]
+    }
+}
+
+impl ExportRecord for LocalSchedule {
+    const HEADER: &'static [&'static str] = &[
+        "id", "user_id", "task_id", "name", "start_time", "end_time", "days_of_week", "start_date", "is_active",
+    ];
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.user_id.clone(),
+            opt_to_string(&self.task_id),
+            self.name.clone(),
+            self.start_time.clone(),
+            self.end_time.clone(),
+            serde_json::to_string(&self.days_of_week).unwrap_or_default(),
+            opt_to_string(&self.start_date),
+            self.is_active.to_string(),
+        ]
+    }
+}
+
+/// RFC 4180: 쉼표/따옴표/줄바꿈이 있으면 따옴표로 감싸고 내부 따옴표는 두 번 씀
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn write_dataset<T: ExportRecord>(
+    out_dir: &Path,
+    dataset: &str,
+    records: &[T],
+    format: ExportFormat,
+) -> AppResult<ExportedFile> {
+    let path = out_dir.join(format!("{}.{}", dataset, format.extension()));
+    let file = File::create(&path).map_err(|e| AppError::from(e).context(format!("Failed to create {:?}", path)))?;
+    let mut writer = BufWriter::new(file);
+    let write_err = |e: std::io::Error| AppError::from(e).context(format!("Failed to write {:?}", path));
+
+    match format {
+        ExportFormat::Jsonl => {
+            for record in records {
+                let line = serde_json::to_string(record)?;
+                writeln!(writer, "{}", line).map_err(write_err)?;
+            }
+        }
+        ExportFormat::Csv => {
+            writeln!(writer, "{}", T::HEADER.join(",")).map_err(write_err)?;
+            for record in records {
+                let row: Vec<String> = record.csv_fields().iter().map(|f| csv_escape(f)).collect();
+                writeln!(writer, "{}", row.join(",")).map_err(write_err)?;
+            }
+        }
+    }
+    writer.flush().map_err(write_err)?;
+
+    Ok(ExportedFile {
+        dataset: dataset.to_string(),
+        path: path.to_string_lossy().to_string(),
+        rows: records.len() as u64,
+    })
+}
+
+/// 기간 [from_s, to_s) 데이터를 out_dir에 데이터셋별 파일로 저장
+/// (events, sessions, feedback, schedules - 스케줄은 기간과 무관하게 전체)
+pub fn export_data(
+    storage: &StorageManager,
+    from_s: u64,
+    to_s: u64,
+    format: ExportFormat,
+    out_dir: &Path,
+) -> AppResult<ExportReport> {
+    if from_s >= to_s {
+        return Err(AppError::InvalidInput("Export range is empty (from must be before to).".to_string()));
+    }
+    fs::create_dir_all(out_dir).map_err(|e| AppError::from(e).context(format!("Failed to create {:?}", out_dir)))?;
+
+    let files = vec![
+        write_dataset(out_dir, "events", &storage.get_events_in_range(from_s, to_s)?, format)?,
+        write_dataset(out_dir, "sessions", &storage.get_session_histories_in_range(from_s, to_s)?, format)?,
+        write_dataset(out_dir, "feedback", &storage.get_feedbacks_in_range(from_s, to_s)?, format)?,
+        write_dataset(out_dir, "schedules", &storage.get_all_schedules()?, format)?,
+    ];
+
+    Ok(ExportReport { from_s, to_s, format, files })
+}
+
+/// "YYYY-MM-DD" 두 날짜(로컬 시간, 양 끝 포함)를 [from_s, to_s) 초 범위로 변환
+pub fn parse_date_range(from_date: &str, to_date: &str) -> AppResult<(u64, u64)> {
+    let parse = |value: &str| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|e| AppError::InvalidInput(format!("Invalid date '{}' (expected YYYY-MM-DD): {}", value, e)))
+    };
+    let local_midnight_s = |date: NaiveDate| -> AppResult<u64> {
+        let midnight = date
+            .and_hms_opt(0, 0, 0)
+            .ok_or_else(|| AppError::InvalidInput("Invalid date".to_string()))?;
+        Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .map(|dt| dt.timestamp().max(0) as u64)
+            .ok_or_else(|| AppError::InvalidInput(format!("Local midnight does not exist for {}", date)))
+    };
+
+    let from = parse(from_date)?;
+    let to = parse(to_date)?;
+    if to < from {
+        return Err(AppError::InvalidInput(format!("Export range is reversed ({} > {}).", from_date, to_date)));
+    }
+    Ok((local_midnight_s(from)?, local_midnight_s(to + ChronoDuration::days(1))?))
+}
+
+/// Tauri의 app_data_dir와 같은 위치 (OS 데이터 디렉토리/identifier)
+fn default_app_data_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+    base.map(|dir| dir.join(APP_IDENTIFIER))
+}
+
+const CLI_USAGE: &str = "Usage: desktop-agent export --from YYYY-MM-DD --to YYYY-MM-DD \
+[--format jsonl|csv] [--out DIR] [--data-dir DIR]";
+
+/// 헤드리스 내보내기 진입점 (main.rs에서 `export` 서브커맨드로 호출). 프로세스 종료 코드 반환
+pub fn run_cli(args: &[String]) -> i32 {
+    attach_parent_console();
+
+    match run_cli_inner(args) {
+        Ok(report) => {
+            for file in &report.files {
+                println!("{}\t{} rows\t{}", file.dataset, file.rows, file.path);
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Export failed: {}", e);
+            eprintln!("{}", CLI_USAGE);
+            2
+        }
+    }
+}
+
+/// 릴리스 Windows 빌드는 GUI 서브시스템(windows_subsystem = "windows")이라 콘솔이 없으므로,
+/// 실행한 터미널의 콘솔에 붙어서 결과/사용법/에러가 보이게 함 (탐색기에서 실행하는 등 부모 콘솔이 없으면 무시)
+#[cfg(target_os = "windows")]
+fn attach_parent_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_parent_console() {}
+
+fn run_cli_inner(args: &[String]) -> Result<ExportReport, String> {
+    let mut from_date = None;
+    let mut to_date = None;
+    let mut format = ExportFormat::Jsonl;
+    let mut out_dir = None;
+    let mut data_dir = None;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let mut value = || iter.next().cloned().ok_or_else(|| format!("Missing value for {}", flag));
+        match flag.as_str() {
+            "--from" => from_date = Some(value()?),
+            "--to" => to_date = Some(value()?),
+            "--format" => format = ExportFormat::parse(&value()?)?,
+            "--out" => out_dir = Some(PathBuf::from(value()?)),
+            "--data-dir" => data_dir = Some(PathBuf::from(value()?)),
+            other => return Err(format!("Unknown argument '{}'", other)),
+        }
+    }
+
+    let from_date = from_date.ok_or("--from is required")?;
+    let to_date = to_date.ok_or("--to is required")?;
+    let (from_s, to_s) = parse_date_range(&from_date, &to_date)?;
+
+    let data_dir = data_dir
+        .or_else(default_app_data_dir)
+        .ok_or("Cannot determine app data directory (use --data-dir)")?;
+    let out_dir =
+        out_dir.unwrap_or_else(|| PathBuf::from(format!("force-focus-export-{}_{}", from_date, to_date)));
+
+    let storage = StorageManager::open_read_only(&data_dir)?;
+    Ok(export_data(&storage, from_s, to_s, format, &out_dir)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("{\"x\":1}"), "\"{\"\"x\"\":1}\"");
+    }
+
+    #[test]
+    fn test_parse_date_range_is_inclusive() {
+        let (from_s, to_s) = parse_date_range("2026-03-02", "2026-03-02").unwrap();
+        assert!(to_s > from_s && to_s - from_s <= 25 * 60 * 60);
+        assert_eq!(parse_date_range("2026-03-03", "2026-03-02").unwrap_err().code(), "invalid_input");
+        assert_eq!(parse_date_range("03/02/2026", "2026-03-02").unwrap_err().code(), "invalid_input");
+    }
+
+    #[test]
+    fn test_export_data_jsonl_and_csv() {
+        let storage = StorageManager::new_in_memory().unwrap();
+        storage
+            .cache_event("s-1", "evt-1", "code.exe", "main rs, editor", "{\"mouse\":1}", "FOCUS")
+            .unwrap();
+        storage.cache_feedback("evt-1", "is_work").unwrap();
+
+        let now_s = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let out_dir = std::env::temp_dir().join(format!("ff-export-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&out_dir);
+
+        let report = export_data(&storage, now_s - 60, now_s + 60, ExportFormat::Jsonl, &out_dir).unwrap();
+        let rows: Vec<(&str, u64)> = report.files.iter().map(|f| (f.dataset.as_str(), f.rows)).collect();
+        assert_eq!(rows, vec![("events", 1), ("sessions", 0), ("feedback", 1), ("schedules", 0)]);
+        let events = fs::read_to_string(out_dir.join("events.jsonl")).unwrap();
+        let event: serde_json::Value = serde_json::from_str(events.trim()).unwrap();
+        assert_eq!(event["window_title"], "main rs, editor");
+
+        export_data(&storage, now_s - 60, now_s + 60, ExportFormat::Csv, &out_dir).unwrap();
+        let csv = fs::read_to_string(out_dir.join("events.csv")).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "id,session_id,client_event_id,timestamp,app_name,window_title,activity_vector");
+        assert!(lines[1].ends_with(",code.exe,\"main rs, editor\",\"{\"\"mouse\"\":1}\""));
+        // 헤더만 있는 빈 데이터셋
+        assert_eq!(fs::read_to_string(out_dir.join("sessions.csv")).unwrap().lines().count(), 1);
+
+        // 기간 밖
+        let report = export_data(&storage, now_s + 60, now_s + 120, ExportFormat::Csv, &out_dir).unwrap();
+        assert_eq!(report.files[0].rows, 0);
+
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+// 세션 히스토리 / 요약 리포트 모듈
+// session_history(세션 메타데이터 + 개입 횟수)와 이벤트 샘플(cached_events + session_event_rollup)로
+// 세션 요약을 계산한다. 이벤트가 서버로 동기화되어 삭제된 뒤에도 오프라인으로 동작.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::managers::storage::{SessionHistoryRecord, SessionStateSample, StorageManager};
+use crate::utils::error::{AppError, AppResult};
+
+// Core Loop Slow Path 주기 = 이벤트 1건이 대표하는 시간 (초)
+pub const EVENT_SAMPLE_INTERVAL_SEC: u64 = 5;
+const TOP_DISTRACTING_APPS: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppDistraction {
+    pub app_name: String,
+    pub distracted_sec: u64,
+}
+
+/// get_session_summary 응답
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub task_id: Option<String>,
+    pub intent: Option<String>,
+    pub start_time_s: u64,
+    pub end_time_s: Option<u64>, // 진행 중이면 None
+    pub goal_duration_min: u32,
+    pub user_evaluation_score: Option<u8>,
+    pub paused_sec: u64,
+    // FSM 상태별 체류 시간 (예: {"FOCUS": 1200, "DRIFT": 35})
+    pub state_durations_sec: BTreeMap<String, u64>,
+    pub notification_count: u32,
+    pub overlay_count: u32,
+    pub rest_recommendation_count: u32,
+    // DRIFT/DISTRACTED 상태에서 가장 오래 머문 앱
+    pub top_distracting_apps: Vec<AppDistraction>,
+}
+
+/// LSN 데이터로 세션 요약 계산
+pub fn build_session_summary(storage: &StorageManager, session_id: &str) -> AppResult<SessionSummary> {
+    let record = storage
+        .get_session_history(session_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Session '{}' not found in local history.", session_id)))?;
+    let samples = storage.get_session_state_samples(session_id)?;
+
+    Ok(summarize(record, &samples))
+}
+
+fn summarize(record: SessionHistoryRecord, samples: &[SessionStateSample]) -> SessionSummary {
+    let mut state_durations_sec: BTreeMap<String, u64> = BTreeMap::new();
+    let mut distracted_by_app: HashMap<&str, u64> = HashMap::new();
+
+    for sample in samples {
+        let sec = sample.sample_count * EVENT_SAMPLE_INTERVAL_SEC;
+        *state_durations_sec.entry(sample.fsm_state.clone()).or_insert(0) += sec;
+
+        if sample.fsm_state == "DRIFT" || sample.fsm_state == "DISTRACTED" {
+            *distracted_by_app.entry(sample.app_name.as_str()).or_insert(0) += sec;
+        }
+    }
+
+    let mut top_distracting_apps: Vec<AppDistraction> = distracted_by_app
+        .into_iter()
+        .map(|(app_name, distracted_sec)| AppDistraction { app_name: app_name.to_string(), distracted_sec })
+        .collect();
+    // 시간 내림차순, 동률이면 이름순 (결과 고정)
+    top_distracting_apps.sort_by(|a, b| b.distracted_sec.cmp(&a.distracted_sec).then(a.app_name.cmp(&b.app_name)));
+    top_distracting_apps.truncate(TOP_DISTRACTING_APPS);
+
+    SessionSummary {
+        session_id: record.session_id,
+        task_id: record.task_id,
+        intent: record.intent,
+        start_time_s: record.start_time_s,
+        end_time_s: record.end_time_s,
+        goal_duration_min: record.goal_duration_min,
+        user_evaluation_score: record.user_evaluation_score,
+        paused_sec: record.paused_s,
+        state_durations_sec,
+        notification_count: record.notification_count,
+        overlay_count: record.overlay_count,
+        rest_recommendation_count: record.rest_recommendation_count,
+        top_distracting_apps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::state::InterventionTrigger;
+    use crate::ActiveSessionInfo;
+
+    fn session(session_id: &str) -> ActiveSessionInfo {
+        ActiveSessionInfo {
+            session_id: session_id.to_string(),
+            task_id: Some("task-1".to_string()),
+            start_time_s: 1000,
+            intent: None,
+            scheduled_end_s: None,
+            paused_total_s: 0,
+            paused_at_s: None,
+            goal_duration_min: None,
+            goal_reached_s: None,
+        }
+    }
+
+    #[test]
+    fn test_summary_survives_event_sync() {
+        let storage = StorageManager::new_in_memory().unwrap();
+        storage.insert_session_history(&session("s-1"), 50).unwrap();
+
+        for (i, (app, state)) in [
+            ("code.exe", "FOCUS"),
+            ("code.exe", "FOCUS"),
+            ("chrome.exe", "DRIFT"),
+            ("chrome.exe", "DISTRACTED"),
+            ("slack.exe", "DRIFT"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            storage.cache_event("s-1", &format!("evt-{}", i), app, "", "{}", state).unwrap();
+        }
+        storage.cache_event("s-2", "evt-other", "game.exe", "", "{}", "DISTRACTED").unwrap(); // 다른 세션
+
+        storage.record_session_intervention("s-1", &InterventionTrigger::TriggerNotification).unwrap();
+        storage.record_session_intervention("s-1", &InterventionTrigger::TriggerOverlay).unwrap();
+        storage.record_session_intervention("s-1", &InterventionTrigger::DoNothing).unwrap();
+        storage.finish_session_history("s-1", 4000, 4, 120).unwrap();
+
+        let before = build_session_summary(&storage, "s-1").unwrap();
+
+        // 서버 동기화 후 이벤트 삭제 -> 요약은 그대로
+        let ids: Vec<i64> = storage.get_unsynced_events(2).unwrap().iter().map(|e| e.id).collect();
+        storage.delete_events_by_ids(&ids).unwrap();
+        let after = build_session_summary(&storage, "s-1").unwrap();
+        assert_eq!(before, after);
+
+        assert_eq!(after.end_time_s, Some(4000));
+        assert_eq!(after.user_evaluation_score, Some(4));
+        assert_eq!(after.paused_sec, 120);
+        assert_eq!(after.state_durations_sec.get("FOCUS"), Some(&10));
+        assert_eq!(after.state_durations_sec.get("DRIFT"), Some(&10));
+        assert_eq!(after.state_durations_sec.get("DISTRACTED"), Some(&5));
+        assert_eq!((after.notification_count, after.overlay_count), (1, 1));
+        assert_eq!(after.top_distracting_apps, vec![
+            AppDistraction { app_name: "chrome.exe".to_string(), distracted_sec: 10 },
+            AppDistraction { app_name: "slack.exe".to_string(), distracted_sec: 5 },
+        ]);
+
+        assert_eq!(build_session_summary(&storage, "unknown").unwrap_err().code(), "not_found");
+    }
+}
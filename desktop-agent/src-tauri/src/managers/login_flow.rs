@@ -0,0 +1,177 @@
+// 딥 링크 로그인 보호(CSRF state + PKCE) 모듈
+// 브라우저를 열기 전에 1회용 state와 PKCE code_verifier를 만들어 메모리에만 보관하고,
+// force-focus://auth/callback 콜백은 state가 일치하고 만료되지 않은 경우에만 받아들인다.
+// 콜백에는 토큰 대신 1회용 code만 실려 오며, code_verifier와 함께 서버에서 토큰으로 교환한다.
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+// 로그인 시작 후 콜백을 기다리는 최대 시간 (초)
+pub const LOGIN_STATE_TTL_SEC: u64 = 600;
+
+/// 진행 중인 로그인 시도 (앱 메모리에만 존재, 1회용)
+#[derive(Debug, Clone)]
+pub struct PendingLogin {
+    pub state: String,
+    pub code_verifier: String,
+    pub created_at_s: u64,
+}
+
+/// 콜백 거부 사유 (security_events.kind로 기록)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CallbackRejection {
+    NoPendingLogin,      // 앱이 로그인을 시작하지 않았는데 콜백이 들어옴
+    StateMismatch,       // state 불일치 (위조된 링크)
+    Expired,             // TTL 초과
+    MissingParams,       // code/state 누락
+    LegacyTokenCallback, // 토큰을 직접 실은 구버전 형식 콜백
+}
+
+impl CallbackRejection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CallbackRejection::NoPendingLogin => "login_callback_no_pending",
+            CallbackRejection::StateMismatch => "login_callback_state_mismatch",
+            CallbackRejection::Expired => "login_callback_expired",
+            CallbackRejection::MissingParams => "login_callback_missing_params",
+            CallbackRejection::LegacyTokenCallback => "login_callback_legacy_tokens",
+        }
+    }
+}
+
+impl PendingLogin {
+    pub fn new(now_s: u64) -> Self {
+        Self {
+            state: random_url_safe(32),
+            code_verifier: random_url_safe(32),
+            created_at_s: now_s,
+        }
+    }
+
+    pub fn is_expired(&self, now_s: u64) -> bool {
+        now_s.saturating_sub(self.created_at_s) > LOGIN_STATE_TTL_SEC
+    }
+
+    /// 브라우저로 열 로그인 URL (state, code_challenge는 base64url이라 별도 인코딩 불필요)
+    pub fn login_url(&self, api_base_url: &str) -> String {
+        format!(
+            "{}/auth/desktop/google/login?state={}&code_challenge={}&code_challenge_method=S256",
+            api_base_url,
+            self.state,
+            code_challenge(&self.code_verifier)
+        )
+    }
+}
+
+/// PKCE S256: base64url(sha256(code_verifier))
+pub fn code_challenge(code_verifier: &str) -> String {
+    let digest = Sha256::digest(code_verifier.as_bytes());
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest)
+}
+
+/// 콜백 쿼리에서 (code, state) 추출
+pub fn parse_callback_query(query: &HashMap<String, String>) -> Result<(String, String), CallbackRejection> {
+    if query.contains_key("access_token") || query.contains_key("refresh_token") {
+        return Err(CallbackRejection::LegacyTokenCallback);
+    }
+    match (query.get("code"), query.get("state")) {
+        (Some(code), Some(state)) if !code.is_empty() && !state.is_empty() => Ok((code.clone(), state.clone())),
+        _ => Err(CallbackRejection::MissingParams),
+    }
+}
+
+/// 콜백 state 검증
+/// 일치 또는 만료 시 대기 중인 로그인을 소비(1회용), 불일치 시에는 유지 (위조 링크로 정상 로그인을 방해하지 못하게)
+pub fn validate_callback(
+    pending: &mut Option<PendingLogin>,
+    state: &str,
+    now_s: u64,
+) -> Result<PendingLogin, CallbackRejection> {
+    let current = pending.as_ref().ok_or(CallbackRejection::NoPendingLogin)?;
+
+    if current.is_expired(now_s) {
+        *pending = None;
+        return Err(CallbackRejection::Expired);
+    }
+    if !constant_time_eq(current.state.as_bytes(), state.as_bytes()) {
+        return Err(CallbackRejection::StateMismatch);
+    }
+
+    pending.take().ok_or(CallbackRejection::NoPendingLogin)
+}
+
+fn random_url_safe(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_challenge_rfc7636_vector() {
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_validate_callback() {
+        let login = PendingLogin::new(1_000);
+        assert_ne!(login.state, login.code_verifier);
+        let mut pending = Some(login.clone());
+
+        // 위조된 state -> 거부, 대기 중인 로그인은 유지
+        assert_eq!(
+            validate_callback(&mut pending, "forged", 1_010).unwrap_err(),
+            CallbackRejection::StateMismatch
+        );
+        assert!(pending.is_some());
+
+        // 정상 state -> 통과 후 소비 (재사용 불가)
+        let accepted = validate_callback(&mut pending, &login.state, 1_020).unwrap();
+        assert_eq!(accepted.code_verifier, login.code_verifier);
+        assert_eq!(
+            validate_callback(&mut pending, &login.state, 1_030).unwrap_err(),
+            CallbackRejection::NoPendingLogin
+        );
+
+        // 만료 -> 거부 후 폐기
+        let mut pending = Some(login.clone());
+        assert_eq!(
+            validate_callback(&mut pending, &login.state, 1_000 + LOGIN_STATE_TTL_SEC + 1).unwrap_err(),
+            CallbackRejection::Expired
+        );
+        assert!(pending.is_none());
+    }
+
+    #[test]
+    fn test_parse_callback_query() {
+        let query = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+
+        assert_eq!(
+            parse_callback_query(&query(&[("code", "c"), ("state", "s")])).unwrap(),
+            ("c".to_string(), "s".to_string())
+        );
+        assert_eq!(
+            parse_callback_query(&query(&[("code", "c")])).unwrap_err(),
+            CallbackRejection::MissingParams
+        );
+        assert_eq!(
+            parse_callback_query(&query(&[("access_token", "a"), ("code", "c"), ("state", "s")])).unwrap_err(),
+            CallbackRejection::LegacyTokenCallback
+        );
+    }
+}
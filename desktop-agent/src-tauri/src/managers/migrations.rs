@@ -0,0 +1,561 @@
+// LSN SQLite 스키마 마이그레이션 모듈
+// 스키마 버전은 PRAGMA user_version에 기록하고, 시작 시 현재 버전 이후의 마이그레이션만 순서대로 적용한다.
+// 각 마이그레이션은 하나의 트랜잭션에서 실행되며 (user_version 갱신 포함), 실패하면 해당 단계 전체가 롤백된다.
+//
+// 새 스키마 변경은 기존 마이그레이션을 수정하지 말고 MIGRATIONS 끝에 새 항목을 추가할 것.
+
+use rusqlite::Connection;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub up: fn(&Connection) -> Result<(), String>,
+}
+
+/// 버전 순서대로 정렬된 전체 마이그레이션 목록
+pub const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, name: "baseline", up: m001_baseline },
+    Migration { version: 2, name: "erasure_requests", up: m002_erasure_requests },
+];
+
+/// 이 앱이 아는 최신 스키마 버전
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+pub fn current_version(conn: &Connection) -> Result<u32, String> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| format!("Failed to read schema version: {}", e))
+}
+
+/// 시작 시 호출: 최신 버전까지 마이그레이션 후 스키마 검증
+pub fn run_migrations(conn: &Connection) -> Result<(), String> {
+    migrate_to(conn, latest_version())?;
+    verify_schema(conn)
+}
+
+/// target 버전까지 마이그레이션 적용
+/// DB 버전이 target보다 높으면 (새 버전 앱이 만든 DB) 데이터를 건드리지 않고 에러
+pub fn migrate_to(conn: &Connection, target: u32) -> Result<(), String> {
+    apply_migrations(conn, MIGRATIONS, target)
+}
+
+fn apply_migrations(conn: &Connection, migrations: &[Migration], target: u32) -> Result<(), String> {
+    let current = current_version(conn)?;
+    let latest = migrations.last().map(|m| m.version).unwrap_or(0);
+    if current > latest {
+        return Err(format!(
+            "Database schema version {} is newer than this app supports ({}). Please update the app.",
+            current, latest
+        ));
+    }
+    if current > target {
+        return Err(format!("Cannot downgrade database schema from version {} to {}", current, target));
+    }
+
+    for migration in migrations.iter().filter(|m| m.version > current && m.version <= target) {
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Migration {} ({}): failed to begin: {}", migration.version, migration.name, e))?;
+        (migration.up)(&tx)
+            .map_err(|e| format!("Migration {} ({}) failed: {}", migration.version, migration.name, e))?;
+        tx.pragma_update(None, "user_version", migration.version)
+            .map_err(|e| format!("Migration {} ({}): failed to set version: {}", migration.version, migration.name, e))?;
+        tx.commit()
+            .map_err(|e| format!("Migration {} ({}): failed to commit: {}", migration.version, migration.name, e))?;
+        println!("StorageManager: Applied migration {} ({}).", migration.version, migration.name);
+    }
+    Ok(())
+}
+
+/// 스키마 드리프트 검사: 마이그레이션으로 만들어져야 할 테이블/컬럼/인덱스가 실제 DB에 모두 있는지 확인
+/// (수동 수정, 손상, 버전 번호만 올라간 DB 등)
+pub fn verify_schema(conn: &Connection) -> Result<(), String> {
+    let expected = {
+        let reference = Connection::open_in_memory().map_err(|e| e.to_string())?;
+        migrate_to(&reference, current_version(conn)?)?;
+        schema_snapshot(&reference)?
+    };
+    let actual = schema_snapshot(conn)?;
+
+    let mut missing = Vec::new();
+    for (object, columns) in &expected {
+        match actual.get(object) {
+            None => missing.push(object.clone()),
+            Some(actual_columns) => {
+                missing.extend(columns.difference(actual_columns).map(|c| format!("{}.{}", object, c)));
+            }
+        }
+    }
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Database schema drift detected at version {}: missing {}",
+            current_version(conn)?,
+            missing.join(", ")
+        ))
+    }
+}
+
+/// 테이블 -> 컬럼 목록, 인덱스 -> 빈 목록
+fn schema_snapshot(conn: &Connection) -> Result<BTreeMap<String, BTreeSet<String>>, String> {
+    let objects: Vec<(String, String)> = {
+        let mut stmt = conn
+            .prepare("SELECT type, name FROM sqlite_master WHERE type IN ('table', 'index') AND name NOT LIKE 'sqlite_%'")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<_, _>>().map_err(|e| e.to_string())?
+    };
+
+    let mut snapshot = BTreeMap::new();
+    for (kind, name) in objects {
+        let columns = if kind == "table" { table_columns(conn, &name)? } else { BTreeSet::new() };
+        snapshot.insert(name, columns);
+    }
+    Ok(snapshot)
+}
+
+pub(crate) fn table_columns(conn: &Connection, table: &str) -> Result<BTreeSet<String>, String> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({})", table))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+/// 컬럼이 없을 때만 추가 (이전 버전 앱이 이미 추가한 컬럼은 그대로)
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<(), String> {
+    if table_columns(conn, table)?.contains(column) {
+        return Ok(());
+    }
+    conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl), [])
+        .map(|_| ())
+        .map_err(|e| format!("Failed to add column {}.{}: {}", table, column, e))
+}
+
+// --- 마이그레이션 ---
+
+/// v1: 버전 관리 도입 시점의 전체 스키마
+/// user_version이 0인 기존 DB(이전 앱 버전들이 CREATE TABLE IF NOT EXISTS로 만든 DB)도 이 단계에서 흡수
+fn m001_baseline(conn: &Connection) -> Result<(), String> {
+    // --- 1. 활성 세션 정보 테이블 ---
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS active_session (
+            session_id TEXT PRIMARY KEY,
+            task_id TEXT NULL,
+            start_time_s INTEGER NOT NULL,
+            intent TEXT NULL,
+            scheduled_end_s INTEGER NULL,
+            paused_total_s INTEGER NOT NULL DEFAULT 0,
+            paused_at_s INTEGER NULL,
+            goal_duration_min INTEGER NULL,
+            goal_reached_s INTEGER NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create active_session table: {}", e))?;
+
+    // 이전 버전 앱이 만든 테이블에는 없을 수 있는 컬럼
+    add_column_if_missing(conn, "active_session", "intent", "TEXT")?;
+    add_column_if_missing(conn, "active_session", "scheduled_end_s", "INTEGER")?;
+    add_column_if_missing(conn, "active_session", "paused_total_s", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "active_session", "paused_at_s", "INTEGER")?;
+    add_column_if_missing(conn, "active_session", "goal_duration_min", "INTEGER")?;
+    add_column_if_missing(conn, "active_session", "goal_reached_s", "INTEGER")?;
+
+    // --- 2. 캐시된 이벤트 데이터 테이블 ---
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cached_events (
+            id INTEGER PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            client_event_id TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            app_name TEXT NOT NULL,       
+            window_title TEXT NOT NULL,   
+            activity_vector TEXT NOT NULL, -- JSON
+            fsm_state TEXT NULL -- 캐싱 시점의 FSM 상태 (세션 요약용)
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create cached_events table: {}", e))?;
+
+    // 캐싱 시점의 FSM 상태 (세션 요약용)
+    add_column_if_missing(conn, "cached_events", "fsm_state", "TEXT")?;
+    // 서버가 이 행을 거부한 횟수 (임계치 도달 시 sync_dead_letter로 이동)
+    add_column_if_missing(conn, "cached_events", "upload_failures", "INTEGER NOT NULL DEFAULT 0")?;
+    // 마지막 업로드 시도 시각 (전송 후 삭제 전에 종료되면 재전송됨 -> 서버가 client_event_id로 중복 제거)
+    add_column_if_missing(conn, "cached_events", "upload_attempted_at", "INTEGER")?;
+
+    // client_event_id 고유 인덱스 (기존 중복 행은 가장 오래된 것만 남김)
+    conn.execute(
+        "DELETE FROM cached_events WHERE id NOT IN (SELECT MIN(id) FROM cached_events GROUP BY client_event_id)",
+        [],
+    )
+    .map_err(|e| format!("Failed to dedupe cached_events: {}", e))?;
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_cached_events_client_event_id ON cached_events (client_event_id)",
+        [],
+    )
+    .map_err(|e| format!("Failed to create cached_events client_event_id index: {}", e))?;
+
+    // 3. 피드백 캐싱 테이블
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cached_feedback (
+            id INTEGER PRIMARY KEY,
+            timestamp INTEGER NOT NULL,
+            event_id TEXT NOT NULL, 
+            feedback_type TEXT NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create cached_feedback table: {}", e))?;
+
+    add_column_if_missing(conn, "cached_feedback", "upload_failures", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // 4. 인증 토큰 테이블
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS auth_token (
+            id INTEGER PRIMARY KEY CHECK (id = 1), 
+            access_token TEXT NOT NULL,
+            refresh_token TEXT NOT NULL,
+            user_id TEXT NOT NULL,
+            user_email TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create auth_token table: {}", e))?;
+
+    // 5. Schedules 테이블
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schedules (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            task_id TEXT,
+            name TEXT NOT NULL,
+            start_time TEXT NOT NULL,
+            end_time TEXT NOT NULL,
+            days_of_week TEXT NOT NULL, 
+            start_date TEXT, -- [New] Optional Date
+            is_active INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create schedules table: {}", e))?;
+
+    // start_date 도입 이전에 만들어진 테이블
+    add_column_if_missing(conn, "schedules", "start_date", "TEXT")?;
+
+    // 6. Tasks 테이블
+    // target_arguments는 이제 단순 TEXT (NULL 허용)입니다.
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tasks (
+            id TEXT PRIMARY KEY,
+            user_id TEXT NOT NULL,
+            task_name TEXT NOT NULL,
+            description TEXT,
+            target_executable TEXT,
+            target_arguments TEXT,
+            status TEXT NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create tasks table: {}", e))?;
+
+    // 오프라인 편집: 서버 버전, 미전송 표시, 로컬 수정 시각
+    add_column_if_missing(conn, "tasks", "updated_at", "TEXT")?;
+    add_column_if_missing(conn, "tasks", "dirty", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "tasks", "local_updated_ms", "INTEGER")?;
+
+    // 7. 사용자 설정 테이블 (단일 행, JSON)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            settings_json TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create app_settings table: {}", e))?;
+
+    // 8. 스케줄 발화 기록 (occurrence 단위 Ledger: 같은 회차 중복 실행 방지)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schedule_runs (
+            schedule_id TEXT NOT NULL,
+            occurrence_start_s INTEGER NOT NULL,
+            outcome TEXT NOT NULL, -- 'started' | 'missed'
+            recorded_at INTEGER NOT NULL,
+            PRIMARY KEY (schedule_id, occurrence_start_s)
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create schedule_runs table: {}", e))?;
+
+    // 9. 스케줄 모니터 상태 (마지막으로 평가한 시각, 단일 행)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schedule_monitor_state (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            last_evaluated_s INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create schedule_monitor_state table: {}", e))?;
+
+    // 10. 세션 일시정지 구간 (resumed_at_s가 NULL이면 진행 중)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_pauses (
+            id INTEGER PRIMARY KEY,
+            session_id TEXT NOT NULL,
+            paused_at_s INTEGER NOT NULL,
+            resumed_at_s INTEGER NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create session_pauses table: {}", e))?;
+
+    // 11. 세션 기록 (종료 후에도 남는 로컬 히스토리)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_history (
+            session_id TEXT PRIMARY KEY,
+            task_id TEXT NULL,
+            intent TEXT NULL,
+            start_time_s INTEGER NOT NULL,
+            end_time_s INTEGER NULL,
+            goal_duration_min INTEGER NOT NULL DEFAULT 0,
+            user_evaluation_score INTEGER NULL,
+            paused_s INTEGER NOT NULL DEFAULT 0,
+            notification_count INTEGER NOT NULL DEFAULT 0,
+            overlay_count INTEGER NOT NULL DEFAULT 0,
+            rest_recommendation_count INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create session_history table: {}", e))?;
+
+    // 12. 동기화되어 삭제된 이벤트의 집계 (앱/FSM 상태별 샘플 수)
+    // 이벤트가 서버로 전송된 뒤에도 오프라인으로 세션 요약을 계산하기 위함
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_event_rollup (
+            session_id TEXT NOT NULL,
+            app_name TEXT NOT NULL,
+            fsm_state TEXT NOT NULL,
+            sample_count INTEGER NOT NULL,
+            PRIMARY KEY (session_id, app_name, fsm_state)
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create session_event_rollup table: {}", e))?;
+
+    // 13. 보안 이벤트 로그 (거부된 로그인 콜백 등)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS security_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            detail TEXT NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create security_events table: {}", e))?;
+
+    // 14. 세션 시작/종료 Outbox (오프라인에서도 기록, sync 루프가 순서대로 전송)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_outbox (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            local_session_id TEXT NOT NULL,
+            op TEXT NOT NULL,
+            payload TEXT NOT NULL, -- JSON
+            created_at INTEGER NOT NULL,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create session_outbox table: {}", e))?;
+
+    // 15. 로컬 세션 ID -> 서버 세션 ID 매핑 (이벤트 업로드 시 치환)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS session_id_map (
+            local_session_id TEXT PRIMARY KEY,
+            server_session_id TEXT NOT NULL,
+            synced_at INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create session_id_map table: {}", e))?;
+
+    // 16. 반복해서 서버에 거부된 업로드 데이터 (동기화 큐에서 격리, 원본 행은 JSON으로 보관)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_dead_letter (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL, -- 'event' | 'feedback' | 'task'
+            payload TEXT NOT NULL, -- JSON
+            last_error TEXT NOT NULL,
+            failed_at INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create sync_dead_letter table: {}", e))?;
+
+    // 17. Down-Sync 커서 (컬렉션별 마지막 ETag, 변경 없으면 서버가 304 응답)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS sync_cursors (
+            collection TEXT PRIMARY KEY, -- 'tasks' | 'schedules'
+            etag TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create sync_cursors table: {}", e))?;
+
+    // 18. Task 동기화 충돌 기록 (Last-Writer-Wins로 해결된 양쪽 버전을 JSON으로 보관)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS task_conflicts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id TEXT NOT NULL,
+            resolution TEXT NOT NULL, -- 'local_won' | 'server_won'
+            local_version TEXT NOT NULL,
+            server_version TEXT NOT NULL,
+            resolved_at INTEGER NOT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create task_conflicts table: {}", e))?;
+
+    Ok(())
+}
+
+/// v2: 서버 데이터 삭제 요청 대기열
+fn m002_erasure_requests(conn: &Connection) -> Result<(), String> {
+    // 19. 전체 삭제(delete_all_my_data) 후 서버로 보낼 삭제 요청 (단일 행)
+    // 전송될 때까지 auth_token 행만 함께 남고, 전송 후 둘 다 삭제됨
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS erasure_requests (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            requested_at INTEGER NOT NULL,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT NULL
+        )",
+        [],
+    )
+    .map_err(|e| format!("Failed to create erasure_requests table: {}", e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fresh_snapshot() -> BTreeMap<String, BTreeSet<String>> {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        schema_snapshot(&conn).unwrap()
+    }
+
+    #[test]
+    fn test_upgrades_from_every_older_version() {
+        let expected = fresh_snapshot();
+        assert_eq!(MIGRATIONS.iter().map(|m| m.version).collect::<Vec<_>>(), (1..=latest_version()).collect::<Vec<_>>());
+
+        for from in 0..latest_version() {
+            let conn = Connection::open_in_memory().unwrap();
+            migrate_to(&conn, from).unwrap();
+            assert_eq!(current_version(&conn).unwrap(), from);
+
+            run_migrations(&conn).unwrap();
+            assert_eq!(current_version(&conn).unwrap(), latest_version(), "from v{}", from);
+            assert_eq!(schema_snapshot(&conn).unwrap(), expected, "from v{}", from);
+
+            // 재실행해도 변화 없음
+            run_migrations(&conn).unwrap();
+            assert_eq!(schema_snapshot(&conn).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_legacy_unversioned_db_is_adopted() {
+        // 버전 관리 이전 앱이 만든 DB (user_version = 0, 컬럼 일부 없음, client_event_id 중복 가능)
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE active_session (session_id TEXT PRIMARY KEY, task_id TEXT NULL, start_time_s INTEGER NOT NULL);
+             CREATE TABLE cached_events (id INTEGER PRIMARY KEY, session_id TEXT NOT NULL, client_event_id TEXT NOT NULL,
+                timestamp INTEGER NOT NULL, app_name TEXT NOT NULL, window_title TEXT NOT NULL, activity_vector TEXT NOT NULL);
+             CREATE TABLE tasks (id TEXT PRIMARY KEY, user_id TEXT NOT NULL, task_name TEXT NOT NULL, description TEXT,
+                target_executable TEXT, target_arguments TEXT, status TEXT NOT NULL);
+             INSERT INTO active_session VALUES ('s-1', NULL, 100);
+             INSERT INTO cached_events (session_id, client_event_id, timestamp, app_name, window_title, activity_vector)
+                VALUES ('s-1', 'evt-1', 1, 'a', 't', '{}'), ('s-1', 'evt-1', 1, 'a', 't', '{}'), ('s-1', 'evt-2', 2, 'a', 't', '{}');
+             INSERT INTO tasks VALUES ('t-1', 'u-1', 'Task', NULL, NULL, NULL, 'pending');",
+        )
+        .unwrap();
+
+        run_migrations(&conn).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+        assert_eq!(schema_snapshot(&conn).unwrap(), fresh_snapshot());
+
+        // 기존 데이터 보존 (중복 이벤트만 정리), 새 컬럼은 기본값
+        let events: i64 = conn.query_row("SELECT COUNT(*) FROM cached_events", [], |r| r.get(0)).unwrap();
+        assert_eq!(events, 2);
+        let (paused_total, dirty): (i64, i64) = conn
+            .query_row(
+                "SELECT (SELECT paused_total_s FROM active_session), (SELECT dirty FROM tasks WHERE id = 't-1')",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((paused_total, dirty), (0, 0));
+    }
+
+    #[test]
+    fn test_newer_schema_version_is_rejected() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn.pragma_update(None, "user_version", latest_version() + 1).unwrap();
+
+        let err = run_migrations(&conn).unwrap_err();
+        assert!(err.contains("newer than this app supports"), "{}", err);
+        assert_eq!(current_version(&conn).unwrap(), latest_version() + 1);
+    }
+
+    #[test]
+    fn test_schema_drift_is_reported() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn.execute_batch("DROP TABLE task_conflicts; DROP INDEX idx_cached_events_client_event_id;").unwrap();
+
+        let err = run_migrations(&conn).unwrap_err();
+        assert!(err.contains("drift"), "{}", err);
+        assert!(err.contains("task_conflicts") && err.contains("idx_cached_events_client_event_id"), "{}", err);
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() {
+        fn create_ok(conn: &Connection) -> Result<(), String> {
+            conn.execute("CREATE TABLE step_one (id INTEGER)", []).map(|_| ()).map_err(|e| e.to_string())
+        }
+        fn create_then_fail(conn: &Connection) -> Result<(), String> {
+            conn.execute("CREATE TABLE step_two (id INTEGER)", []).map_err(|e| e.to_string())?;
+            Err("boom".to_string())
+        }
+        let migrations = [
+            Migration { version: 1, name: "one", up: create_ok },
+            Migration { version: 2, name: "two", up: create_then_fail },
+        ];
+
+        let conn = Connection::open_in_memory().unwrap();
+        let err = apply_migrations(&conn, &migrations, 2).unwrap_err();
+        assert!(err.contains("Migration 2 (two) failed"), "{}", err);
+
+        // 1단계는 커밋, 2단계는 테이블 생성까지 롤백
+        assert_eq!(current_version(&conn).unwrap(), 1);
+        let snapshot = schema_snapshot(&conn).unwrap();
+        assert!(snapshot.contains_key("step_one") && !snapshot.contains_key("step_two"));
+    }
+}
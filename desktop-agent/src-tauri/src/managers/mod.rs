@@ -1,5 +1,14 @@
 pub mod storage;
+pub mod db;
+pub mod migrations;
 pub mod sync;
+pub mod retention;
 pub mod tray;
 pub mod widget;
 pub mod schedule;
+pub mod settings;
+pub mod history;
+pub mod export;
+pub mod erasure;
+pub mod token_vault;
+pub mod login_flow;
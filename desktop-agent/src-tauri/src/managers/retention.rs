@@ -0,0 +1,58 @@
+// 파일 위치: src-tauri/src/managers/retention.rs
+// 로컬 데이터 보관 정책 적용 (업로드 대기 이벤트/피드백, JSONL 로그 파일)
+// 로그아웃 상태에서는 동기화가 멈추므로 이 작업이 로컬 저장 공간의 상한을 보장함
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tokio::time::sleep;
+
+use crate::managers::settings::RetentionSettings;
+use crate::utils::logging;
+use crate::{SettingsArcMutex, StorageManagerArc};
+
+// 정리 주기 (앱 시작 직후 1회 실행 후 반복)
+const RETENTION_INTERVAL_SEC: u64 = 60 * 60;
+
+pub fn start_retention_loop(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        println!("Retention: Started background loop (Interval: {}s)", RETENTION_INTERVAL_SEC);
+        loop {
+            if let Err(e) = enforce_retention(&app_handle) {
+                eprintln!("Retention Error: {}", e);
+            }
+            sleep(Duration::from_secs(RETENTION_INTERVAL_SEC)).await;
+        }
+    });
+}
+
+/// 현재 설정 기준으로 DB와 로그 파일 정리
+fn enforce_retention(app: &AppHandle) -> Result<(), String> {
+    let retention: RetentionSettings = {
+        let settings_state = app.state::<SettingsArcMutex>();
+        let settings = settings_state.lock().map_err(|e| e.to_string())?;
+        settings.retention.clone()
+    };
+
+    let now = SystemTime::now();
+    let now_s = now.duration_since(UNIX_EPOCH).map_err(|e| e.to_string())?.as_secs();
+
+    let report = {
+        let storage = app.state::<StorageManagerArc>();
+        storage.enforce_retention(&retention, now_s)?
+    };
+    if report.events_deleted + report.dead_letters_deleted + report.feedback_deleted > 0 {
+        println!("Retention: pruned local data {:?}", report);
+    }
+
+    let log_dir = logging::get_log_dir(app)?;
+    let deleted = logging::prune_log_files(
+        &log_dir,
+        Duration::from_secs(retention.log_max_age_days as u64 * 24 * 60 * 60),
+        retention.log_max_total_mb as u64 * 1024 * 1024,
+        now,
+    )?;
+    if deleted > 0 {
+        println!("Retention: deleted {} log file(s)", deleted);
+    }
+    Ok(())
+}
@@ -1,40 +1,241 @@
-use chrono::{Datelike, Local};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate, NaiveTime, TimeZone};
+use serde::Serialize;
 use std::process::Command;
+use std::sync::Mutex;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::time::sleep; // 시간 계산용
 
-use crate::{ActiveSessionInfo, SessionStateArcMutex, StorageManagerArcMutex};
+use crate::managers::storage::{LocalSchedule, StorageManager};
+use crate::{ActiveSessionInfo, SessionStateArcMutex, SettingsArcMutex, StorageManagerArc};
 use tauri_plugin_notification::NotificationExt; // 알림 플러그인
 
+// 첫 실행(평가 기록 없음) 시 거슬러 올라가 확인할 구간 (자정을 넘기는 스케줄 포함)
+const FIRST_RUN_LOOKBACK_SEC: u64 = 24 * 60 * 60;
+// 장기간 절전 후 최대 보정 구간 (이보다 오래된 회차는 무시)
+const MAX_CATCH_UP_SEC: u64 = 7 * 24 * 60 * 60;
+
+/// 'session-end-warning' 이벤트 페이로드 (위젯의 연장 버튼 표시용)
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionEndWarningPayload {
+    pub session_id: String,
+    pub scheduled_end_s: u64,
+    pub remaining_sec: u64,
+    pub extend_min: u32,
+}
+
+/// 스케줄 세션 종료 시각에 대한 판정 결과
+#[derive(Debug, PartialEq)]
+enum ScheduledEndAction {
+    Nothing,
+    Warn { remaining_sec: u64 },
+    End,
+}
+
 /// 스케줄 모니터링 루프 시작
 pub fn start_monitor_loop(app_handle: AppHandle) {
     tauri::async_runtime::spawn(async move {
         println!("Schedule Monitor: Started background loop (Interval: 60s)");
 
+        // 종료 경고를 이미 보낸 (session_id, scheduled_end_s). 연장되면 종료 시각이 바뀌므로 다시 경고
+        let mut end_warning_sent: Option<(String, u64)> = None;
+
         loop {
-            // 정각(00초)에 가깝게 실행되도록 보정할 수 있으나,
-            // 일단 단순하게 60초 간격으로 체크
-            sleep(Duration::from_secs(60)).await;
+            // 종료를 먼저 처리해야 연달아 붙은 스케줄(14:00~15:00, 15:00~)의 다음 세션이 시작될 수 있음
+            if let Err(e) = check_scheduled_session_end(&app_handle, &mut end_warning_sent) {
+                eprintln!("Schedule Monitor Error (auto-end): {}", e);
+            }
 
             if let Err(e) = check_and_execute_schedules(&app_handle).await {
                 eprintln!("Schedule Monitor Error: {}", e);
             }
+
+            // 회차는 '마지막 평가 시각 ~ 현재' 구간으로 계산하므로 주기가 밀려도 누락되지 않음
+            // (앱 시작 직후 1회 즉시 평가 -> 늦게 켜진 경우에도 진행 중인 스케줄 시작)
+            sleep(Duration::from_secs(60)).await;
         }
     });
 }
 
+/// 스케줄 세션의 end_time 처리 (경고 알림 -> 자동 종료)
+fn check_scheduled_session_end(
+    app: &AppHandle,
+    end_warning_sent: &mut Option<(String, u64)>,
+) -> Result<(), String> {
+    let session_state = app
+        .try_state::<SessionStateArcMutex>()
+        .ok_or("SessionState not found")?;
+
+    let (session_id, scheduled_end_s) = {
+        let session = session_state.lock().map_err(|e| e.to_string())?;
+        match session.as_ref() {
+            Some(ActiveSessionInfo { session_id, scheduled_end_s: Some(end_s), .. }) => (session_id.clone(), *end_s),
+            _ => return Ok(()), // 세션이 없거나 수동 세션
+        }
+    };
+
+    let schedule_settings = match app.try_state::<SettingsArcMutex>() {
+        Some(settings) => settings.lock().map_err(|e| e.to_string())?.schedule.clone(),
+        None => Default::default(),
+    };
+    if !schedule_settings.auto_end_enabled {
+        return Ok(());
+    }
+
+    let now_s = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let already_warned = end_warning_sent
+        .as_ref()
+        .is_some_and(|(id, end_s)| *id == session_id && *end_s == scheduled_end_s);
+
+    match evaluate_scheduled_end(
+        now_s,
+        scheduled_end_s,
+        schedule_settings.end_warning_lead_min as u64 * 60,
+        already_warned,
+    ) {
+        ScheduledEndAction::Nothing => {}
+        ScheduledEndAction::Warn { remaining_sec } => {
+            println!(
+                "Schedule Monitor: Session {} ends in {}s. Sending warning.",
+                session_id, remaining_sec
+            );
+            *end_warning_sent = Some((session_id.clone(), scheduled_end_s));
+
+            let remaining_min = remaining_sec.div_ceil(60);
+            let _ = app
+                .notification()
+                .builder()
+                .title("집중 스케줄 종료 예정")
+                .body(format!(
+                    "{}분 후 세션이 종료됩니다. 위젯에서 {}분 연장할 수 있습니다.",
+                    remaining_min, schedule_settings.extend_min
+                ))
+                .show();
+
+            // 데스크톱 알림에는 버튼을 붙일 수 없으므로 위젯을 띄워 '연장' 버튼을 노출
+            crate::managers::widget::show_widget_window(app);
+            app.emit(
+                "session-end-warning",
+                SessionEndWarningPayload {
+                    session_id,
+                    scheduled_end_s,
+                    remaining_sec,
+                    extend_min: schedule_settings.extend_min,
+                },
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        ScheduledEndAction::End => {
+            println!("Schedule Monitor: Scheduled end reached. Ending session {}.", session_id);
+            *end_warning_sent = None;
+
+            // 수동 종료와 동일한 경로 (평가 점수는 위젯 종료와 같은 0)
+            crate::commands::session::end_active_session(app, 0)?;
+
+            let _ = app
+                .notification()
+                .builder()
+                .title("집중 스케줄 종료")
+                .body("스케줄 종료 시각이 되어 집중 모드를 종료했습니다.")
+                .show();
+        }
+    }
+
+    Ok(())
+}
+
+/// 현재 시각과 종료 예정 시각으로 수행할 동작 판정 (lead_sec == 0 이면 경고 없음)
+fn evaluate_scheduled_end(now_s: u64, scheduled_end_s: u64, lead_sec: u64, already_warned: bool) -> ScheduledEndAction {
+    if now_s >= scheduled_end_s {
+        return ScheduledEndAction::End;
+    }
+    let remaining_sec = scheduled_end_s - now_s;
+    if lead_sec > 0 && remaining_sec <= lead_sec && !already_warned {
+        return ScheduledEndAction::Warn { remaining_sec };
+    }
+    ScheduledEndAction::Nothing
+}
+
+/// 스케줄의 실행 회차 1개 (Unix timestamp, 초)
+#[derive(Debug, Clone, PartialEq)]
+struct ScheduleOccurrence {
+    start_s: u64,
+    end_s: u64,
+}
+
+/// DB의 "HH:MM:SS" (또는 "HH:MM") 시각 파싱
+fn parse_schedule_time(time_str: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time_str, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time_str, "%H:%M"))
+        .ok()
+}
+
+/// (from, to] 구간에서 시작하는 스케줄 회차 목록 (시작 시각 오름차순)
+/// - days_of_week: 0=Mon ~ 6=Sun, start_date(YYYY-MM-DD) 이전 날짜는 제외
+/// - end_time이 start_time보다 이르거나 같으면 자정을 넘기는 스케줄로 보고 다음 날 종료
+fn schedule_occurrences<Tz: TimeZone>(
+    schedule: &LocalSchedule,
+    from: &DateTime<Tz>,
+    to: &DateTime<Tz>,
+) -> Vec<ScheduleOccurrence> {
+    let (Some(start_time), Some(end_time)) = (
+        parse_schedule_time(&schedule.start_time),
+        parse_schedule_time(&schedule.end_time),
+    ) else {
+        return Vec::new();
+    };
+    let start_date = schedule
+        .start_date
+        .as_deref()
+        .filter(|d| !d.is_empty())
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+
+    let tz = to.timezone();
+    let (from_s, to_s) = (from.timestamp(), to.timestamp());
+    let mut occurrences = Vec::new();
+
+    let mut date = from.date_naive();
+    while date <= to.date_naive() {
+        let weekday = date.weekday().num_days_from_monday() as u8;
+        let date_ok = schedule.days_of_week.contains(&weekday) && start_date.is_none_or(|sd| date >= sd);
+
+        if date_ok {
+            let end_date = if end_time <= start_time { date + ChronoDuration::days(1) } else { date };
+            // DST 전환으로 존재하지 않는 시각은 건너뜀
+            if let (Some(start), Some(end)) = (
+                tz.from_local_datetime(&date.and_time(start_time)).earliest(),
+                tz.from_local_datetime(&end_date.and_time(end_time)).earliest(),
+            ) {
+                let start_s = start.timestamp();
+                if start_s > from_s && start_s <= to_s {
+                    occurrences.push(ScheduleOccurrence {
+                        start_s: start_s.max(0) as u64,
+                        end_s: end.timestamp().max(0) as u64,
+                    });
+                }
+            }
+        }
+        date += ChronoDuration::days(1);
+    }
+
+    occurrences
+}
+
 /// 스케줄 확인 및 실행 로직
+/// 마지막 평가 시각 ~ 현재 사이에 시작된 모든 회차를 계산하므로
+/// 절전/슬립, 루프 지연, 늦은 앱 시작으로 정각을 놓쳐도 회차가 누락되지 않음.
+/// 회차별 발화 기록(schedule_runs)으로 같은 회차의 중복 실행을 막는다.
 async fn check_and_execute_schedules(app: &AppHandle) -> Result<(), String> {
     // 1. LSN 접근
-    let storage_state = app
-        .try_state::<StorageManagerArcMutex>()
+    let storage = app
+        .try_state::<StorageManagerArc>()
         .ok_or("StorageManager state not found")?;
 
     // 2. 현재 로그인한 사용자 ID 확인 (격리)
     let user_id = {
-        let storage = storage_state.lock().map_err(|e| e.to_string())?;
-
         // load_auth_token 반환값: Option<(Access, Refresh, Email, UserID)>
         match storage.load_auth_token()? {
             Some((_, _, _, uid)) => uid, // user_id 추출
@@ -42,66 +243,86 @@ async fn check_and_execute_schedules(app: &AppHandle) -> Result<(), String> {
         }
     };
 
-    // 3. '내 ID'로 등록된 활성 스케줄만 조회
-    let schedules = {
-        let storage = storage_state.lock().map_err(|e| e.to_string())?;
-        storage.get_active_schedules(&user_id)?
-    };
-
-    if schedules.is_empty() {
-        return Ok(());
-    }
-
-    // 4. 현재 시간 확인 (Local Time 기준)
+    // 3. '내 ID'로 등록된 활성 스케줄 + 마지막 평가 시각 조회
     let now = Local::now();
-    let current_weekday = now.weekday().num_days_from_monday() as u8; // 0=Mon ~ 6=Sun
-    let current_time_str = now.format("%H:%M").to_string(); // "14:00" 형식
-
+    let now_s = now.timestamp().max(0) as u64;
+    let schedules = storage.get_active_schedules(&user_id)?;
+    let last_evaluated_s = storage.load_schedule_last_evaluated()?;
+
+    // 4. 평가 구간 (from, now]
+    // 첫 실행이면 하루 전부터 보고 진행 중인 회차를 잡고, 긴 절전 후에도 최대 7일까지만 거슬러 올라감
+    let from_s = last_evaluated_s
+        .unwrap_or(now_s.saturating_sub(FIRST_RUN_LOOKBACK_SEC))
+        .max(now_s.saturating_sub(MAX_CATCH_UP_SEC));
+    let from = Local
+        .timestamp_opt(from_s as i64, 0)
+        .single()
+        .ok_or("Invalid last evaluated timestamp")?;
+
+    let mut due: Vec<(LocalSchedule, ScheduleOccurrence)> = Vec::new();
     for schedule in schedules {
-        // [조건 1] 요일 및 날짜 조건 매칭 로직
-        // 1. target_date가 설정되어 있고, 오늘 날짜가 시작 날짜보다 이전이면 실행 안 함
-        // 2. 이후, days_of_week에 오늘의 요일(0~6)이 포함되어 있어야만 실행됨 
-        let mut is_date_match = schedule.days_of_week.contains(&current_weekday);
-
-        if is_date_match {
-            if let Some(target_date) = &schedule.start_date {
-                if !target_date.is_empty() {
-                    let today_str = now.format("%Y-%m-%d").to_string();
-                    // 문자열 비교로 간단히 '오늘이 시작일 이전인지' 체크 (YYYY-MM-DD 형식이므로 사전순 비교 가능)
-                    if today_str < *target_date {
-                        is_date_match = false;
-                    }
-                }
-            }
+        for occurrence in schedule_occurrences(&schedule, &from, &now) {
+            due.push((schedule.clone(), occurrence));
         }
+    }
+    due.sort_by_key(|(_, occurrence)| occurrence.start_s);
 
-        if !is_date_match {
-            continue;
-        }
+    // 이미 진행 중인 세션 때문에 건너뛴 회차는 기록하지 않고, 다음 평가 구간에 다시 포함되도록
+    // 평가 시각을 그 회차 시작 직전까지만 전진시킨다. (구간이 끝나면 'missed'로 기록됨)
+    let mut evaluated_until_s = now_s;
 
-        // [조건 2] 시작 시간 일치 여부 (HH:MM 비교)
-        // DB에는 "HH:MM:SS"로 저장되어 있으므로 앞 5글자만 비교
-        if !schedule.start_time.starts_with(&current_time_str) {
+    for (schedule, occurrence) in due {
+        if storage.is_schedule_run_recorded(&schedule.id, occurrence.start_s)? {
             continue;
         }
 
-        println!(
-            "Schedule Monitor: Matched schedule '{}' (ID: {})",
-            schedule.name, schedule.id
-        );
+        let outcome = if occurrence.end_s <= now_s {
+            // 구간이 이미 끝난 회차는 실행하지 않고 기록만 남김
+            println!(
+                "Schedule Monitor: Missed schedule '{}' (ID: {}) window already ended.",
+                schedule.name, schedule.id
+            );
+            "missed"
+        } else {
+            if occurrence.start_s + 60 < now_s {
+                println!(
+                    "Schedule Monitor: Catching up missed start of '{}' (ID: {}), {}s late.",
+                    schedule.name,
+                    schedule.id,
+                    now_s - occurrence.start_s
+                );
+            } else {
+                println!(
+                    "Schedule Monitor: Matched schedule '{}' (ID: {})",
+                    schedule.name, schedule.id
+                );
+            }
 
-        // [실행] 스케줄 트리거
-        trigger_schedule(app, &schedule, &storage_state).await?;
+            // [실행] 스케줄 트리거 (진행 중인 회차는 남은 구간만큼 세션 시작)
+            if !trigger_schedule(app, &schedule, occurrence.end_s, &storage).await? {
+                evaluated_until_s = evaluated_until_s.min(occurrence.start_s.saturating_sub(1));
+                continue;
+            }
+            "started"
+        };
+
+        storage.record_schedule_run(&schedule.id, occurrence.start_s, outcome)?;
     }
 
+    // 5. 평가 시각 저장 + 오래된 기록 정리
+    storage.save_schedule_last_evaluated(evaluated_until_s)?;
+    storage.prune_schedule_runs(now_s.saturating_sub(MAX_CATCH_UP_SEC * 2))?;
+
     Ok(())
 }
 
+/// 세션을 시작했으면 true, 이미 세션이 진행 중이어서 건너뛰었으면 false
 async fn trigger_schedule(
     app: &AppHandle,
-    schedule: &crate::managers::storage::LocalSchedule,
-    storage_state: &StorageManagerArcMutex,
-) -> Result<(), String> {
+    schedule: &LocalSchedule,
+    scheduled_end_s: u64,
+    storage: &StorageManager,
+) -> Result<bool, String> {
     // A. 이미 세션이 진행 중인지 확인 (중복 실행 방지)
     let session_state = app
         .try_state::<SessionStateArcMutex>()
@@ -111,13 +332,16 @@ async fn trigger_schedule(
         let session = session_state.lock().map_err(|e| e.to_string())?;
         if session.is_some() {
             println!("Schedule Monitor: Session is already active. Skipping auto-start.");
-            return Ok(());
+            return Ok(false);
+        }
+        if crate::managers::erasure::is_erasure_in_progress() {
+            println!("Schedule Monitor: All data is being deleted. Skipping auto-start.");
+            return Ok(false);
         }
     }
 
     // B. 연결된 Task 정보 조회 (실행 파일 + 인자)
     let (target_executable, target_arguments) = if let Some(task_id) = &schedule.task_id {
-        let storage = storage_state.lock().map_err(|e| e.to_string())?;
         if let Some(task) = storage.get_task_by_id(task_id)? {
             (task.target_executable, task.target_arguments)
         } else {
@@ -155,22 +379,28 @@ async fn trigger_schedule(
     }
 
     // D. 세션 시작 (로컬 상태 업데이트)
+    let start_time_s = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    // 목표 시간 = 스케줄 구간 길이 (분, 위젯 진행률 표시용)
+    let goal_duration_min = (scheduled_end_s.saturating_sub(start_time_s) / 60) as u32;
     let new_session = ActiveSessionInfo {
         session_id: format!("auto-{}", uuid::Uuid::new_v4()),
         task_id: schedule.task_id.clone(),
-        start_time_s: SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs(),
+        start_time_s,
+        intent: None,
+        scheduled_end_s: Some(scheduled_end_s),
+        paused_total_s: 0,
+        paused_at_s: None,
+        goal_duration_min: Some(goal_duration_min).filter(|m| *m > 0),
+        goal_reached_s: None,
     };
 
-    {
-        let mut session = session_state.lock().map_err(|e| e.to_string())?;
-        let storage = storage_state.lock().map_err(|e| e.to_string())?;
-
-        storage.save_active_session(&new_session)?;
-        *session = Some(new_session.clone());
+    if !start_auto_session(&session_state, storage, &new_session, goal_duration_min)? {
+        return Ok(false);
     }
+    crate::managers::sync::flush_session_outbox(app);
 
     // E. 알림 및 UI 업데이트
     // 프론트엔드에 세션 시작 이벤트 전송 (MainView 전환용)
@@ -189,7 +419,32 @@ async fn trigger_schedule(
         ))
         .show();
 
-    Ok(())
+    Ok(true)
+}
+
+/// 세션 상태 lock 안에서 다시 확인한 뒤 자동 세션 저장 + 시작.
+/// 작업 앱을 실행하는 사이 사용자가 세션을 시작했거나 전체 삭제가 시작되었으면 만들지 않고 false
+fn start_auto_session(
+    session_state: &Mutex<Option<ActiveSessionInfo>>,
+    storage: &StorageManager,
+    new_session: &ActiveSessionInfo,
+    goal_duration_min: u32,
+) -> Result<bool, String> {
+    let mut session = session_state.lock().map_err(|e| e.to_string())?;
+    if session.is_some() {
+        println!("Schedule Monitor: Session started while launching the task app. Skipping auto-start.");
+        return Ok(false);
+    }
+    if crate::managers::erasure::is_erasure_in_progress() {
+        println!("Schedule Monitor: All data is being deleted. Skipping auto-start.");
+        return Ok(false);
+    }
+
+    storage.save_active_session(new_session)?;
+    storage.insert_session_history(new_session, goal_duration_min)?;
+    crate::managers::sync::enqueue_session_start(storage, new_session, goal_duration_min)?;
+    *session = Some(new_session.clone());
+    Ok(true)
 }
 
 // [보안 패치] 경로 검증 및 인자 파싱 유틸리티 함수 추가
@@ -250,3 +505,127 @@ fn parse_arguments(args_str: &str) -> Vec<String> {
     
     args
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn schedule(start_time: &str, end_time: &str, days_of_week: Vec<u8>, start_date: Option<&str>) -> LocalSchedule {
+        LocalSchedule {
+            id: "sch-1".to_string(),
+            user_id: "user-1".to_string(),
+            task_id: None,
+            name: "Deep Work".to_string(),
+            start_time: start_time.to_string(),
+            end_time: end_time.to_string(),
+            days_of_week,
+            start_date: start_date.map(|d| d.to_string()),
+            is_active: true,
+        }
+    }
+
+    fn ts(d: u32, h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, d, h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_occurrences_caught_up_after_sleep() {
+        // 2026-03-02 = 월요일. 매일 09:00~10:00
+        let s = schedule("09:00:00", "10:00:00", (0..7).collect(), None);
+
+        // 08:59에 마지막 평가 후 09:01에 깨어남 -> 09:00 회차가 잡혀야 함
+        let occ = schedule_occurrences(&s, &ts(2, 8, 59), &ts(2, 9, 1));
+        assert_eq!(occ, vec![ScheduleOccurrence {
+            start_s: ts(2, 9, 0).timestamp() as u64,
+            end_s: ts(2, 10, 0).timestamp() as u64,
+        }]);
+
+        // 다음 평가 구간에서는 같은 회차가 다시 나오지 않음 (from은 미포함)
+        assert!(schedule_occurrences(&s, &ts(2, 9, 1), &ts(2, 9, 2)).is_empty());
+
+        // 세션 진행 중이라 건너뛴 회차는 평가 시각을 시작 직전에 묶어 두므로 구간이 끝날 때까지 다시 평가됨
+        let skipped_from = Utc.timestamp_opt(ts(2, 9, 0).timestamp() - 1, 0).unwrap();
+        let occ = schedule_occurrences(&s, &skipped_from, &ts(2, 9, 30));
+        assert_eq!(occ.len(), 1);
+        assert!(occ[0].end_s > ts(2, 9, 30).timestamp() as u64);
+
+        // 이틀 동안 절전 -> 회차 2개 (03/03, 03/04 09:00)
+        let occ = schedule_occurrences(&s, &ts(2, 12, 0), &ts(4, 9, 30));
+        assert_eq!(occ.len(), 2);
+        assert_eq!(occ[1].start_s, ts(4, 9, 0).timestamp() as u64);
+    }
+
+    #[test]
+    fn test_occurrences_respect_weekday_start_date_and_midnight() {
+        // 화요일(1)만, 자정을 넘기는 23:00~01:00
+        let s = schedule("23:00:00", "01:00:00", vec![1], None);
+        let occ = schedule_occurrences(&s, &ts(2, 0, 0), &ts(8, 0, 0));
+        assert_eq!(occ, vec![ScheduleOccurrence {
+            start_s: ts(3, 23, 0).timestamp() as u64,
+            end_s: ts(4, 1, 0).timestamp() as u64,
+        }]);
+
+        // start_date 이전 회차 제외
+        let s = schedule("09:00", "10:00", (0..7).collect(), Some("2026-03-04"));
+        let occ = schedule_occurrences(&s, &ts(2, 0, 0), &ts(5, 0, 0));
+        assert_eq!(occ.len(), 1);
+        assert_eq!(occ[0].start_s, ts(4, 9, 0).timestamp() as u64);
+
+        // 잘못된 시각 형식
+        let s = schedule("not-a-time", "10:00", (0..7).collect(), None);
+        assert!(schedule_occurrences(&s, &ts(2, 0, 0), &ts(5, 0, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_auto_start_skipped_when_session_started_while_launching() {
+        let storage = StorageManager::new_in_memory().unwrap();
+        let session_state = Mutex::new(None);
+        let session = |id: &str| ActiveSessionInfo {
+            session_id: id.to_string(),
+            task_id: None,
+            start_time_s: 1000,
+            intent: None,
+            scheduled_end_s: None,
+            paused_total_s: 0,
+            paused_at_s: None,
+            goal_duration_min: None,
+            goal_reached_s: None,
+        };
+
+        // 첫 확인(A) 이후, 작업 앱을 실행하는 사이 사용자가 직접 세션 시작
+        *session_state.lock().unwrap() = Some(session("manual"));
+
+        // 자동 세션은 만들지 않음 -> false (회차는 기록되지 않고 구간이 끝날 때까지 다시 평가됨)
+        let auto = ActiveSessionInfo { scheduled_end_s: Some(4600), ..session("auto-1") };
+        assert!(!start_auto_session(&session_state, &storage, &auto, 60).unwrap());
+        assert_eq!(session_state.lock().unwrap().as_ref().unwrap().session_id, "manual");
+        assert!(storage.load_active_session().unwrap().is_none());
+        assert!(storage.get_session_history("auto-1").unwrap().is_none());
+        assert!(storage.get_pending_session_ops(10).unwrap().is_empty());
+
+        // 진행 중인 세션이 없으면 시작
+        *session_state.lock().unwrap() = None;
+        assert!(start_auto_session(&session_state, &storage, &auto, 60).unwrap());
+        assert_eq!(session_state.lock().unwrap().as_ref().unwrap().session_id, "auto-1");
+        assert_eq!(storage.get_pending_session_ops(10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_evaluate_scheduled_end() {
+        let end_s = 10_000;
+        let lead = 5 * 60;
+
+        assert_eq!(evaluate_scheduled_end(end_s - 600, end_s, lead, false), ScheduledEndAction::Nothing);
+        assert_eq!(
+            evaluate_scheduled_end(end_s - 240, end_s, lead, false),
+            ScheduledEndAction::Warn { remaining_sec: 240 }
+        );
+        // 이미 경고를 보냈으면 중복 알림 없음
+        assert_eq!(evaluate_scheduled_end(end_s - 180, end_s, lead, true), ScheduledEndAction::Nothing);
+        // 경고 비활성화 (lead = 0)
+        assert_eq!(evaluate_scheduled_end(end_s - 60, end_s, 0, false), ScheduledEndAction::Nothing);
+        // 60초 주기 체크가 종료 시각을 지나친 경우에도 종료
+        assert_eq!(evaluate_scheduled_end(end_s + 30, end_s, lead, true), ScheduledEndAction::End);
+    }
+}
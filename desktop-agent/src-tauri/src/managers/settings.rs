@@ -0,0 +1,199 @@
+// 사용자 설정(Settings) 모듈
+// 사람마다 다른 개입 곡선(임계값/배속)을 LSN(app_settings 테이블)에 저장하고,
+// 실행 중인 StateEngine에 재시작 없이 적용한다.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::state::StateConfig;
+use crate::managers::storage::StorageManager;
+
+pub const DEFAULT_END_WARNING_LEAD_MIN: u32 = 5;
+pub const DEFAULT_EXTEND_MIN: u32 = 10;
+
+/// 스케줄로 시작된 세션의 자동 종료 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScheduleSettings {
+    // end_time 도달 시 세션 자동 종료 여부
+    pub auto_end_enabled: bool,
+    // 종료 몇 분 전에 경고 알림을 보낼지 (0 = 경고 없음)
+    pub end_warning_lead_min: u32,
+    // 경고 알림에서 '연장' 선택 시 늘어나는 시간 (분)
+    pub extend_min: u32,
+}
+
+impl Default for ScheduleSettings {
+    fn default() -> Self {
+        Self {
+            auto_end_enabled: true,
+            end_warning_lead_min: DEFAULT_END_WARNING_LEAD_MIN,
+            extend_min: DEFAULT_EXTEND_MIN,
+        }
+    }
+}
+
+impl ScheduleSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.end_warning_lead_min > 120 {
+            return Err("end_warning_lead_min must be at most 120 minutes".to_string());
+        }
+        if self.extend_min == 0 || self.extend_min > 240 {
+            return Err("extend_min must be between 1 and 240 minutes".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 목표 집중 시간 도달 시 동작
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalReachedAction {
+    // 세션을 유지하고 초과 시간(overtime)을 표시
+    Overtime,
+    // 세션 자동 종료
+    AutoEnd,
+}
+
+/// 수동 세션의 목표 시간(goal_duration) 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GoalSettings {
+    pub on_goal_reached: GoalReachedAction,
+    // 목표 달성 시 데스크톱 알림 표시 여부
+    pub notify_enabled: bool,
+}
+
+impl Default for GoalSettings {
+    fn default() -> Self {
+        Self {
+            on_goal_reached: GoalReachedAction::Overtime,
+            notify_enabled: true,
+        }
+    }
+}
+
+/// 로컬 데이터 보관 기간/용량 (백그라운드 정리 작업이 주기적으로 적용)
+/// 로그아웃 상태에서는 이벤트가 업로드되지 않으므로 이 한도가 DB 크기의 상한이 됨
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionSettings {
+    // 업로드 대기 이벤트 / 격리된 업로드 데이터
+    pub event_max_age_days: u32,
+    pub event_max_total_mb: u32,
+    // 업로드 대기 피드백
+    pub feedback_max_age_days: u32,
+    pub feedback_max_total_mb: u32,
+    // JSONL 활동 로그 파일
+    pub log_max_age_days: u32,
+    pub log_max_total_mb: u32,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            event_max_age_days: 30,
+            event_max_total_mb: 200,
+            feedback_max_age_days: 90,
+            feedback_max_total_mb: 10,
+            log_max_age_days: 14,
+            log_max_total_mb: 200,
+        }
+    }
+}
+
+impl RetentionSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        let ages = [self.event_max_age_days, self.feedback_max_age_days, self.log_max_age_days];
+        if ages.iter().any(|d| *d == 0 || *d > 3650) {
+            return Err("retention age must be between 1 and 3650 days".to_string());
+        }
+        let sizes = [self.event_max_total_mb, self.feedback_max_total_mb, self.log_max_total_mb];
+        if sizes.iter().any(|mb| *mb == 0 || *mb > 100_000) {
+            return Err("retention size must be between 1 and 100000 MB".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// LSN에 JSON으로 저장되는 사용자 설정 묶음
+/// (#[serde(default)]: 이후 필드가 추가되어도 기존 저장값을 그대로 읽을 수 있음)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserSettings {
+    // FSM 임계값 및 InferenceResult별 배속
+    pub fsm: StateConfig,
+    // 스케줄 세션 자동 종료/연장
+    pub schedule: ScheduleSettings,
+    // 목표 시간 도달 시 알림/자동 종료
+    pub goal: GoalSettings,
+    // 로컬 데이터 보관 기간/용량
+    pub retention: RetentionSettings,
+}
+
+impl UserSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        self.fsm.validate()?;
+        self.schedule.validate()?;
+        self.retention.validate()
+    }
+}
+
+/// LSN에서 설정을 로드 (저장값이 없거나 손상/무효한 경우 기본값)
+pub fn load_or_default(storage: &StorageManager) -> UserSettings {
+    match storage.load_user_settings() {
+        Ok(Some(settings)) => match settings.validate() {
+            Ok(()) => settings,
+            Err(e) => {
+                eprintln!("Settings: Stored settings are invalid ({}). Using defaults.", e);
+                UserSettings::default()
+            }
+        },
+        Ok(None) => UserSettings::default(),
+        Err(e) => {
+            eprintln!("Settings: Failed to load settings from LSN: {}. Using defaults.", e);
+            UserSettings::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_fall_back_to_defaults() {
+        // 일부 필드만 저장된 JSON (구버전 설정 등)
+        let json = r#"{ "fsm": { "threshold_notify_sec": 20.0 } }"#;
+        let settings: UserSettings = serde_json::from_str(json).expect("should parse partial settings");
+
+        assert_eq!(settings.fsm.threshold_notify_sec, 20.0);
+        assert_eq!(settings.fsm.threshold_block_sec, StateConfig::default().threshold_block_sec);
+        assert_eq!(settings.schedule, ScheduleSettings::default());
+        assert_eq!(settings.goal, GoalSettings::default());
+        assert_eq!(settings.retention, RetentionSettings::default());
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_schedule_settings_validation() {
+        let mut settings = UserSettings::default();
+        settings.schedule.extend_min = 0;
+        assert!(settings.validate().is_err());
+
+        settings.schedule.extend_min = 15;
+        settings.schedule.end_warning_lead_min = 0; // 경고 끄기는 허용
+        assert!(settings.validate().is_ok());
+
+        settings.retention.log_max_total_mb = 0;
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_goal_action_serialization() {
+        let json = r#"{ "goal": { "on_goal_reached": "auto_end" } }"#;
+        let settings: UserSettings = serde_json::from_str(json).expect("should parse goal settings");
+
+        assert_eq!(settings.goal.on_goal_reached, GoalReachedAction::AutoEnd);
+        assert!(settings.goal.notify_enabled);
+    }
+}
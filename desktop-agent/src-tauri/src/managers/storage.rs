@@ -6,15 +6,23 @@ Table에 새로운 데이터 추가는 commands.rs에서 이루어짐
 
 use rusqlite::{Connection, OptionalExtension, Result, params};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::sync::Mutex;
+use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Manager, Runtime}; // cache_event 함수에 필요한 use 문
 
+use crate::core::state::InterventionTrigger;
+use crate::managers::db::{self, DbTarget, DbWriter, ReadPool};
+use crate::managers::migrations;
+use crate::utils::error::{AppError, AppResult};
+use crate::managers::settings::{RetentionSettings, UserSettings};
+use crate::managers::token_vault::{self, TokenVault};
+
 // lib.rs
 
 // 로컬 작업 및 스케줄 구조체 (public)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LocalTask {
     pub id: String,
     pub user_id: String,
@@ -23,9 +31,44 @@ pub struct LocalTask {
     pub target_executable: Option<String>, // Backend: Optional[str]
     pub target_arguments: Option<String>,  // Backend: Optional[str] (단일 문자열)
     pub status: String,
+    pub updated_at: Option<String>,        // Backend: updated_at (서버 버전, 한 번도 동기화되지 않은 로컬 Task는 None)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 로컬에서 생성/수정되어 아직 서버에 올라가지 않은 Task
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirtyTask {
+    pub task: LocalTask,
+    pub local_updated_ms: i64, // 마지막 로컬 수정 시각 (Last-Writer-Wins 비교 기준)
+}
+
+// 테이블별 사용량 (get_storage_usage)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TableUsage {
+    pub table: String,
+    pub rows: u64,
+    pub bytes: u64, // 컬럼 값 크기 합 (인덱스/페이지 여유 공간 제외)
+}
+
+// 전체 삭제 시 테이블별 삭제 행 수
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WipedTable {
+    pub table: String,
+    pub rows: u64,
+}
+
+// 보관 정책 적용 결과
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RetentionReport {
+    pub events_deleted: u64,
+    pub dead_letters_deleted: u64,
+    pub feedback_deleted: u64,
+}
+
+// 충돌 해결 결과 (task_conflicts.resolution)
+pub const TASK_CONFLICT_LOCAL_WON: &str = "local_won";
+pub const TASK_CONFLICT_SERVER_WON: &str = "server_won";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LocalSchedule {
     pub id: String,
     pub user_id: String,
@@ -38,8 +81,52 @@ pub struct LocalSchedule {
     pub is_active: bool,
 }
 
+// 세션 일시정지 구간 (session_pauses 테이블)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionPause {
+    pub paused_at_s: u64,
+    pub resumed_at_s: Option<u64>,
+}
+
+// 로컬 세션 기록 (session_history 테이블)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionHistoryRecord {
+    pub session_id: String,
+    pub task_id: Option<String>,
+    pub intent: Option<String>,
+    pub start_time_s: u64,
+    pub end_time_s: Option<u64>,
+    pub goal_duration_min: u32,
+    pub user_evaluation_score: Option<u8>,
+    pub paused_s: u64,
+    pub notification_count: u32,
+    pub overlay_count: u32,
+    pub rest_recommendation_count: u32,
+}
+
+// 세션의 앱/FSM 상태별 이벤트 샘플 수 (캐시 + 집계 합산)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionStateSample {
+    pub app_name: String,
+    pub fsm_state: String,
+    pub sample_count: u64,
+}
+
+// 서버로 보낼 세션 생명주기 작업 (session_outbox 테이블)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionOutboxItem {
+    pub id: i64,
+    pub local_session_id: String,
+    pub op: String,      // SESSION_OP_START | SESSION_OP_END
+    pub payload: String, // 요청 본문 JSON
+    pub attempts: u32,
+}
+
+pub const SESSION_OP_START: &str = "start";
+pub const SESSION_OP_END: &str = "end";
+
 // 동기화할 이벤트 데이터 구조체 (public)
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CachedEvent {
     pub id: i64,
     pub session_id: String,
@@ -60,180 +147,141 @@ pub struct CachedFeedback {
 }
 
 // Local Storage Manager 구조체
+// 쓰기는 writer 스레드, 조회는 읽기 연결 풀이 담당하므로 외부 Mutex 없이 여러 스레드에서 공유 (Arc<StorageManager>)
 pub struct StorageManager {
-    conn: Mutex<Connection>,
+    writer: DbWriter,
+    readers: ReadPool,
+    // 인증 토큰 암호화 (키는 DB 밖에 보관)
+    vault: TokenVault,
 }
 
 // --- 1. 생성자 및 초기화 로직 ---
 impl StorageManager {
     /// DB 연결 경로를 설정, 초기화
-    pub fn new_from_path<R: Runtime>(app_handle: AppHandle<R>) -> Result<Self, String> {
+    pub fn new_from_path<R: Runtime>(app_handle: AppHandle<R>) -> AppResult<Self> {
         // OS 표준 데이터 경로
         let app_dir = app_handle.path().app_data_dir().map_err(|e| {
-            format!(
+            AppError::Platform(format!(
                 "Failed to get application data directory from Tauri API: {}",
                 e
-            )
+            ))
         })?;
 
+        Self::open_in_dir(&app_dir)
+    }
+
+    /// 앱 데이터 디렉토리의 local.db를 직접 열기 (AppHandle 없이 실행되는 내보내기 CLI용)
+    pub fn open_in_dir(app_dir: &Path) -> AppResult<Self> {
         let db_path = app_dir.join("local.db");
 
         if let Some(parent) = db_path.parent() {
             if !parent.exists() {
                 fs::create_dir_all(parent)
-                    .map_err(|e| format!("Failed to create app directory: {}", e))?;
+                    .map_err(|e| AppError::from(e).context("Failed to create app directory"))?;
             }
         }
 
-        // 1.  Connection을 열고 초기화
-        let conn = Connection::open(&db_path)
-            .map_err(|e| format!("Failed to open DB connection: {}", e))?;
-        // 에러 타입을 String으로 변환
-        Self::initialize_db_with_conn(&conn).map_err(|e| format!("DB Table init failed: {}", e))?;
+        // 1. 토큰 암호화 키 로드 (DB와 분리된 키 파일 / 키링)
+        let vault = TokenVault::load_or_create(app_dir).map_err(AppError::Storage)?;
+        println!("StorageManager: Token vault ready (key source: {:?}).", vault.source());
+
+        // 2. WAL 연결 열기 + 초기화
+        Self::open_target(&DbTarget::File(db_path), vault)
+    }
+
+    /// 읽기 전용으로 열기 (내보내기 CLI용)
+    /// 마이그레이션, 토큰 키 파일 생성, writer 스레드 없이 SQLITE_OPEN_READ_ONLY 연결만 사용하므로 실행 중인 앱과 함께 써도 안전.
+    /// 스키마 버전이 이 앱과 다르면 에러 (앱을 실행해 마이그레이션하거나 앱을 업데이트해야 함)
+    pub fn open_read_only(app_dir: &Path) -> AppResult<Self> {
+        let db_path = app_dir.join("local.db");
+        if !db_path.is_file() {
+            return Err(AppError::NotFound(format!("No local database found in {:?}", app_dir)));
+        }
+
+        let readers = ReadPool::open_read_only(&DbTarget::File(db_path), 1)
+            .map_err(|e| e.context("Failed to open read-only connection"))?;
+        let version = migrations::current_version(&*readers.get()?).map_err(AppError::Storage)?;
+        if version != migrations::latest_version() {
+            return Err(AppError::Storage(format!(
+                "Database schema version {} does not match this app ({}). Run the app once (or update it) before exporting.",
+                version,
+                migrations::latest_version()
+            )));
+        }
 
-        // 2. Mutex로 감싼 Connection을 저장
         Ok(StorageManager {
-            conn: Mutex::new(conn),
+            writer: DbWriter::disabled(),
+            readers,
+            vault: TokenVault::ephemeral(),
         })
     }
 
     /// (테스트용) 인메모리 DB로 LSN을 생성
     #[cfg(test)]
-    fn new_in_memory() -> Result<Self, String> {
-        // 1. 인메모리 Connection 열기
-        let conn = Connection::open_in_memory()
-            .map_err(|e| format!("Failed to open in-memory DB: {}", e))?;
+    pub(crate) fn new_in_memory() -> AppResult<Self> {
+        Self::open_target(&DbTarget::unique_memory(), TokenVault::ephemeral())
+    }
 
-        // 2. 초기화
-        Self::initialize_db_with_conn(&conn)
-            .map_err(|e| format!("DB (in-memory) init failed: {}", e))?;
+    /// 쓰기 연결에서 마이그레이션 -> 읽기 연결 생성 -> writer 스레드 시작
+    fn open_target(target: &DbTarget, vault: TokenVault) -> AppResult<Self> {
+        let conn = db::open_writer(target).map_err(|e| e.context("Failed to open DB connection"))?;
+        Self::initialize_db_with_conn(&conn).map_err(|e| e.context("DB Table init failed"))?;
+        let readers = ReadPool::open(target, db::READ_CONNECTIONS)
+            .map_err(|e| e.context("Failed to open read connections"))?;
 
-        // 3. Mutex로 감싼 Connection을 저장
         Ok(StorageManager {
-            conn: Mutex::new(conn),
+            writer: DbWriter::spawn(conn)?,
+            readers,
+            vault,
         })
     }
 
-    fn initialize_db_with_conn(conn: &Connection) -> Result<(), String> {
-        // --- 1. 활성 세션 정보 테이블 ---
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS active_session (
-                session_id TEXT PRIMARY KEY,
-                task_id TEXT NULL,
-                start_time_s INTEGER NOT NULL
-            )",
-            [],
-        )
-        .map_err(|e| format!("Failed to create active_session table: {}", e))?;
-
-        // --- 2. 캐시된 이벤트 데이터 테이블 ---
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS cached_events (
-                id INTEGER PRIMARY KEY,
-                session_id TEXT NOT NULL,
-                client_event_id TEXT NOT NULL,
-                timestamp INTEGER NOT NULL,
-                app_name TEXT NOT NULL,       
-                window_title TEXT NOT NULL,   
-                activity_vector TEXT NOT NULL -- JSON
-            )",
-            [],
-        )
-        .map_err(|e| format!("Failed to create cached_events table: {}", e))?;
-
-        // 3. 피드백 캐싱 테이블
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS cached_feedback (
-                id INTEGER PRIMARY KEY,
-                timestamp INTEGER NOT NULL,
-                event_id TEXT NOT NULL, 
-                feedback_type TEXT NOT NULL
-            )",
-            [],
-        )
-        .map_err(|e| format!("Failed to create cached_feedback table: {}", e))?;
-
-        // 4. 인증 토큰 테이블
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS auth_token (
-                id INTEGER PRIMARY KEY CHECK (id = 1), 
-                access_token TEXT NOT NULL,
-                refresh_token TEXT NOT NULL,
-                user_id TEXT NOT NULL,
-                user_email TEXT NOT NULL,
-                updated_at INTEGER NOT NULL
-            )",
-            [],
-        )
-        .map_err(|e| format!("Failed to create auth_token table: {}", e))?;
-
-        // 5. Schedules 테이블
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS schedules (
-                id TEXT PRIMARY KEY,
-                user_id TEXT NOT NULL,
-                task_id TEXT,
-                name TEXT NOT NULL,
-                start_time TEXT NOT NULL,
-                end_time TEXT NOT NULL,
-                days_of_week TEXT NOT NULL, 
-                start_date TEXT, -- [New] Optional Date
-                is_active INTEGER NOT NULL
-            )",
-            [],
-        )
-        .map_err(|e| format!("Failed to create schedules table: {}", e))?;
-
-        // [Migration] 기존 테이블에 start_date 컬럼이 없을 수 있으므로 추가 시도 (실패 시 무시)
-        let _ = conn.execute("ALTER TABLE schedules ADD COLUMN start_date TEXT", []);
-
-        // 6. Tasks 테이블
-        // target_arguments는 이제 단순 TEXT (NULL 허용)입니다.
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS tasks (
-                id TEXT PRIMARY KEY,
-                user_id TEXT NOT NULL,
-                task_name TEXT NOT NULL,
-                description TEXT,
-                target_executable TEXT,
-                target_arguments TEXT,
-                status TEXT NOT NULL
-            )",
-            [],
-        )
-        .map_err(|e| format!("Failed to create tasks table: {}", e))?;
+    /// 큐에 쌓인 쓰기(cache_event 등)가 모두 반영될 때까지 대기 (직후 조회가 최신 데이터를 봐야 할 때)
+    pub fn flush(&self) -> AppResult<()> {
+        self.writer.flush()
+    }
 
-        Ok(())
+    fn initialize_db_with_conn(conn: &Connection) -> AppResult<()> {
+        // 버전별 마이그레이션 적용 (PRAGMA user_version) + 스키마 검증
+        migrations::run_migrations(conn).map_err(AppError::Storage)
     }
 }
 
 // --- LSN 핵심 CRUD 함수  ---
 impl StorageManager {
     /// 활성 세션 정보를 로컬 DB에 저장 (세션 시작 시 호출)
-    pub fn save_active_session(&self, info: &crate::ActiveSessionInfo) -> Result<(), String> {
-        // self.conn.lock()을 사용하여 공유된 연결에 접근
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+    pub fn save_active_session(&self, info: &crate::ActiveSessionInfo) -> AppResult<()> {
+        let info = info.clone();
+        self.writer.execute(move |conn| {
+            conn.execute("DELETE FROM active_session", [])?;
 
-        conn.execute("DELETE FROM active_session", [])
-            .map_err(|e| e.to_string())?;
-
-        conn.execute(
-            "INSERT INTO active_session (session_id, task_id, start_time_s) VALUES (?1, ?2, ?3)",
-            rusqlite::params![info.session_id, info.task_id, info.start_time_s],
-        )
-        .map_err(|e| e.to_string())?;
+            conn.execute(
+                "INSERT INTO active_session (session_id, task_id, start_time_s, intent, scheduled_end_s, paused_total_s, paused_at_s, goal_duration_min, goal_reached_s)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                rusqlite::params![
+                    info.session_id,
+                    info.task_id,
+                    info.start_time_s,
+                    info.intent,
+                    info.scheduled_end_s,
+                    info.paused_total_s,
+                    info.paused_at_s,
+                    info.goal_duration_min,
+                    info.goal_reached_s
+                ],
+            )?;
 
-        Ok(())
+            Ok(())
+        })
     }
 
     /// 활성 세션 정보를 로컬 DB에서 읽기. (앱 시작 시 호출)
-    pub fn load_active_session(&self) -> Result<Option<crate::ActiveSessionInfo>, String> {
-        //  self.conn.lock()을 사용.
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+    pub fn load_active_session(&self) -> AppResult<Option<crate::ActiveSessionInfo>> {
+        let conn = self.readers.get()?;
 
         let mut stmt = conn
-            .prepare("SELECT session_id, task_id, start_time_s FROM active_session LIMIT 1")
-            .map_err(|e| format!("SQL prepare error: {}", e))?;
+            .prepare("SELECT session_id, task_id, start_time_s, intent, scheduled_end_s, paused_total_s, paused_at_s, goal_duration_min, goal_reached_s FROM active_session LIMIT 1")
+            .map_err(|e| AppError::from(e).context("SQL prepare error"))?;
 
         let row_result = stmt
             .query_row([], |row| {
@@ -241,6 +289,12 @@ impl StorageManager {
                     session_id: row.get(0)?,
                     task_id: row.get(1)?,
                     start_time_s: row.get(2)?,
+                    intent: row.get(3)?,
+                    scheduled_end_s: row.get(4)?,
+                    paused_total_s: row.get(5)?,
+                    paused_at_s: row.get(6)?,
+                    goal_duration_min: row.get(7)?,
+                    goal_reached_s: row.get(8)?,
                 })
             })
             .optional(); // 쿼리 결과가 없을 경우 None을 반환하도록 설정
@@ -248,20 +302,176 @@ impl StorageManager {
         match row_result {
             Ok(Some(info)) => Ok(Some(info)),
             Ok(None) => Ok(None),
-            Err(e) => Err(format!("Failed to load active session: {}", e)),
+            Err(e) => Err(AppError::from(e).context("Failed to load active session")),
         }
     }
 
     /// 활성 세션 정보를 로컬 DB에서 삭제 (세션 종료 시 호출)
-    pub fn delete_active_session(&self) -> Result<(), String> {
-        // self.conn.lock()을 사용
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
-        conn.execute("DELETE FROM active_session", [])
-            .map_err(|e| e.to_string())?;
-        Ok(())
+    pub fn delete_active_session(&self) -> AppResult<()> {
+        self.writer.execute(move |conn| {
+            conn.execute("DELETE FROM active_session", [])?;
+            Ok(())
+        })
+    }
+
+    /// 일시정지 구간 시작 기록
+    pub fn start_session_pause(&self, session_id: &str, paused_at_s: u64) -> AppResult<()> {
+        let session_id = session_id.to_string();
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "INSERT INTO session_pauses (session_id, paused_at_s) VALUES (?1, ?2)",
+                params![session_id, paused_at_s],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// 진행 중인 일시정지 구간 종료 기록 (재개 또는 일시정지 상태에서 세션 종료 시)
+    pub fn finish_session_pause(&self, session_id: &str, resumed_at_s: u64) -> AppResult<()> {
+        let session_id = session_id.to_string();
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "UPDATE session_pauses SET resumed_at_s = ?2 WHERE session_id = ?1 AND resumed_at_s IS NULL",
+                params![session_id, resumed_at_s],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn get_session_pauses(&self, session_id: &str) -> AppResult<Vec<SessionPause>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare("SELECT paused_at_s, resumed_at_s FROM session_pauses WHERE session_id = ?1 ORDER BY paused_at_s ASC")?;
+
+        let rows = stmt
+            .query_map(params![session_id], |row| {
+                Ok(SessionPause {
+                    paused_at_s: row.get(0)?,
+                    resumed_at_s: row.get(1)?,
+                })
+            })?;
+
+        let mut pauses = Vec::new();
+        for row in rows {
+            pauses.push(row?);
+        }
+        Ok(pauses)
+    }
+
+    // --- 세션 기록 (session_history) ---
+
+    /// 세션 시작 시 기록 행 생성
+    pub fn insert_session_history(&self, info: &crate::ActiveSessionInfo, goal_duration_min: u32) -> AppResult<()> {
+        let info = info.clone();
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "INSERT OR IGNORE INTO session_history (session_id, task_id, intent, start_time_s, goal_duration_min)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![info.session_id, info.task_id, info.intent, info.start_time_s, goal_duration_min],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// 개입 발생 횟수 누적 (DoNothing은 무시)
+    pub fn record_session_intervention(&self, session_id: &str, trigger: &InterventionTrigger) -> AppResult<()> {
+        let column = match trigger {
+            InterventionTrigger::TriggerNotification => "notification_count",
+            InterventionTrigger::TriggerOverlay => "overlay_count",
+            InterventionTrigger::TriggerRestRecommendation => "rest_recommendation_count",
+            InterventionTrigger::DoNothing => return Ok(()),
+        };
+        // Core Loop에서 호출되므로 cache_event와 같이 응답을 기다리지 않음
+        let session_id = session_id.to_string();
+        self.writer.enqueue(move |tx| {
+            tx.execute(
+                &format!("UPDATE session_history SET {0} = {0} + 1 WHERE session_id = ?1", column),
+                params![session_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// 세션 종료 시 기록 확정
+    pub fn finish_session_history(
+        &self,
+        session_id: &str,
+        end_time_s: u64,
+        user_evaluation_score: u8,
+        paused_s: u64,
+    ) -> AppResult<()> {
+        let session_id = session_id.to_string();
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "UPDATE session_history SET end_time_s = ?2, user_evaluation_score = ?3, paused_s = ?4
+                 WHERE session_id = ?1",
+                params![session_id, end_time_s, user_evaluation_score, paused_s],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn get_session_history(&self, session_id: &str) -> AppResult<Option<SessionHistoryRecord>> {
+        let conn = self.readers.get()?;
+        conn.query_row(
+            "SELECT session_id, task_id, intent, start_time_s, end_time_s, goal_duration_min, user_evaluation_score,
+                    paused_s, notification_count, overlay_count, rest_recommendation_count
+             FROM session_history WHERE session_id = ?1",
+            params![session_id],
+            |row| {
+                Ok(SessionHistoryRecord {
+                    session_id: row.get(0)?,
+                    task_id: row.get(1)?,
+                    intent: row.get(2)?,
+                    start_time_s: row.get(3)?,
+                    end_time_s: row.get(4)?,
+                    goal_duration_min: row.get(5)?,
+                    user_evaluation_score: row.get(6)?,
+                    paused_s: row.get(7)?,
+                    notification_count: row.get(8)?,
+                    overlay_count: row.get(9)?,
+                    rest_recommendation_count: row.get(10)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(AppError::from)
+    }
+
+    /// 앱/FSM 상태별 샘플 수 (아직 캐시된 이벤트 + 동기화 후 집계된 이벤트)
+    pub fn get_session_state_samples(&self, session_id: &str) -> AppResult<Vec<SessionStateSample>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT app_name, fsm_state, SUM(cnt) FROM (
+                    SELECT app_name, fsm_state, COUNT(*) AS cnt FROM cached_events
+                    WHERE session_id = ?1 AND fsm_state IS NOT NULL
+                    GROUP BY app_name, fsm_state
+                    UNION ALL
+                    SELECT app_name, fsm_state, sample_count AS cnt FROM session_event_rollup
+                    WHERE session_id = ?1
+                 )
+                 GROUP BY app_name, fsm_state",
+            )?;
+
+        let rows = stmt
+            .query_map(params![session_id], |row| {
+                Ok(SessionStateSample {
+                    app_name: row.get(0)?,
+                    fsm_state: row.get(1)?,
+                    sample_count: row.get(2)?,
+                })
+            })?;
+
+        let mut samples = Vec::new();
+        for row in rows {
+            samples.push(row?);
+        }
+        Ok(samples)
     }
 
     // 이벤트를 로컬 DB에 캐싱
+    // Core Loop 틱을 막지 않도록 writer 큐에만 넣고 반환 (모인 이벤트는 한 트랜잭션으로 저장, 즉시 조회가 필요하면 flush)
     pub fn cache_event(
         &self,
         session_id: &str,
@@ -269,57 +479,67 @@ impl StorageManager {
         app_name: &str,
         window_title: &str,
         activity_vector_json: &str, // JSON 문자열을 직접 받음
-    ) -> Result<(), String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+        fsm_state: &str,
+    ) -> AppResult<()> {
         let now_s = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| e.to_string())?
+            .duration_since(UNIX_EPOCH)?
             .as_secs();
-
-        // 스키마에 맞게 INSERT
-        conn.execute(
-            "INSERT INTO cached_events (session_id, client_event_id, timestamp, app_name, window_title, activity_vector) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            rusqlite::params![
-                session_id,
-                client_event_id,
-                now_s,
-                app_name,
-                window_title,
-                activity_vector_json // JSON 문자열 저장
-            ],
-        ).map_err(|e| e.to_string())?;
-
-        Ok(())
+        let (session_id, client_event_id, app_name, window_title, activity_vector_json, fsm_state) = (
+            session_id.to_string(),
+            client_event_id.to_string(),
+            app_name.to_string(),
+            window_title.to_string(),
+            activity_vector_json.to_string(),
+            fsm_state.to_string(),
+        );
+
+        // 스키마에 맞게 INSERT (같은 client_event_id는 한 번만 저장)
+        self.writer.enqueue(move |tx| {
+            tx.execute(
+                "INSERT OR IGNORE INTO cached_events (session_id, client_event_id, timestamp, app_name, window_title, activity_vector, fsm_state) 
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    session_id,
+                    client_event_id,
+                    now_s,
+                    app_name,
+                    window_title,
+                    activity_vector_json, // JSON 문자열 저장
+                    fsm_state
+                ],
+            )?;
+            Ok(())
+        })
     }
 
     // 피드백 저장
-    pub fn cache_feedback(&self, event_id: &str, feedback_type: &str) -> Result<(), String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
-        let now_s = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| e.to_string())?
-            .as_secs() as i64;
-
-        conn.execute(
-            "INSERT INTO cached_feedback (timestamp, event_id, feedback_type) VALUES (?1, ?2, ?3)",
-            params![now_s, event_id, feedback_type],
-        )
-        .map_err(|e| e.to_string())?;
-
-        Ok(())
+    pub fn cache_feedback(&self, event_id: &str, feedback_type: &str) -> AppResult<()> {
+        let event_id = event_id.to_string();
+        let feedback_type = feedback_type.to_string();
+        self.writer.execute(move |conn| {
+            let now_s = SystemTime::now()
+                .duration_since(UNIX_EPOCH)?
+                .as_secs() as i64;
+
+            conn.execute(
+                "INSERT INTO cached_feedback (timestamp, event_id, feedback_type) VALUES (?1, ?2, ?3)",
+                params![now_s, event_id, feedback_type],
+            )?;
+
+            Ok(())
+        })
     }
 
     // 미전송 피드백 조회 (FIFO)
-    pub fn get_unsynced_feedbacks(&self, limit: u32) -> Result<Vec<CachedFeedback>, String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+    pub fn get_unsynced_feedbacks(&self, limit: u32) -> AppResult<Vec<CachedFeedback>> {
+        let conn = self.readers.get()?;
 
         let mut stmt = conn.prepare(
             "SELECT id, timestamp, event_id, feedback_type 
              FROM cached_feedback 
              ORDER BY timestamp ASC 
              LIMIT ?1"
-        ).map_err(|e| e.to_string())?;
+        )?;
         
         let rows = stmt.query_map([limit], |row| {
             Ok(CachedFeedback {
@@ -328,42 +548,147 @@ impl StorageManager {
                 event_id: row.get(2)?,
                 feedback_type: row.get(3)?,
             })
-        }).map_err(|e| e.to_string())?;
+        })?;
 
         let mut feedbacks = Vec::new();
         for row in rows {
-            feedbacks.push(row.map_err(|e| e.to_string())?);
+            feedbacks.push(row?);
         }
         Ok(feedbacks)
     }
 
     // 전송 완료된 피드백 삭제
-    pub fn delete_feedbacks_by_ids(&self, ids: &[i64]) -> Result<(), String> {
-        let mut conn = self.conn.lock().map_err(|e| e.to_string())?;
-        let tx = conn.transaction().map_err(|e| e.to_string())?;
+    pub fn delete_feedbacks_by_ids(&self, ids: &[i64]) -> AppResult<()> {
+        let ids = ids.to_vec();
+        self.writer.execute(move |conn| {
+            let tx = conn.transaction()?;
 
-        for id in ids {
-            tx.execute("DELETE FROM cached_feedback WHERE id = ?1", params![id])
-                .map_err(|e| e.to_string())?;
-        }
+            for id in ids {
+                tx.execute("DELETE FROM cached_feedback WHERE id = ?1", params![id])?;
+            }
+
+            tx.commit()?;
+            Ok(())
+        })
+    }
+
+    /// 업로드 직전 시도 시각 기록 (전송 성공 후 삭제 전 비정상 종료 시 재전송 대상임을 표시)
+    pub fn mark_events_upload_attempted(&self, ids: &[i64]) -> AppResult<()> {
+        let ids = ids.to_vec();
+        self.writer.execute(move |conn| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let tx = conn.transaction()?;
+
+            for id in ids {
+                tx.execute(
+                    "UPDATE cached_events SET upload_attempted_at = ?2 WHERE id = ?1",
+                    params![id, now],
+                )?;
+            }
+
+            tx.commit()?;
+            Ok(())
+        })
+    }
+
+    /// 서버가 거부한 이벤트 1건 기록. 거부 횟수가 max_failures에 도달하면 dead-letter로 이동 후 true
+    pub fn record_event_rejection(&self, id: i64, error: &str, max_failures: u32) -> AppResult<bool> {
+        self.record_upload_rejection(
+            "cached_events",
+            "json_object('session_id', session_id, 'client_event_id', client_event_id, 'timestamp', timestamp,
+                         'app_name', app_name, 'window_title', window_title, 'activity_vector', activity_vector)",
+            "event",
+            id,
+            error,
+            max_failures,
+        )
+    }
+
+    /// 서버가 거부한 피드백 1건 기록 (record_event_rejection과 동일 정책)
+    pub fn record_feedback_rejection(&self, id: i64, error: &str, max_failures: u32) -> AppResult<bool> {
+        self.record_upload_rejection(
+            "cached_feedback",
+            "json_object('event_id', event_id, 'feedback_type', feedback_type, 'timestamp', timestamp)",
+            "feedback",
+            id,
+            error,
+            max_failures,
+        )
+    }
+
+    // table / payload_expr는 위 두 함수의 고정 문자열만 사용 (사용자 입력 아님)
+    fn record_upload_rejection(
+        &self,
+        table: &'static str,
+        payload_expr: &'static str,
+        kind: &'static str,
+        id: i64,
+        error: &str,
+        max_failures: u32,
+    ) -> AppResult<bool> {
+        let error = error.to_string();
+        self.writer.execute(move |conn| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let tx = conn.transaction()?;
 
-        tx.commit().map_err(|e| e.to_string())?;
-        Ok(())
+            tx.execute(
+                &format!("UPDATE {} SET upload_failures = upload_failures + 1 WHERE id = ?1", table),
+                params![id],
+            )?;
+
+            let moved = tx
+                .execute(
+                    &format!(
+                        "INSERT INTO sync_dead_letter (kind, payload, last_error, failed_at)
+                         SELECT ?2, {}, ?3, ?4 FROM {} WHERE id = ?1 AND upload_failures >= ?5",
+                        payload_expr, table
+                    ),
+                    params![id, kind, error, now, max_failures],
+                )?
+                > 0;
+            if moved {
+                tx.execute(&format!("DELETE FROM {} WHERE id = ?1", table), params![id])?;
+            }
+
+            tx.commit()?;
+            Ok(moved)
+        })
+    }
+
+    /// 업로드 대기 중인 이벤트 수 (sync-status 표시용)
+    pub fn count_unsynced_events(&self) -> AppResult<u64> {
+        let conn = self.readers.get()?;
+        conn.query_row("SELECT COUNT(*) FROM cached_events", [], |row| row.get(0))
+            .map_err(AppError::from)
+    }
+
+    pub fn count_dead_letters(&self) -> AppResult<u64> {
+        let conn = self.readers.get()?;
+        conn.query_row("SELECT COUNT(*) FROM sync_dead_letter", [], |row| row.get(0))
+            .map_err(AppError::from)
     }
 
     // 동기화를 위해 전송되지 않은 이벤트 조회
     // limit: 한 번에 가져올 개수 (예: 50개)
-    pub fn get_unsynced_events(&self, limit: u32) -> Result<Vec<CachedEvent>, String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+    pub fn get_unsynced_events(&self, limit: u32) -> AppResult<Vec<CachedEvent>> {
+        let conn = self.readers.get()?;
 
         let mut stmt = conn
             .prepare(
-                "SELECT id, session_id, client_event_id, timestamp, app_name, window_title, activity_vector 
-             FROM cached_events 
-             ORDER BY timestamp ASC 
+                "SELECT e.id, COALESCE(m.server_session_id, e.session_id), e.client_event_id, e.timestamp,
+                    e.app_name, e.window_title, e.activity_vector
+             FROM cached_events e
+             LEFT JOIN session_id_map m ON m.local_session_id = e.session_id
+             WHERE e.session_id NOT IN (SELECT local_session_id FROM session_outbox WHERE op = 'start')
+             ORDER BY e.timestamp ASC
              LIMIT ?1",
-            )
-            .map_err(|e| e.to_string())?;
+            )?;
 
         let rows = stmt
             .query_map([limit], |row| {
@@ -376,12 +701,11 @@ impl StorageManager {
                     window_title: row.get(5)?,
                     activity_vector: row.get(6)?,
                 })
-            })
-            .map_err(|e| e.to_string())?;
+            })?;
 
         let mut events = Vec::new();
         for row in rows {
-            events.push(row.map_err(|e| e.to_string())?);
+            events.push(row?);
         }
 
         Ok(events)
@@ -389,20 +713,304 @@ impl StorageManager {
 
     // 전송 완료된 이벤트 삭제 (Batch Delete)
     // ids: 삭제할 이벤트의 ID 목록
-    pub fn delete_events_by_ids(&self, ids: &[i64]) -> Result<(), String> {
-        let mut conn = self.conn.lock().map_err(|e| e.to_string())?;
+    pub fn delete_events_by_ids(&self, ids: &[i64]) -> AppResult<()> {
+        let ids = ids.to_vec();
+        self.writer.execute(move |conn| {
+
+            // 트랜잭션 시작 (중간에 실패하면 롤백)
+            let tx = conn.transaction()?;
+
+            for id in ids {
+                // 삭제 전에 세션 요약용 집계에 반영 (fsm_state가 없는 구버전 이벤트는 제외)
+                tx.execute(
+                    "INSERT INTO session_event_rollup (session_id, app_name, fsm_state, sample_count)
+                     SELECT session_id, app_name, fsm_state, 1 FROM cached_events
+                     WHERE id = ?1 AND fsm_state IS NOT NULL
+                     ON CONFLICT(session_id, app_name, fsm_state) DO UPDATE SET sample_count = sample_count + 1",
+                    [id],
+                )?;
+
+                tx.execute("DELETE FROM cached_events WHERE id = ?1", [id])?;
+            }
 
-        // 트랜잭션 시작 (중간에 실패하면 롤백)
-        let tx = conn.transaction().map_err(|e| e.to_string())?;
+            tx.commit()?;
 
-        for id in ids {
-            tx.execute("DELETE FROM cached_events WHERE id = ?1", [id])
-                .map_err(|e| e.to_string())?;
-        }
+            Ok(())
+        })
+    }
 
-        tx.commit().map_err(|e| e.to_string())?;
+    /// 보관 기간/용량 초과분 삭제 (오래된 것부터)
+    /// 이벤트는 삭제 전에 세션 요약용 집계에 반영 (업로드되지 못한 이벤트도 로컬 요약에는 남도록)
+    pub fn enforce_retention(&self, retention: &RetentionSettings, now_s: u64) -> AppResult<RetentionReport> {
+        const DAY_S: u64 = 24 * 60 * 60;
+        const MB: u64 = 1024 * 1024;
+        let event_before_s = now_s.saturating_sub(retention.event_max_age_days as u64 * DAY_S);
+        let feedback_before_s = now_s.saturating_sub(retention.feedback_max_age_days as u64 * DAY_S);
+
+        // 최신 행부터 누적한 크기가 한도를 넘는 행 + 기간이 지난 행
+        let expired_events = "id IN (
+            SELECT id FROM (
+                SELECT id, timestamp, SUM(
+                    LENGTH(CAST(activity_vector AS BLOB)) + LENGTH(CAST(window_title AS BLOB)) + LENGTH(CAST(app_name AS BLOB))
+                    + LENGTH(client_event_id) + LENGTH(session_id)
+                ) OVER (ORDER BY id DESC) AS newer_bytes
+                FROM cached_events
+            ) WHERE timestamp < ?1 OR newer_bytes > ?2
+        )";
+        let expired_feedback = "id IN (
+            SELECT id FROM (
+                SELECT id, timestamp, SUM(LENGTH(event_id) + LENGTH(feedback_type)) OVER (ORDER BY id DESC) AS newer_bytes
+                FROM cached_feedback
+            ) WHERE timestamp < ?1 OR newer_bytes > ?2
+        )";
+
+        let event_max_bytes = retention.event_max_total_mb as u64 * MB;
+        let feedback_max_bytes = retention.feedback_max_total_mb as u64 * MB;
+
+        self.writer.execute(move |conn| {
+            let tx = conn.transaction()?;
+            let event_params = params![event_before_s, event_max_bytes];
 
-        Ok(())
+            tx.execute(
+                &format!(
+                    "INSERT INTO session_event_rollup (session_id, app_name, fsm_state, sample_count)
+                     SELECT session_id, app_name, fsm_state, COUNT(*) FROM cached_events
+                     WHERE fsm_state IS NOT NULL AND {}
+                     GROUP BY session_id, app_name, fsm_state
+                     ON CONFLICT(session_id, app_name, fsm_state) DO UPDATE SET sample_count = sample_count + excluded.sample_count",
+                    expired_events
+                ),
+                event_params,
+            )
+            .map_err(|e| AppError::from(e).context("Failed to roll up expired events"))?;
+
+            let events_deleted = tx
+                .execute(&format!("DELETE FROM cached_events WHERE {}", expired_events), event_params)
+                .map_err(|e| AppError::from(e).context("Failed to prune cached_events"))? as u64;
+            let dead_letters_deleted = tx
+                .execute("DELETE FROM sync_dead_letter WHERE failed_at < ?1", params![event_before_s])
+                .map_err(|e| AppError::from(e).context("Failed to prune sync_dead_letter"))? as u64;
+            let feedback_deleted = tx
+                .execute(
+                    &format!("DELETE FROM cached_feedback WHERE {}", expired_feedback),
+                    params![feedback_before_s, feedback_max_bytes],
+                )
+                .map_err(|e| AppError::from(e).context("Failed to prune cached_feedback"))? as u64;
+
+            tx.commit()?;
+            Ok(RetentionReport { events_deleted, dead_letters_deleted, feedback_deleted })
+        })
+    }
+
+    // --- 내보내기용 기간 조회 (from_s <= 시각 < to_s) ---
+    // 이벤트의 session_id는 로컬 ID, window_title은 저장된 그대로(시맨틱 토큰)
+
+    pub fn get_events_in_range(&self, from_s: u64, to_s: u64) -> AppResult<Vec<CachedEvent>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, session_id, client_event_id, timestamp, app_name, window_title, activity_vector
+                 FROM cached_events WHERE timestamp >= ?1 AND timestamp < ?2
+                 ORDER BY timestamp ASC, id ASC",
+            )?;
+
+        let rows = stmt
+            .query_map(params![from_s, to_s], |row| {
+                Ok(CachedEvent {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    client_event_id: row.get(2)?,
+                    timestamp: row.get(3)?,
+                    app_name: row.get(4)?,
+                    window_title: row.get(5)?,
+                    activity_vector: row.get(6)?,
+                })
+            })?;
+        rows.collect::<Result<_, _>>().map_err(AppError::from)
+    }
+
+    pub fn get_feedbacks_in_range(&self, from_s: u64, to_s: u64) -> AppResult<Vec<CachedFeedback>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, timestamp, event_id, feedback_type
+                 FROM cached_feedback WHERE timestamp >= ?1 AND timestamp < ?2
+                 ORDER BY timestamp ASC, id ASC",
+            )?;
+
+        let rows = stmt
+            .query_map(params![from_s, to_s], |row| {
+                Ok(CachedFeedback {
+                    id: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    event_id: row.get(2)?,
+                    feedback_type: row.get(3)?,
+                })
+            })?;
+        rows.collect::<Result<_, _>>().map_err(AppError::from)
+    }
+
+    /// 시작 시각 기준으로 기간에 포함되는 세션 기록
+    pub fn get_session_histories_in_range(&self, from_s: u64, to_s: u64) -> AppResult<Vec<SessionHistoryRecord>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT session_id, task_id, intent, start_time_s, end_time_s, goal_duration_min, user_evaluation_score,
+                        paused_s, notification_count, overlay_count, rest_recommendation_count
+                 FROM session_history WHERE start_time_s >= ?1 AND start_time_s < ?2
+                 ORDER BY start_time_s ASC",
+            )?;
+
+        let rows = stmt
+            .query_map(params![from_s, to_s], |row| {
+                Ok(SessionHistoryRecord {
+                    session_id: row.get(0)?,
+                    task_id: row.get(1)?,
+                    intent: row.get(2)?,
+                    start_time_s: row.get(3)?,
+                    end_time_s: row.get(4)?,
+                    goal_duration_min: row.get(5)?,
+                    user_evaluation_score: row.get(6)?,
+                    paused_s: row.get(7)?,
+                    notification_count: row.get(8)?,
+                    overlay_count: row.get(9)?,
+                    rest_recommendation_count: row.get(10)?,
+                })
+            })?;
+        rows.collect::<Result<_, _>>().map_err(AppError::from)
+    }
+
+    /// 비활성 스케줄을 포함한 전체 스케줄 (기간과 무관한 설정 데이터)
+    pub fn get_all_schedules(&self) -> AppResult<Vec<LocalSchedule>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, user_id, task_id, name, start_time, end_time, days_of_week, is_active, start_date
+                 FROM schedules ORDER BY name ASC, id ASC",
+            )?;
+
+        let rows = stmt.query_map([], schedule_from_row)?;
+        rows.collect::<Result<_, _>>().map_err(AppError::from)
+    }
+
+    /// 전체 삭제: 모든 LSN 테이블 비우기 (스키마는 유지)
+    /// keep_auth_token: 서버 삭제 요청이 대기 중이면 전송에 필요한 토큰 행만 남김
+    /// 삭제 후 VACUUM + WAL 체크포인트로 파일에 남은 페이지까지 정리
+    pub fn wipe_all_data(&self, keep_auth_token: bool) -> AppResult<Vec<WipedTable>> {
+        self.writer.execute(move |conn| {
+            let tx = conn.transaction()?;
+
+            let tables: Vec<String> = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT name FROM sqlite_master
+                         WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name != 'erasure_requests'
+                         ORDER BY name",
+                    )?;
+                let rows = stmt.query_map([], |row| row.get(0))?;
+                rows.collect::<Result<_, _>>()?
+            };
+
+            let mut wiped = Vec::with_capacity(tables.len());
+            for table in tables {
+                if keep_auth_token && table == "auth_token" {
+                    continue;
+                }
+                let rows = tx
+                    .execute(&format!("DELETE FROM \"{}\"", table), [])
+                    .map_err(|e| AppError::from(e).context(format!("Failed to wipe {}", table)))?;
+                wiped.push(WipedTable { table, rows: rows as u64 });
+            }
+            // AUTOINCREMENT 카운터 초기화
+            tx.execute("DELETE FROM sqlite_sequence", []).ok();
+            tx.commit()?;
+
+            conn.execute_batch("VACUUM").map_err(|e| AppError::from(e).context("Failed to vacuum database"))?;
+            // WAL 파일에 남은 이전 페이지도 DB에 반영 후 비움
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+                .map_err(|e| AppError::from(e).context("Failed to checkpoint WAL"))?;
+            Ok(wiped)
+        })
+    }
+
+    // --- 서버 데이터 삭제 요청 ---
+
+    pub fn queue_erasure_request(&self, now_s: u64) -> AppResult<()> {
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "INSERT INTO erasure_requests (id, requested_at, attempts) VALUES (1, ?1, 0)
+                 ON CONFLICT(id) DO UPDATE SET requested_at = excluded.requested_at",
+                params![now_s],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn has_pending_erasure_request(&self) -> AppResult<bool> {
+        let conn = self.readers.get()?;
+        conn.query_row("SELECT EXISTS(SELECT 1 FROM erasure_requests)", [], |row| row.get(0))
+            .map_err(AppError::from)
+    }
+
+    pub fn mark_erasure_request_failed(&self, error: &str) -> AppResult<()> {
+        let error = error.to_string();
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "UPDATE erasure_requests SET attempts = attempts + 1, last_error = ?1",
+                params![error],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// 삭제 요청 처리 완료 (또는 폐기): 요청과 남겨둔 토큰을 함께 삭제 -> 로그아웃 상태
+    pub fn complete_erasure_request(&self) -> AppResult<()> {
+        self.writer.execute(move |conn| {
+            let tx = conn.transaction()?;
+            tx.execute("DELETE FROM erasure_requests", [])?;
+            tx.execute("DELETE FROM auth_token", [])?;
+            tx.commit().map_err(AppError::from)
+        })
+    }
+
+    /// 테이블별 행 수와 데이터 크기
+    pub fn get_table_usage(&self) -> AppResult<Vec<TableUsage>> {
+        let conn = self.readers.get()?;
+        let tables: Vec<String> = {
+            let mut stmt = conn
+                .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.collect::<Result<_, _>>()?
+        };
+
+        let mut usage = Vec::with_capacity(tables.len());
+        for table in tables {
+            let bytes_expr = migrations::table_columns(&conn, &table)?
+                .iter()
+                .map(|c| format!("COALESCE(LENGTH(CAST(\"{}\" AS BLOB)), 0)", c))
+                .collect::<Vec<_>>()
+                .join(" + ");
+            let (rows, bytes): (i64, i64) = conn
+                .query_row(
+                    &format!("SELECT COUNT(*), COALESCE(SUM({}), 0) FROM \"{}\"", bytes_expr, table),
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )?;
+            usage.push(TableUsage { table, rows: rows as u64, bytes: bytes as u64 });
+        }
+        Ok(usage)
+    }
+
+    /// DB 파일 크기 (page_count * page_size)
+    pub fn get_db_size_bytes(&self) -> AppResult<u64> {
+        let conn = self.readers.get()?;
+        conn.query_row(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|bytes| bytes as u64)
+        .map_err(AppError::from)
     }
 
     pub fn save_auth_token(
@@ -411,46 +1019,47 @@ impl StorageManager {
         refresh: &str,
         email: &str,
         user_id: &str,
-    ) -> Result<(), String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+    ) -> AppResult<()> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
 
-        // OS의 Credential Manager 서비스가 꺼져 있거나 증발하는 환경의 버그를 우회하기 위해,
-        // 로컬 응용 계층의 Obfuscation 암호화를 적용하여 DB에 안전하게 저장합니다 (M-7 평문 방지 요건 충족)
-        let enc_access = Self::xor_obfuscate(access);
-        let enc_refresh = Self::xor_obfuscate(refresh);
-
-        println!("StorageManager: Saving obfuscated token to DB.");
-        conn.execute(
-            "INSERT OR REPLACE INTO auth_token (id, access_token, refresh_token, user_email, user_id, updated_at)
-             VALUES (1, ?1, ?2, ?3, ?4, ?5)",
-            rusqlite::params![enc_access, enc_refresh, email, user_id, now],
-        ).map_err(|e| e.to_string())?;
-        
-        Ok(())
+        // AEAD(AES-256-GCM)로 암호화하여 저장 (M-7 평문 방지 요건). 키는 DB 밖에 있으므로 DB 파일만으로는 복호화 불가
+        let enc_access = self.vault.encrypt(access, "access_token")?;
+        let enc_refresh = self.vault.encrypt(refresh, "refresh_token")?;
+        let email = email.to_string();
+        let user_id = user_id.to_string();
+
+        println!("StorageManager: Saving encrypted token to DB.");
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO auth_token (id, access_token, refresh_token, user_email, user_id, updated_at)
+                 VALUES (1, ?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![enc_access, enc_refresh, email, user_id, now],
+            )?;
+            Ok(())
+        })
     }
 
-    pub fn load_auth_token(&self) -> Result<Option<(String, String, String, String)>, String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
-        let mut stmt = conn
-            .prepare("SELECT access_token, refresh_token, user_email, user_id FROM auth_token WHERE id = 1")
-            .map_err(|e| e.to_string())?;
+    pub fn load_auth_token(&self) -> AppResult<Option<(String, String, String, String)>> {
+        let result = self
+            .readers
+            .get()?
+            .query_row(
+                "SELECT access_token, refresh_token, user_email, user_id FROM auth_token WHERE id = 1",
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?, // user_id
+                    ))
+                },
+            )
+            .optional()?;
 
-        let result = stmt
-            .query_row([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, String>(2)?,
-                    row.get::<_, String>(3)?, // user_id
-                ))
-            })
-            .optional()
-            .map_err(|e| e.to_string())?;
-            
         if let Some((db_access, db_refresh, email, user_id)) = result {
             // DB에서 "keyring" 플래그를 잡았으나 실제 데이터가 증발한 레거시 값인 경우를 대비한 하위 호환
             if db_access == "keyring" {
@@ -458,185 +1067,615 @@ impl StorageManager {
                 return Ok(None);
             }
             
-            // 기존 데이터가 평문인지, 인코딩된 것인지 길이/HEX 여부로 유추
-            let is_obfuscated = db_access.len() % 2 == 0 && db_access.chars().all(|c| c.is_ascii_hexdigit());
-            
-            let final_access = if is_obfuscated { Self::xor_deobfuscate(&db_access) } else { db_access.clone() };
-            let final_refresh = if is_obfuscated { Self::xor_deobfuscate(&db_refresh) } else { db_refresh.clone() };
+            if TokenVault::is_encrypted(&db_access) {
+                // 키 파일 분실/교체 또는 변조 -> 재로그인 유도
+                return match (
+                    self.vault.decrypt(&db_access, "access_token"),
+                    self.vault.decrypt(&db_refresh, "refresh_token"),
+                ) {
+                    (Ok(access), Ok(refresh)) => Ok(Some((access, refresh, email, user_id))),
+                    (Err(e), _) | (_, Err(e)) => {
+                        eprintln!("StorageManager: Failed to decrypt stored token: {}. Login required.", e);
+                        Ok(None)
+                    }
+                };
+            }
+
+            // [Migration] 구버전 hex-XOR(또는 평문) 토큰 -> 복원 후 AEAD로 재암호화
+            let (final_access, final_refresh) = if token_vault::is_legacy_xor(&db_access) {
+                match (
+                    token_vault::legacy_xor_deobfuscate(&db_access),
+                    token_vault::legacy_xor_deobfuscate(&db_refresh),
+                ) {
+                    (Some(access), Some(refresh)) => (access, refresh),
+                    _ => {
+                        eprintln!("StorageManager: Legacy token is corrupted. Login required.");
+                        return Ok(None);
+                    }
+                }
+            } else {
+                (db_access, db_refresh)
+            };
+
+            let enc_access = self.vault.encrypt(&final_access, "access_token")?;
+            let enc_refresh = self.vault.encrypt(&final_refresh, "refresh_token")?;
+            self.writer.execute(move |conn| {
+                conn.execute(
+                    "UPDATE auth_token SET access_token = ?1, refresh_token = ?2 WHERE id = 1",
+                    params![enc_access, enc_refresh],
+                )?;
+                Ok(())
+            })?;
+            println!("StorageManager: Migrated legacy token to encrypted storage.");
 
             return Ok(Some((final_access, final_refresh, email, user_id)));
         }
         Ok(None)
     }
 
-    pub fn delete_auth_token(&self) -> Result<(), String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
-        conn.execute("DELETE FROM auth_token WHERE id = 1", [])
-            .map_err(|e| e.to_string())?;
-        Ok(())
+    pub fn delete_auth_token(&self) -> AppResult<()> {
+        self.writer.execute(move |conn| {
+            conn.execute("DELETE FROM auth_token WHERE id = 1", [])?;
+            Ok(())
+        })
     }
 
-    // --- 보안을 위한 유틸리티 함수 ---
-    fn xor_obfuscate(data: &str) -> String {
-        let key = b"force-focus-secret-key-2026-secure-vault";
-        let xored: Vec<u8> = data.bytes().enumerate().map(|(i, b)| b ^ key[i % key.len()]).collect();
-        xored.iter().map(|b| format!("{:02x}", b)).collect()
+    // --- 보안 이벤트 ---
+
+    /// 보안 이벤트 기록 (detail에는 토큰 등 민감 정보를 넣지 않음)
+    pub fn record_security_event(&self, kind: &str, detail: &str) -> AppResult<()> {
+        let kind = kind.to_string();
+        let detail = detail.to_string();
+        self.writer.execute(move |conn| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            conn.execute(
+                "INSERT INTO security_events (timestamp, kind, detail) VALUES (?1, ?2, ?3)",
+                params![now, kind, detail],
+            )
+            .map_err(|e| AppError::from(e).context("Failed to record security event"))?;
+            Ok(())
+        })
     }
 
-    fn xor_deobfuscate(data: &str) -> String {
-        let key = b"force-focus-secret-key-2026-secure-vault";
-        let mut bytes = Vec::new();
-        let chars: Vec<char> = data.chars().collect();
-        for i in (0..chars.len()).step_by(2) {
-            if i + 1 < chars.len() {
-                if let Ok(b) = u8::from_str_radix(&format!("{}{}", chars[i], chars[i+1]), 16) {
-                    bytes.push(b);
-                }
-            }
-        }
-        let original: Vec<u8> = bytes.into_iter().enumerate().map(|(i, b)| b ^ key[i % key.len()]).collect();
-        String::from_utf8(original).unwrap_or_default()
+    /// 최근 보안 이벤트 (kind, detail) 목록 (최신순)
+    pub fn get_security_events(&self, limit: u32) -> AppResult<Vec<(String, String)>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare("SELECT kind, detail FROM security_events ORDER BY id DESC LIMIT ?1")?;
+        let rows = stmt
+            .query_map(params![limit], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(AppError::from)
     }
 
-    // --- 스케줄 관리 함수 ---
+    // --- 세션 Outbox / ID 매핑 ---
+
+    pub fn enqueue_session_op(&self, local_session_id: &str, op: &str, payload: &str) -> AppResult<()> {
+        let local_session_id = local_session_id.to_string();
+        let op = op.to_string();
+        let payload = payload.to_string();
+        self.writer.execute(move |conn| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            conn.execute(
+                "INSERT INTO session_outbox (local_session_id, op, payload, created_at) VALUES (?1, ?2, ?3, ?4)",
+                params![local_session_id, op, payload, now],
+            )
+            .map_err(|e| AppError::from(e).context("Failed to enqueue session op"))?;
+            Ok(())
+        })
+    }
 
-    pub fn sync_schedules(&self, schedules: Vec<LocalSchedule>) -> Result<(), String> {
-        let mut conn = self.conn.lock().map_err(|e| e.to_string())?;
-        let tx = conn.transaction().map_err(|e| e.to_string())?;
+    /// 대기 중인 작업 (등록 순서)
+    pub fn get_pending_session_ops(&self, limit: u32) -> AppResult<Vec<SessionOutboxItem>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, local_session_id, op, payload, attempts
+                 FROM session_outbox ORDER BY id ASC LIMIT ?1",
+            )?;
+        let rows = stmt
+            .query_map(params![limit], |row| {
+                Ok(SessionOutboxItem {
+                    id: row.get(0)?,
+                    local_session_id: row.get(1)?,
+                    op: row.get(2)?,
+                    payload: row.get(3)?,
+                    attempts: row.get(4)?,
+                })
+            })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(AppError::from)
+    }
 
-        tx.execute("DELETE FROM schedules", [])
-            .map_err(|e| e.to_string())?;
+    pub fn delete_session_op(&self, id: i64) -> AppResult<()> {
+        self.writer.execute(move |conn| {
+            conn.execute("DELETE FROM session_outbox WHERE id = ?1", params![id])?;
+            Ok(())
+        })
+    }
 
-        for s in schedules {
-            let days_json = serde_json::to_string(&s.days_of_week)
-                .map_err(|e| format!("Failed to serialize days: {}", e))?;
+    /// 세션의 남은 작업 전부 폐기 (start가 영구 실패하면 end도 보낼 수 없음)
+    pub fn delete_session_ops_for(&self, local_session_id: &str) -> AppResult<()> {
+        let local_session_id = local_session_id.to_string();
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "DELETE FROM session_outbox WHERE local_session_id = ?1",
+                params![local_session_id],
+            )?;
+            Ok(())
+        })
+    }
 
-            tx.execute(
-                "INSERT INTO schedules (id, user_id, task_id, name, start_time, end_time, days_of_week, start_date, is_active)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-                rusqlite::params![
-                    s.id, s.user_id, s.task_id, s.name, s.start_time, s.end_time, days_json, s.start_date, s.is_active as i32
-                ],
-            ).map_err(|e| e.to_string())?;
+    /// 전송 실패 기록 (재시도 횟수 증가)
+    pub fn mark_session_op_failed(&self, id: i64, error: &str) -> AppResult<()> {
+        let error = error.to_string();
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "UPDATE session_outbox SET attempts = attempts + 1, last_error = ?2 WHERE id = ?1",
+                params![id, error],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn save_session_id_mapping(&self, local_session_id: &str, server_session_id: &str) -> AppResult<()> {
+        let local_session_id = local_session_id.to_string();
+        let server_session_id = server_session_id.to_string();
+        self.writer.execute(move |conn| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            conn.execute(
+                "INSERT OR REPLACE INTO session_id_map (local_session_id, server_session_id, synced_at)
+                 VALUES (?1, ?2, ?3)",
+                params![local_session_id, server_session_id, now],
+            )
+            .map_err(|e| AppError::from(e).context("Failed to save session id mapping"))?;
+            Ok(())
+        })
+    }
+
+    pub fn get_server_session_id(&self, local_session_id: &str) -> AppResult<Option<String>> {
+        let conn = self.readers.get()?;
+        conn.query_row(
+            "SELECT server_session_id FROM session_id_map WHERE local_session_id = ?1",
+            params![local_session_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(AppError::from)
+    }
+
+    // --- 사용자 설정 관리 함수 ---
+
+    pub fn save_user_settings(&self, settings: &UserSettings) -> AppResult<()> {
+        let settings_json = serde_json::to_string(settings)
+            .map_err(|e| AppError::from(e).context("Failed to serialize settings"))?;
+        self.writer.execute(move |conn| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            conn.execute(
+                "INSERT OR REPLACE INTO app_settings (id, settings_json, updated_at) VALUES (1, ?1, ?2)",
+                params![settings_json, now],
+            )?;
+
+            Ok(())
+        })
+    }
+
+    pub fn load_user_settings(&self) -> AppResult<Option<UserSettings>> {
+        let conn = self.readers.get()?;
+        let settings_json: Option<String> = conn
+            .query_row("SELECT settings_json FROM app_settings WHERE id = 1", [], |row| row.get(0))
+            .optional()?;
+
+        match settings_json {
+            Some(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| AppError::from(e).context("Failed to parse stored settings")),
+            None => Ok(None),
         }
-        tx.commit().map_err(|e| e.to_string())?;
-        Ok(())
     }
 
-    pub fn get_active_schedules(&self, user_id: &str) -> Result<Vec<LocalSchedule>, String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+    // --- 스케줄 관리 함수 ---
+
+    /// 서버 스케줄 목록을 로컬에 반영 (변경된 행만 upsert, 서버에 없는 행 삭제). 실제 변경이 있었으면 true
+    pub fn sync_schedules(&self, schedules: Vec<LocalSchedule>) -> AppResult<bool> {
+        self.writer.execute(move |conn| {
+            let tx = conn.transaction()?;
+
+            let existing: HashMap<String, LocalSchedule> = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT id, user_id, task_id, name, start_time, end_time, days_of_week, is_active, start_date FROM schedules",
+                    )?;
+                let rows = stmt.query_map([], schedule_from_row)?;
+                rows.map(|r| r.map(|s| (s.id.clone(), s)))
+                    .collect::<Result<_, _>>()?
+            };
+
+            let mut changed = false;
+            for s in &schedules {
+                if existing.get(&s.id) == Some(s) {
+                    continue;
+                }
+                let days_json = serde_json::to_string(&s.days_of_week)
+                    .map_err(|e| AppError::from(e).context("Failed to serialize days"))?;
+
+                tx.execute(
+                    "INSERT OR REPLACE INTO schedules (id, user_id, task_id, name, start_time, end_time, days_of_week, start_date, is_active)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    rusqlite::params![
+                        s.id, s.user_id, s.task_id, s.name, s.start_time, s.end_time, days_json, s.start_date, s.is_active as i32
+                    ],
+                )?;
+                changed = true;
+            }
+
+            for id in existing.keys() {
+                if !schedules.iter().any(|s| &s.id == id) {
+                    tx.execute("DELETE FROM schedules WHERE id = ?1", params![id])?;
+                    changed = true;
+                }
+            }
+            tx.commit()?;
+            Ok(changed)
+        })
+    }
+
+    pub fn get_active_schedules(&self, user_id: &str) -> AppResult<Vec<LocalSchedule>> {
+        let conn = self.readers.get()?;
         let mut stmt = conn
             .prepare(
                 "SELECT id, user_id, task_id, name, start_time, end_time, days_of_week, is_active, start_date 
              FROM schedules WHERE is_active = 1",
-            )
-            .map_err(|e| e.to_string())?;
+            )?;
 
         let rows = stmt
-            .query_map([], |row| {
-                let days_str: String = row.get(6)?;
-                let days_vec: Vec<u8> = serde_json::from_str(&days_str).unwrap_or_default();
-                let is_active_int: i32 = row.get(7)?;
-                let start_date: Option<String> = row.get(8).unwrap_or(None);
-
-                Ok(LocalSchedule {
-                    id: row.get(0)?,
-                    user_id: row.get(1)?,
-                    task_id: row.get(2)?,
-                    name: row.get(3)?,
-                    start_time: row.get(4)?,
-                    end_time: row.get(5)?,
-                    days_of_week: days_vec,
-                    start_date, // [Mapped]
-                    is_active: is_active_int == 1,
-                })
-            })
-            .map_err(|e| e.to_string())?;
+            .query_map([], schedule_from_row)?;
 
         let mut schedules = Vec::new();
         for row in rows {
-            schedules.push(row.map_err(|e| e.to_string())?);
+            schedules.push(row?);
         }
         Ok(schedules)
     }
 
+    /// 스케줄 회차 발화 기록. 이미 기록된 회차면 false (중복 실행 방지)
+    pub fn record_schedule_run(&self, schedule_id: &str, occurrence_start_s: u64, outcome: &str) -> AppResult<bool> {
+        let schedule_id = schedule_id.to_string();
+        let outcome = outcome.to_string();
+        self.writer.execute(move |conn| {
+            let now_s = SystemTime::now()
+                .duration_since(UNIX_EPOCH)?
+                .as_secs();
+
+            let inserted = conn
+                .execute(
+                    "INSERT OR IGNORE INTO schedule_runs (schedule_id, occurrence_start_s, outcome, recorded_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![schedule_id, occurrence_start_s, outcome, now_s],
+                )?;
+            Ok(inserted == 1)
+        })
+    }
+
+    pub fn is_schedule_run_recorded(&self, schedule_id: &str, occurrence_start_s: u64) -> AppResult<bool> {
+        let conn = self.readers.get()?;
+        let exists: Option<i64> = conn
+            .query_row(
+                "SELECT 1 FROM schedule_runs WHERE schedule_id = ?1 AND occurrence_start_s = ?2",
+                params![schedule_id, occurrence_start_s],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(exists.is_some())
+    }
+
+    /// 오래된 발화 기록 정리 (occurrence_start_s < before_s)
+    pub fn prune_schedule_runs(&self, before_s: u64) -> AppResult<()> {
+        self.writer.execute(move |conn| {
+            conn.execute("DELETE FROM schedule_runs WHERE occurrence_start_s < ?1", params![before_s])?;
+            Ok(())
+        })
+    }
+
+    pub fn load_schedule_last_evaluated(&self) -> AppResult<Option<u64>> {
+        let conn = self.readers.get()?;
+        conn.query_row("SELECT last_evaluated_s FROM schedule_monitor_state WHERE id = 1", [], |row| row.get(0))
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    pub fn save_schedule_last_evaluated(&self, last_evaluated_s: u64) -> AppResult<()> {
+        self.writer.execute(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO schedule_monitor_state (id, last_evaluated_s) VALUES (1, ?1)",
+                params![last_evaluated_s],
+            )?;
+            Ok(())
+        })
+    }
+
     // --- Task 관리 함수  ---
 
-    pub fn sync_tasks(&self, tasks: Vec<LocalTask>) -> Result<(), String> {
-        let mut conn = self.conn.lock().map_err(|e| e.to_string())?;
-        let tx = conn.transaction().map_err(|e| e.to_string())?;
+    /// 서버 Task 목록을 로컬에 반영 (변경된 행만 upsert, 서버에 없는 행 삭제). 실제 변경이 있었으면 true
+    /// 아직 올리지 않은 로컬 수정(dirty)은 덮어쓰지도 지우지도 않음 (Up-Sync에서 충돌 해결)
+    pub fn sync_tasks(&self, tasks: Vec<LocalTask>) -> AppResult<bool> {
+        self.writer.execute(move |conn| {
+            let tx = conn.transaction()?;
+
+            let (existing, dirty_ids): (HashMap<String, LocalTask>, Vec<String>) = {
+                let mut stmt = tx
+                    .prepare(&format!("SELECT {}, dirty FROM tasks", TASK_COLUMNS))?;
+                let rows = stmt
+                    .query_map([], |row| Ok((task_from_row(row)?, row.get::<_, i64>(8)? != 0)))?;
+                let mut existing = HashMap::new();
+                let mut dirty_ids = Vec::new();
+                for row in rows {
+                    let (task, dirty) = row?;
+                    if dirty {
+                        dirty_ids.push(task.id.clone());
+                    }
+                    existing.insert(task.id.clone(), task);
+                }
+                (existing, dirty_ids)
+            };
 
-        tx.execute("DELETE FROM tasks", [])
-            .map_err(|e| e.to_string())?;
+            let mut changed = false;
+            for t in &tasks {
+                if existing.get(&t.id) == Some(t) || dirty_ids.contains(&t.id) {
+                    continue;
+                }
+                //  JSON 직렬화 제거, Option<String> 그대로 저장
+                tx.execute(
+                    "INSERT OR REPLACE INTO tasks (id, user_id, task_name, description, target_executable, target_arguments, status, updated_at, dirty)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0)",
+                    rusqlite::params![
+                        t.id, t.user_id, t.task_name, t.description, t.target_executable, t.target_arguments, t.status, t.updated_at
+                    ],
+                )?;
+                changed = true;
+            }
 
-        for t in tasks {
-            //  JSON 직렬화 제거, Option<String> 그대로 저장
-            tx.execute(
-                "INSERT INTO tasks (id, user_id, task_name, description, target_executable, target_arguments, status)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                rusqlite::params![
-                    t.id, t.user_id, t.task_name, t.description, t.target_executable, t.target_arguments, t.status
+            for id in existing.keys() {
+                if !dirty_ids.contains(id) && !tasks.iter().any(|t| &t.id == id) {
+                    tx.execute("DELETE FROM tasks WHERE id = ?1", params![id])?;
+                    changed = true;
+                }
+            }
+            tx.commit()?;
+            Ok(changed)
+        })
+    }
+
+    /// 로컬 Task 생성/수정 (dirty 표시 -> 다음 동기화 때 서버로 전송). 서버 버전(updated_at)은 유지
+    pub fn save_local_task(&self, task: &LocalTask) -> AppResult<()> {
+        let task = task.clone();
+        self.writer.execute(move |conn| {
+            let now_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64;
+
+            conn.execute(
+                "INSERT INTO tasks (id, user_id, task_name, description, target_executable, target_arguments, status, updated_at, dirty, local_updated_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 1, ?9)
+                 ON CONFLICT(id) DO UPDATE SET
+                    task_name = excluded.task_name,
+                    description = excluded.description,
+                    target_executable = excluded.target_executable,
+                    target_arguments = excluded.target_arguments,
+                    status = excluded.status,
+                    dirty = 1,
+                    local_updated_ms = excluded.local_updated_ms",
+                params![
+                    task.id, task.user_id, task.task_name, task.description, task.target_executable,
+                    task.target_arguments, task.status, task.updated_at, now_ms
                 ],
-            ).map_err(|e| e.to_string())?;
-        }
-        tx.commit().map_err(|e| e.to_string())?;
-        Ok(())
+            )
+            .map_err(|e| AppError::from(e).context("Failed to save task"))?;
+            Ok(())
+        })
     }
 
-    pub fn get_task_by_id(&self, task_id: &str) -> Result<Option<LocalTask>, String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+    /// 서버로 올릴 로컬 수정 Task (오래된 수정부터)
+    pub fn get_dirty_tasks(&self) -> AppResult<Vec<DirtyTask>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {}, local_updated_ms FROM tasks WHERE dirty = 1 ORDER BY local_updated_ms ASC",
+                TASK_COLUMNS
+            ))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(DirtyTask {
+                    task: task_from_row(row)?,
+                    local_updated_ms: row.get::<_, Option<i64>>(8)?.unwrap_or(0),
+                })
+            })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(AppError::from)
+    }
+
+    /// 서버가 확정한 버전으로 로컬 행 갱신.
+    /// 전송 이후 다시 수정된 행(local_updated_ms 변경)은 건드리지 않고 false (다음 주기에 다시 전송)
+    pub fn apply_task_sync_result(&self, server_task: &LocalTask, sent_local_updated_ms: i64) -> AppResult<bool> {
+        let server_task = server_task.clone();
+        self.writer.execute(move |conn| {
+            let updated = conn
+                .execute(
+                    "UPDATE tasks SET task_name = ?2, description = ?3, target_executable = ?4, target_arguments = ?5,
+                        status = ?6, updated_at = ?7, dirty = 0
+                     WHERE id = ?1 AND dirty = 1 AND local_updated_ms = ?8",
+                    params![
+                        server_task.id, server_task.task_name, server_task.description, server_task.target_executable,
+                        server_task.target_arguments, server_task.status, server_task.updated_at, sent_local_updated_ms
+                    ],
+                )?;
+            Ok(updated == 1)
+        })
+    }
+
+    pub fn record_task_conflict(&self, task_id: &str, resolution: &str, local: &LocalTask, server: &LocalTask) -> AppResult<()> {
+        let task_id = task_id.to_string();
+        let resolution = resolution.to_string();
+        let local_json = serde_json::to_string(local)?;
+        let server_json = serde_json::to_string(server)?;
+        self.writer.execute(move |conn| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+
+            conn.execute(
+                "INSERT INTO task_conflicts (task_id, resolution, local_version, server_version, resolved_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![task_id, resolution, local_json, server_json, now],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// 서버가 거부한 로컬 Task 수정을 sync_dead_letter로 옮기고 dirty 해제 (뒤의 Task 전송을 막지 않도록).
+    /// 전송 이후 다시 수정된 행(local_updated_ms 변경)은 dirty 유지 -> 새 수정은 다음 주기에 전송
+    pub fn dead_letter_rejected_task(&self, item: &DirtyTask, error: &str) -> AppResult<()> {
+        let task_id = item.task.id.clone();
+        let sent_local_updated_ms = item.local_updated_ms;
+        let payload = serde_json::to_string(&item.task)?;
+        let error = error.to_string();
+        self.writer.execute(move |conn| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let tx = conn.transaction()?;
+
+            tx.execute(
+                "INSERT INTO sync_dead_letter (kind, payload, last_error, failed_at) VALUES ('task', ?1, ?2, ?3)",
+                params![payload, error, now],
+            )?;
+            tx.execute(
+                "UPDATE tasks SET dirty = 0 WHERE id = ?1 AND dirty = 1 AND local_updated_ms = ?2",
+                params![task_id, sent_local_updated_ms],
+            )?;
+
+            tx.commit()?;
+            Ok(())
+        })
+    }
+
+    /// 최근 충돌 기록 (task_id, resolution), 최신순
+    pub fn get_task_conflicts(&self, limit: u32) -> AppResult<Vec<(String, String)>> {
+        let conn = self.readers.get()?;
+        let mut stmt = conn
+            .prepare("SELECT task_id, resolution FROM task_conflicts ORDER BY id DESC LIMIT ?1")?;
+        let rows = stmt
+            .query_map(params![limit], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(AppError::from)
+    }
+
+    /// 컬렉션별 마지막 Down-Sync ETag
+    pub fn get_sync_etag(&self, collection: &str) -> AppResult<Option<String>> {
+        let conn = self.readers.get()?;
+        conn.query_row("SELECT etag FROM sync_cursors WHERE collection = ?1", params![collection], |row| row.get(0))
+            .optional()
+            .map_err(AppError::from)
+    }
+
+    /// 로컬 반영이 끝난 뒤에만 저장 (반영 실패 시 다음 주기에 전체 목록을 다시 받도록)
+    pub fn save_sync_etag(&self, collection: &str, etag: &str) -> AppResult<()> {
+        let collection = collection.to_string();
+        let etag = etag.to_string();
+        self.writer.execute(move |conn| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            conn.execute(
+                "INSERT OR REPLACE INTO sync_cursors (collection, etag, updated_at) VALUES (?1, ?2, ?3)",
+                params![collection, etag, now],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn get_task_by_id(&self, task_id: &str) -> AppResult<Option<LocalTask>> {
+        let conn = self.readers.get()?;
         let mut stmt = conn.prepare(
-            "SELECT id, user_id, task_name, description, target_executable, target_arguments, status 
-             FROM tasks WHERE id = ?1"
-        ).map_err(|e| e.to_string())?;
+            &format!("SELECT {} FROM tasks WHERE id = ?1", TASK_COLUMNS)
+        )?;
 
         let result = stmt
-            .query_row([task_id], |row| {
-                Ok(LocalTask {
-                    id: row.get(0)?,
-                    user_id: row.get(1)?,
-                    task_name: row.get(2)?,
-                    description: row.get(3)?,
-                    target_executable: row.get(4)?,
-                    target_arguments: row.get(5)?, // String으로 바로 읽음
-                    status: row.get(6)?,
-                })
-            })
-            .optional()
-            .map_err(|e| e.to_string())?;
+            .query_row([task_id], task_from_row)
+            .optional()?;
 
         Ok(result)
     }
 
     //  유저별 Task 목록 조회
-    pub fn get_tasks_by_user(&self, user_id: &str) -> Result<Vec<LocalTask>, String> {
-        let conn = self.conn.lock().map_err(|e| e.to_string())?;
+    pub fn get_tasks_by_user(&self, user_id: &str) -> AppResult<Vec<LocalTask>> {
+        let conn = self.readers.get()?;
         let mut stmt = conn.prepare(
-            "SELECT id, user_id, task_name, description, target_executable, target_arguments, status 
-             FROM tasks WHERE user_id = ?1"
-        ).map_err(|e| e.to_string())?;
+            &format!("SELECT {} FROM tasks WHERE user_id = ?1", TASK_COLUMNS)
+        )?;
 
         let rows = stmt
-            .query_map([user_id], |row| {
-                Ok(LocalTask {
-                    id: row.get(0)?,
-                    user_id: row.get(1)?,
-                    task_name: row.get(2)?,
-                    description: row.get(3)?,
-                    target_executable: row.get(4)?,
-                    target_arguments: row.get(5)?,
-                    status: row.get(6)?,
-                })
-            })
-            .map_err(|e| e.to_string())?;
+            .query_map([user_id], task_from_row)?;
 
         let mut tasks = Vec::new();
         for row in rows {
-            tasks.push(row.map_err(|e| e.to_string())?);
+            tasks.push(row?);
         }
         Ok(tasks)
     }
 }
+
+// task_from_row가 읽는 컬럼 순서
+const TASK_COLUMNS: &str = "id, user_id, task_name, description, target_executable, target_arguments, status, updated_at";
+
+fn task_from_row(row: &rusqlite::Row) -> rusqlite::Result<LocalTask> {
+    Ok(LocalTask {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        task_name: row.get(2)?,
+        description: row.get(3)?,
+        target_executable: row.get(4)?,
+        target_arguments: row.get(5)?, // String으로 바로 읽음
+        status: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+// SELECT id, user_id, task_id, name, start_time, end_time, days_of_week, is_active, start_date 순서
+fn schedule_from_row(row: &rusqlite::Row) -> rusqlite::Result<LocalSchedule> {
+    let days_str: String = row.get(6)?;
+    let days_vec: Vec<u8> = serde_json::from_str(&days_str).unwrap_or_default();
+    let is_active_int: i32 = row.get(7)?;
+    let start_date: Option<String> = row.get(8).unwrap_or(None);
+
+    Ok(LocalSchedule {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        task_id: row.get(2)?,
+        name: row.get(3)?,
+        start_time: row.get(4)?,
+        end_time: row.get(5)?,
+        days_of_week: days_vec,
+        start_date, // [Mapped]
+        is_active: is_active_int == 1,
+    })
+}
 // --- 유닛 테스트 모듈 ---
 #[cfg(test)]
 mod tests {
@@ -657,6 +1696,12 @@ mod tests {
             session_id: "test-session-123".to_string(),
             task_id: Some("test-task-456".to_string()),
             start_time_s: 123456789,
+            intent: Some("research".to_string()),
+            scheduled_end_s: Some(1678890000),
+            paused_total_s: 120,
+            paused_at_s: Some(123457000),
+            goal_duration_min: Some(50),
+            goal_reached_s: Some(123460000),
         };
 
         // 1. 저장 테스트
@@ -673,6 +1718,12 @@ mod tests {
         assert_eq!(loaded_info.session_id, info.session_id);
         assert_eq!(loaded_info.task_id, info.task_id);
         assert_eq!(loaded_info.start_time_s, info.start_time_s);
+        assert_eq!(loaded_info.intent, info.intent);
+        assert_eq!(loaded_info.scheduled_end_s, info.scheduled_end_s);
+        assert_eq!(loaded_info.paused_total_s, info.paused_total_s);
+        assert_eq!(loaded_info.paused_at_s, info.paused_at_s);
+        assert_eq!(loaded_info.goal_duration_min, info.goal_duration_min);
+        assert_eq!(loaded_info.goal_reached_s, info.goal_reached_s);
     }
 
     #[test]
@@ -683,6 +1734,12 @@ mod tests {
             session_id: "test-session-123".to_string(),
             task_id: None,
             start_time_s: 123456789,
+            intent: None,
+            scheduled_end_s: None,
+            paused_total_s: 0,
+            paused_at_s: None,
+            goal_duration_min: None,
+            goal_reached_s: None,
         };
 
         // 1. 저장
@@ -728,13 +1785,14 @@ mod tests {
 
         // 변경된 cache_event 시그니처 호출
         storage
-            .cache_event("session-1", "test-event-1", "chrome.exe", "YouTube", &json_1)
+            .cache_event("session-1", "test-event-1", "chrome.exe", "YouTube", &json_1, "FOCUS")
             .expect("Failed to cache event 1");
         storage
-            .cache_event("session-1", "test-event-2", "code.exe", "lib.rs", &json_2)
+            .cache_event("session-1", "test-event-2", "code.exe", "lib.rs", &json_2, "FOCUS")
             .expect("Failed to cache event 2");
+        storage.flush().unwrap(); // 이벤트는 writer 큐를 거쳐 저장됨
 
-        let conn = storage.conn.lock().unwrap();
+        let conn = storage.readers.get().unwrap();
         // 스키마(activity_vector)에서 데이터 검증
         let mut stmt = conn
             .prepare(
@@ -748,4 +1806,383 @@ mod tests {
         assert_eq!(count, 1);
         assert!(vector_str.contains("meaningful_input_events\":10")); // JSON 내용 검증
     }
+
+    #[test]
+    fn test_save_and_load_user_settings() {
+        let storage = setup_test_db();
+
+        // 1. 저장된 설정이 없으면 None
+        assert!(storage.load_user_settings().unwrap().is_none());
+
+        // 2. 저장 후 로드
+        let mut settings = UserSettings::default();
+        settings.fsm.threshold_notify_sec = 45.0;
+        settings.fsm.multiplier_inlier = -1.0;
+        storage.save_user_settings(&settings).expect("Failed to save settings");
+
+        let loaded = storage
+            .load_user_settings()
+            .expect("Failed to load settings")
+            .expect("Settings not found after saving");
+        assert_eq!(loaded, settings);
+
+        // 3. 덮어쓰기 (단일 행 유지)
+        settings.fsm.snooze_sec = 20.0;
+        storage.save_user_settings(&settings).unwrap();
+        let reloaded = storage.load_user_settings().unwrap().unwrap();
+        assert_eq!(reloaded.fsm.snooze_sec, 20.0);
+    }
+
+    #[test]
+    fn test_auth_token_encrypted_and_legacy_migrated() {
+        let storage = setup_test_db();
+        let raw_access = || -> String {
+            let conn = storage.readers.get().unwrap();
+            conn.query_row("SELECT access_token FROM auth_token WHERE id = 1", [], |row| row.get(0)).unwrap()
+        };
+
+        // 1. 저장된 값은 평문이 아니어야 함
+        storage.save_auth_token("access-abc", "refresh-xyz", "a@b.c", "user-1").unwrap();
+        assert!(TokenVault::is_encrypted(&raw_access()));
+        assert!(!raw_access().contains("access-abc"));
+        let loaded = storage.load_auth_token().unwrap().unwrap();
+        assert_eq!((loaded.0.as_str(), loaded.1.as_str()), ("access-abc", "refresh-xyz"));
+
+        // 2. 구버전 hex-XOR 행 -> 로드 시 복원 + 재암호화
+        let legacy_xor = |s: &str| -> String {
+            let key = b"force-focus-secret-key-2026-secure-vault";
+            s.bytes().enumerate().map(|(i, b)| format!("{:02x}", b ^ key[i % key.len()])).collect()
+        };
+        let (old_access, old_refresh) = (legacy_xor("old-access"), legacy_xor("old-refresh"));
+        storage
+            .writer
+            .execute(move |conn| {
+                conn.execute(
+                    "UPDATE auth_token SET access_token = ?1, refresh_token = ?2 WHERE id = 1",
+                    params![old_access, old_refresh],
+                )?;
+                Ok(())
+            })
+            .unwrap();
+        let migrated = storage.load_auth_token().unwrap().unwrap();
+        assert_eq!((migrated.0.as_str(), migrated.1.as_str()), ("old-access", "old-refresh"));
+        assert!(TokenVault::is_encrypted(&raw_access()));
+        assert_eq!(storage.load_auth_token().unwrap().unwrap().0, "old-access");
+    }
+
+    #[test]
+    fn test_security_events_newest_first() {
+        let storage = setup_test_db();
+        storage.record_security_event("login_callback_state_mismatch", "query_keys=code,state").unwrap();
+        storage.record_security_event("login_callback_no_pending", "query_keys=code,state").unwrap();
+
+        let events = storage.get_security_events(10).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].0, "login_callback_no_pending");
+        assert_eq!(storage.get_security_events(1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_session_outbox_holds_events_until_mapped() {
+        let storage = setup_test_db();
+        storage.enqueue_session_op("local-1", SESSION_OP_START, "{}").unwrap();
+        storage.enqueue_session_op("local-1", SESSION_OP_END, "{}").unwrap();
+        storage.cache_event("local-1", "evt-1", "code.exe", "lib.rs", "{}", "FOCUS").unwrap();
+
+        // 1. 등록 순서대로 반환, 실패 시 재시도 횟수 증가
+        let ops = storage.get_pending_session_ops(10).unwrap();
+        assert_eq!(ops.iter().map(|o| o.op.as_str()).collect::<Vec<_>>(), vec!["start", "end"]);
+        storage.mark_session_op_failed(ops[0].id, "HTTP 503").unwrap();
+        assert_eq!(storage.get_pending_session_ops(1).unwrap()[0].attempts, 1);
+
+        // 2. start 전송 전에는 해당 세션의 이벤트를 올리지 않음
+        assert!(storage.get_unsynced_events(50).unwrap().is_empty());
+
+        // 3. start 성공 + 매핑 저장 -> 이벤트는 서버 세션 ID로 치환
+        storage.save_session_id_mapping("local-1", "server-abc").unwrap();
+        storage.delete_session_op(ops[0].id).unwrap();
+        assert_eq!(storage.get_server_session_id("local-1").unwrap().as_deref(), Some("server-abc"));
+        let events = storage.get_unsynced_events(50).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].session_id, "server-abc");
+
+        storage.delete_session_ops_for("local-1").unwrap();
+        assert!(storage.get_pending_session_ops(10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cached_event_unique_and_upload_marker() {
+        let storage = setup_test_db();
+        storage.cache_event("s-1", "evt-1", "code.exe", "lib.rs", "{}", "FOCUS").unwrap();
+        storage.cache_event("s-1", "evt-1", "code.exe", "lib.rs", "{}", "FOCUS").unwrap(); // 중복 무시
+        storage.flush().unwrap();
+        let events = storage.get_unsynced_events(10).unwrap();
+        assert_eq!(events.len(), 1);
+
+        storage.mark_events_upload_attempted(&[events[0].id]).unwrap();
+        let conn = storage.readers.get().unwrap();
+        let attempted: Option<i64> = conn
+            .query_row("SELECT upload_attempted_at FROM cached_events WHERE id = ?1", [events[0].id], |row| row.get(0))
+            .unwrap();
+        assert!(attempted.is_some());
+    }
+
+    #[test]
+    fn test_rejected_event_moves_to_dead_letter() {
+        let storage = setup_test_db();
+        storage.cache_event("s-1", "evt-poison", "code.exe", "lib.rs", "{\"k\":1}", "FOCUS").unwrap();
+        storage.cache_feedback("evt-1", "is_work").unwrap();
+        let id = storage.get_unsynced_events(1).unwrap()[0].id;
+
+        // 임계치 전까지는 큐에 남음
+        assert!(!storage.record_event_rejection(id, "HTTP 422", 2).unwrap());
+        assert_eq!(storage.count_unsynced_events().unwrap(), 1);
+
+        // 임계치 도달 -> dead-letter로 이동
+        assert!(storage.record_event_rejection(id, "HTTP 422", 2).unwrap());
+        assert_eq!(storage.count_unsynced_events().unwrap(), 0);
+        assert_eq!(storage.count_dead_letters().unwrap(), 1);
+
+        let feedback_id = storage.get_unsynced_feedbacks(1).unwrap()[0].id;
+        assert!(storage.record_feedback_rejection(feedback_id, "HTTP 400", 1).unwrap());
+        assert!(storage.get_unsynced_feedbacks(10).unwrap().is_empty());
+
+        let conn = storage.readers.get().unwrap();
+        let payload: String = conn
+            .query_row("SELECT payload FROM sync_dead_letter WHERE kind = 'event'", [], |row| row.get(0))
+            .unwrap();
+        assert!(payload.contains("evt-poison"));
+    }
+
+    #[test]
+    fn test_schedule_run_ledger_prevents_double_fire() {
+        let storage = setup_test_db();
+
+        assert!(storage.record_schedule_run("sch-1", 1000, "started").unwrap());
+        // 같은 회차는 다시 기록되지 않음
+        assert!(!storage.record_schedule_run("sch-1", 1000, "started").unwrap());
+        assert!(storage.is_schedule_run_recorded("sch-1", 1000).unwrap());
+        // 다음 회차는 별개
+        assert!(storage.record_schedule_run("sch-1", 87400, "missed").unwrap());
+
+        storage.prune_schedule_runs(2000).unwrap();
+        assert!(!storage.is_schedule_run_recorded("sch-1", 1000).unwrap());
+        assert!(storage.is_schedule_run_recorded("sch-1", 87400).unwrap());
+
+        assert_eq!(storage.load_schedule_last_evaluated().unwrap(), None);
+        storage.save_schedule_last_evaluated(5000).unwrap();
+        storage.save_schedule_last_evaluated(6000).unwrap();
+        assert_eq!(storage.load_schedule_last_evaluated().unwrap(), Some(6000));
+    }
+
+    #[test]
+    fn test_sync_tasks_applies_only_differences() {
+        let storage = setup_test_db();
+        let task = |id: &str, name: &str| LocalTask {
+            id: id.to_string(),
+            user_id: "user-1".to_string(),
+            task_name: name.to_string(),
+            description: None,
+            target_executable: None,
+            target_arguments: None,
+            status: "active".to_string(),
+            updated_at: Some("2026-01-01T00:00:00".to_string()),
+        };
+
+        // 1. 최초 반영 -> 변경 있음, 같은 목록 재반영 -> 변경 없음
+        assert!(storage.sync_tasks(vec![task("t-1", "A"), task("t-2", "B")]).unwrap());
+        assert!(!storage.sync_tasks(vec![task("t-1", "A"), task("t-2", "B")]).unwrap());
+
+        // 2. 수정 + 삭제 반영
+        assert!(storage.sync_tasks(vec![task("t-1", "A2")]).unwrap());
+        assert_eq!(storage.get_task_by_id("t-1").unwrap().unwrap().task_name, "A2");
+        assert!(storage.get_task_by_id("t-2").unwrap().is_none());
+
+        // 3. ETag 커서
+        assert_eq!(storage.get_sync_etag("tasks").unwrap(), None);
+        storage.save_sync_etag("tasks", "\"abc\"").unwrap();
+        assert_eq!(storage.get_sync_etag("tasks").unwrap().as_deref(), Some("\"abc\""));
+    }
+
+    #[test]
+    fn test_dirty_task_survives_down_sync_until_pushed() {
+        let storage = setup_test_db();
+        let server = LocalTask {
+            id: "t-1".to_string(),
+            user_id: "user-1".to_string(),
+            task_name: "Server".to_string(),
+            description: None,
+            target_executable: None,
+            target_arguments: None,
+            status: "pending".to_string(),
+            updated_at: Some("2026-01-01T00:00:00".to_string()),
+        };
+        storage.sync_tasks(vec![server.clone()]).unwrap();
+
+        // 1. 오프라인 수정 + 오프라인 생성 -> dirty
+        let edited = LocalTask { task_name: "Edited".to_string(), ..server.clone() };
+        storage.save_local_task(&edited).unwrap();
+        let offline = LocalTask { id: "t-2".to_string(), updated_at: None, ..edited.clone() };
+        storage.save_local_task(&offline).unwrap();
+        let dirty = storage.get_dirty_tasks().unwrap();
+        assert_eq!(dirty.len(), 2);
+        // 서버 버전은 로컬 수정으로 바뀌지 않음 (충돌 판단 기준)
+        assert_eq!(dirty[0].task.updated_at, server.updated_at);
+
+        // 2. Down-Sync가 dirty 행을 덮어쓰거나 지우지 않음
+        storage.sync_tasks(vec![server.clone()]).unwrap();
+        assert_eq!(storage.get_task_by_id("t-1").unwrap().unwrap().task_name, "Edited");
+        assert!(storage.get_task_by_id("t-2").unwrap().is_some());
+
+        // 3. 전송 이후 다시 수정된 행은 결과를 반영하지 않음, 그대로 보낸 행은 서버 버전으로 확정
+        let confirmed = LocalTask { updated_at: Some("2026-01-02T00:00:00".to_string()), ..edited.clone() };
+        assert!(!storage.apply_task_sync_result(&confirmed, dirty[0].local_updated_ms - 1).unwrap());
+        assert!(storage.apply_task_sync_result(&confirmed, dirty[0].local_updated_ms).unwrap());
+        assert_eq!(storage.get_dirty_tasks().unwrap().len(), 1);
+        assert_eq!(storage.get_task_by_id("t-1").unwrap().unwrap(), confirmed);
+
+        storage.record_task_conflict("t-1", TASK_CONFLICT_SERVER_WON, &edited, &server).unwrap();
+        assert_eq!(
+            storage.get_task_conflicts(10).unwrap(),
+            vec![("t-1".to_string(), TASK_CONFLICT_SERVER_WON.to_string())]
+        );
+    }
+
+    #[test]
+    fn test_enforce_retention_by_age_and_size() {
+        let storage = setup_test_db();
+        let vector = "x".repeat(400 * 1024);
+        for i in 0..5 {
+            storage.cache_event("s-1", &format!("evt-{}", i), "app.exe", "title", &vector, "FOCUS").unwrap();
+        }
+        storage.cache_feedback("evt-0", "is_work").unwrap();
+        storage
+            .writer
+            .execute(|conn| {
+                // 가장 오래된 이벤트/피드백은 기간 초과
+                conn.execute("UPDATE cached_events SET timestamp = 1000 WHERE client_event_id = 'evt-0'", [])?;
+                conn.execute("UPDATE cached_feedback SET timestamp = 1000", [])?;
+                Ok(())
+            })
+            .unwrap();
+
+        let retention = RetentionSettings { event_max_total_mb: 1, ..RetentionSettings::default() };
+        let now_s = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let report = storage.enforce_retention(&retention, now_s).unwrap();
+
+        // evt-0: 기간 초과, evt-1/evt-2: 최신 2개(800KB) 이후 용량 초과
+        assert_eq!(report.events_deleted, 3);
+        assert_eq!(report.feedback_deleted, 1);
+        let remaining: Vec<String> = storage.get_unsynced_events(10).unwrap().into_iter().map(|e| e.client_event_id).collect();
+        assert_eq!(remaining, vec!["evt-3", "evt-4"]);
+
+        // 삭제된 이벤트도 세션 요약 집계에는 남음
+        let rolled_up: i64 = storage
+            .readers
+            .get()
+            .unwrap()
+            .query_row("SELECT sample_count FROM session_event_rollup WHERE session_id = 's-1'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rolled_up, 3);
+
+        let usage = storage.get_table_usage().unwrap();
+        let events = usage.iter().find(|u| u.table == "cached_events").unwrap();
+        assert_eq!(events.rows, 2);
+        assert!(events.bytes >= 2 * 400 * 1024);
+        assert!(storage.get_db_size_bytes().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_wipe_all_data_keeps_token_only_for_pending_erasure() {
+        let storage = setup_test_db();
+        storage.cache_event("s-1", "evt-1", "app.exe", "title", "{}", "FOCUS").unwrap();
+        storage.cache_feedback("evt-1", "is_work").unwrap();
+        storage.save_auth_token("access", "refresh", "user@example.com", "user-1").unwrap();
+        storage.queue_erasure_request(1_000).unwrap();
+
+        let wiped = storage.wipe_all_data(true).unwrap();
+        let rows = |table: &str| wiped.iter().find(|w| w.table == table).map(|w| w.rows);
+        assert_eq!(rows("cached_events"), Some(1));
+        assert_eq!(rows("cached_feedback"), Some(1));
+        assert_eq!(rows("auth_token"), None);
+        assert!(storage.get_table_usage().unwrap().iter().all(|u| u.rows == 0
+            || u.table == "auth_token"
+            || u.table == "erasure_requests"));
+        assert!(storage.load_auth_token().unwrap().is_some());
+        assert!(storage.has_pending_erasure_request().unwrap());
+
+        storage.complete_erasure_request().unwrap();
+        assert!(storage.load_auth_token().unwrap().is_none());
+        assert!(!storage.has_pending_erasure_request().unwrap());
+    }
+
+    #[test]
+    fn test_session_pause_intervals() {
+        let storage = setup_test_db();
+
+        storage.start_session_pause("s-1", 1000).unwrap();
+        storage.finish_session_pause("s-1", 1300).unwrap();
+        storage.start_session_pause("s-1", 2000).unwrap();
+        storage.start_session_pause("s-2", 2100).unwrap();
+
+        let pauses = storage.get_session_pauses("s-1").unwrap();
+        assert_eq!(pauses, vec![
+            SessionPause { paused_at_s: 1000, resumed_at_s: Some(1300) },
+            SessionPause { paused_at_s: 2000, resumed_at_s: None },
+        ]);
+
+        // 순수 집중 시간 = 경과 시간 - (누적 + 진행 중 일시정지)
+        let info = ActiveSessionInfo {
+            session_id: "s-1".to_string(),
+            task_id: None,
+            start_time_s: 0,
+            intent: None,
+            scheduled_end_s: None,
+            paused_total_s: 300,
+            paused_at_s: Some(2000),
+            goal_duration_min: None,
+            goal_reached_s: None,
+        };
+        assert!(info.is_paused());
+        assert_eq!(info.paused_duration_s(2500), 800);
+        assert_eq!(info.net_elapsed_s(2500), 1700);
+    }
+
+    #[test]
+    fn test_open_read_only_does_not_migrate_or_write() {
+        let dir = std::env::temp_dir().join(format!("ff-readonly-{}", uuid::Uuid::new_v4()));
+        {
+            let storage = StorageManager::open_in_dir(&dir).unwrap();
+            storage.cache_event("s-1", "evt-1", "code.exe", "main rs", "{}", "FOCUS").unwrap();
+            storage.flush().unwrap();
+        }
+        fs::remove_file(dir.join("token.key")).unwrap();
+
+        // 조회는 가능, 쓰기는 에러, 토큰 키 파일은 만들지 않음
+        let storage = StorageManager::open_read_only(&dir).unwrap();
+        assert_eq!(storage.get_events_in_range(0, u64::MAX / 2).unwrap().len(), 1);
+        assert!(storage.save_user_settings(&UserSettings::default()).is_err());
+        assert!(!dir.join("token.key").exists());
+        // query_only를 풀어도 연결 자체가 읽기 전용
+        assert!(storage
+            .readers
+            .get()
+            .unwrap()
+            .execute_batch("PRAGMA query_only = 0; CREATE TABLE scratch (a INTEGER);")
+            .is_err());
+        drop(storage);
+
+        // 스키마 버전이 다르면 열지 않음
+        let conn = Connection::open(dir.join("local.db")).unwrap();
+        conn.pragma_update(None, "user_version", migrations::latest_version() + 1).unwrap();
+        drop(conn);
+        assert_eq!(StorageManager::open_read_only(&dir).err().unwrap().code(), "storage");
+
+        // DB가 없으면 NotFound (빈 파일을 만들지 않음)
+        let empty = dir.join("empty");
+        assert!(StorageManager::open_read_only(&empty).is_err());
+        assert!(!empty.join("local.db").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
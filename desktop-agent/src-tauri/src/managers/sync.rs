@@ -1,141 +1,787 @@
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::{AppHandle, Manager};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::Mutex as AsyncMutex;
 use tokio::time::sleep;
 use chrono::{DateTime, Utc}; // 날짜 변환용
 
-use crate::utils::api::{BackendCommunicator, FeedbackPayload};
-use crate::StorageManagerArcMutex;
+use crate::managers::storage::{
+    CachedEvent, DirtyTask, LocalTask, SessionOutboxItem, StorageManager, SESSION_OP_END, SESSION_OP_START,
+    TASK_CONFLICT_LOCAL_WON, TASK_CONFLICT_SERVER_WON,
+};
+use crate::utils::api::{
+    estimated_event_bytes, get_api_base_url, BackendCommunicator, DataErasureResponse, FeedbackPayload, Fetched,
+    SessionEndRequest, SessionStartRequest, SessionStartResponse, TaskPushResponse, UploadError,
+};
+use crate::utils::error::AppResult;
+use crate::{ActiveSessionInfo, StorageManagerArc};
+
+// 정상 동기화 주기 / 연속 실패 시 최대 대기 시간 (초)
+const SYNC_INTERVAL_SEC: u64 = 60;
+const MAX_BACKOFF_SEC: u64 = 30 * 60;
+
+// 같은 행이 단독으로 이 횟수만큼 거부되면 dead-letter로 격리
+const MAX_UPLOAD_REJECTIONS: u32 = 3;
+
+// Down-Sync 컬렉션 이름 (sync_cursors 키 / 'data-changed' 이벤트 페이로드)
+pub const COLLECTION_TASKS: &str = "tasks";
+pub const COLLECTION_SCHEDULES: &str = "schedules";
+
+// 이벤트 배치: 한 번에 조회할 최대 행 수 / 1회 업로드 최대 크기 (압축 전 직렬화 기준)
+const EVENT_FETCH_LIMIT: u32 = 500;
+const MAX_EVENT_BATCH_BYTES: usize = 512 * 1024;
+
+// 주기 동기화와 즉시 전송(flush)이 같은 작업을 중복 전송하지 않도록 직렬화
+static SESSION_OUTBOX_LOCK: AsyncMutex<()> = AsyncMutex::const_new(());
+
+/// UI 표시용 동기화 상태 ('sync-status' 이벤트)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncState {
+    Syncing,
+    Idle,
+    Backoff,
+    Offline,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatusPayload {
+    pub state: SyncState,
+    pub pending_events: u64,
+    pub dead_letter_count: u64,
+    pub consecutive_failures: u32,
+    pub next_retry_in_sec: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+/// 연속 실패 횟수 기반 지수 백오프 (equal jitter: [delay/2, delay])
+#[derive(Debug, Default)]
+pub struct SyncBackoff {
+    consecutive_failures: u32,
+}
+
+impl SyncBackoff {
+    /// 실패 1회 기록 후 다음 시도까지 대기 시간 반환. jitter: 0.0 ~ 1.0 난수
+    pub fn next_delay_s(&mut self, jitter: f64) -> u64 {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        let exp = SYNC_INTERVAL_SEC
+            .saturating_mul(1u64 << self.consecutive_failures.min(16))
+            .min(MAX_BACKOFF_SEC);
+        exp / 2 + ((exp / 2) as f64 * jitter.clamp(0.0, 1.0)) as u64
+    }
+
+    pub fn reset(&mut self) {
+        self.consecutive_failures = 0;
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+}
+
+// process_sync 1회 결과
+enum SyncRun {
+    Offline,
+    Done,
+}
 
 /// 백그라운드 동기화 루프 시작
 pub fn start_sync_loop(app_handle: AppHandle) {
     tauri::async_runtime::spawn(async move {
-        println!("Sync Manager: Started background sync loop (Interval: 60s)");
+        println!("Sync Manager: Started background sync loop (Interval: {}s)", SYNC_INTERVAL_SEC);
+
+        let mut backoff = SyncBackoff::default();
+        let mut delay_s = SYNC_INTERVAL_SEC;
 
         loop {
-            // 1분 대기 (60초)
-            sleep(Duration::from_secs(60)).await;
+            // 정상: 1분 대기, 실패 연속 시: 지수 백오프 대기
+            sleep(Duration::from_secs(delay_s)).await;
+
+            emit_sync_status(&app_handle, SyncState::Syncing, &backoff, None, None);
 
             // 동기화 작업 실행 (실패해도 로그만 남기고 루프 유지)
-            if let Err(e) = process_sync(&app_handle).await {
-                eprintln!("Sync Manager Error: {}", e);
+            match process_sync(&app_handle).await {
+                Ok(run) => {
+                    backoff.reset();
+                    delay_s = SYNC_INTERVAL_SEC;
+                    let state = match run {
+                        SyncRun::Offline => SyncState::Offline,
+                        SyncRun::Done => SyncState::Idle,
+                    };
+                    emit_sync_status(&app_handle, state, &backoff, None, None);
+                }
+                Err(e) => {
+                    delay_s = backoff.next_delay_s(random_jitter());
+                    eprintln!(
+                        "Sync Manager Error: {} (failure #{}, retry in {}s)",
+                        e,
+                        backoff.consecutive_failures(),
+                        delay_s
+                    );
+                    emit_sync_status(&app_handle, SyncState::Backoff, &backoff, Some(delay_s), Some(e));
+                }
             }
         }
     });
 }
 
+// 0.0 ~ 1.0 난수 (OS 난수를 못 읽으면 0 -> jitter 없이 최소 대기)
+fn random_jitter() -> f64 {
+    let mut bytes = [0u8; 4];
+    getrandom::getrandom(&mut bytes).ok();
+    u32::from_le_bytes(bytes) as f64 / u32::MAX as f64
+}
+
+fn emit_sync_status<R: Runtime>(
+    app: &AppHandle<R>,
+    state: SyncState,
+    backoff: &SyncBackoff,
+    next_retry_in_sec: Option<u64>,
+    last_error: Option<String>,
+) {
+    let (pending_events, dead_letter_count) = app
+        .try_state::<StorageManagerArc>()
+        .map(|storage| {
+            (
+                storage.count_unsynced_events().unwrap_or(0),
+                storage.count_dead_letters().unwrap_or(0),
+            )
+        })
+        .unwrap_or((0, 0));
+
+    let payload = SyncStatusPayload {
+        state,
+        pending_events,
+        dead_letter_count,
+        consecutive_failures: backoff.consecutive_failures(),
+        next_retry_in_sec,
+        last_error,
+    };
+    app.emit("sync-status", payload).ok();
+}
+
+/// 배치 업로드 결과
+struct BatchOutcome<T> {
+    accepted: Vec<T>,
+    rejected: Vec<(T, String)>,      // 단독으로 거부된 행 + 사유
+    transient_error: Option<String>, // 일시 오류로 중단된 경우
+}
+
+/// 배치 전송. 400/413/422로 거부되면 절반씩 나눠 재전송하여 문제 행만 격리 (일시 오류 시 즉시 중단)
+/// send: 배치 전송 후 서버가 수락을 확인한 항목 반환 (확인되지 않은 항목은 큐에 남아 다음 회차에 재전송)
+async fn upload_isolating_rejects<T, F, Fut>(items: Vec<T>, mut send: F) -> BatchOutcome<T>
+where
+    T: Clone,
+    F: FnMut(Vec<T>) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, UploadError>>,
+{
+    let mut outcome = BatchOutcome { accepted: Vec::new(), rejected: Vec::new(), transient_error: None };
+    let mut pending = vec![items];
+
+    while let Some(mut batch) = pending.pop() {
+        if batch.is_empty() {
+            continue;
+        }
+        match send(batch.clone()).await {
+            Ok(accepted) => outcome.accepted.extend(accepted),
+            Err(UploadError::Rejected(_)) if batch.len() > 1 => {
+                // 앞쪽 절반을 먼저 처리 (순서 유지)
+                let right = batch.split_off(batch.len() / 2);
+                pending.push(right);
+                pending.push(batch);
+            }
+            Err(UploadError::Rejected(e)) => outcome.rejected.push((batch.remove(0), e)),
+            Err(UploadError::Transient(e)) => {
+                outcome.transient_error = Some(e);
+                break;
+            }
+        }
+    }
+    outcome
+}
+
+/// 오래된 이벤트부터 크기 합이 max_bytes를 넘기 전까지 배치 구성 (첫 행은 크기와 무관하게 포함)
+fn take_batch_by_size(events: Vec<CachedEvent>, max_bytes: usize) -> Vec<CachedEvent> {
+    let mut total = 0usize;
+    let mut batch = Vec::new();
+    for event in events {
+        let size = estimated_event_bytes(&event);
+        if !batch.is_empty() && total + size > max_bytes {
+            break;
+        }
+        total += size;
+        batch.push(event);
+    }
+    batch
+}
+
+fn to_rfc3339(ts_s: u64) -> String {
+    DateTime::<Utc>::from_timestamp(ts_s as i64, 0)
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339()
+}
+
+/// 세션 시작을 Outbox에 기록 (로그인 여부와 무관, 오프라인 세션도 로그인 후 전송)
+pub fn enqueue_session_start(
+    storage: &StorageManager,
+    info: &ActiveSessionInfo,
+    goal_duration_min: u32,
+) -> AppResult<()> {
+    let request = SessionStartRequest {
+        client_session_id: info.session_id.clone(),
+        task_id: info.task_id.clone(),
+        goal_duration: goal_duration_min,
+        start_time: to_rfc3339(info.start_time_s),
+    };
+    let payload = serde_json::to_string(&request)?;
+    storage.enqueue_session_op(&info.session_id, SESSION_OP_START, &payload)
+}
+
+/// 세션 종료를 Outbox에 기록
+pub fn enqueue_session_end(
+    storage: &StorageManager,
+    session_id: &str,
+    end_time_s: u64,
+    user_evaluation_score: u8,
+    paused_duration: u64,
+) -> AppResult<()> {
+    let request = SessionEndRequest {
+        user_evaluation_score,
+        paused_duration,
+        end_time: to_rfc3339(end_time_s),
+        status: "completed".to_string(),
+    };
+    let payload = serde_json::to_string(&request)?;
+    storage.enqueue_session_op(session_id, SESSION_OP_END, &payload)
+}
+
+/// 세션 시작/종료 직후 Outbox 즉시 전송 시도 (실패분은 주기 동기화에서 재시도)
+/// Core Loop(일반 스레드)에서도 호출되므로 Tauri 런타임 사용
+pub fn flush_session_outbox<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = drain_session_outbox(&app).await {
+            eprintln!("Sync Manager: Session outbox flush failed: {}", e);
+        }
+    });
+}
+
+/// Outbox를 등록 순서대로 전송 (start -> end 순서 보장)
+async fn drain_session_outbox<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let _guard = SESSION_OUTBOX_LOCK.lock().await;
+
+    let storage = app
+        .try_state::<StorageManagerArc>()
+        .ok_or("StorageManager state not found in AppHandle")?;
+    let comm_state = app
+        .try_state::<Arc<BackendCommunicator>>()
+        .ok_or("BackendCommunicator state not found")?;
+
+    if comm_state.access_token(app).await?.is_none() {
+        return Ok(()); // 오프라인 모드: 로그인 후 전송
+    }
+
+    loop {
+        // 한 건씩 처리 (start 폐기 시 같은 세션의 end도 함께 지워지므로 매번 다시 조회)
+        let op = storage.get_pending_session_ops(1)?.into_iter().next();
+        let Some(op) = op else { return Ok(()) };
+
+        match send_session_op(app, &comm_state, &storage, &op).await {
+            Ok(()) => {
+                storage.delete_session_op(op.id)?;
+                println!("Sync Manager: Session {} '{}' synced.", op.local_session_id, op.op);
+            }
+            // 일시 오류(오프라인, 서버 장애)는 횟수와 관계없이 유지 -> 동기화 루프의 백오프에 따라 재시도
+            Err(UploadError::Transient(e)) => {
+                storage.mark_session_op_failed(op.id, &e)?;
+                return Err(format!("Session '{}' sync deferred: {}", op.op, e));
+            }
+            // 서버가 거부한 작업만 폐기 (재전송해도 같은 결과)
+            Err(UploadError::Rejected(e)) => {
+                eprintln!(
+                    "Sync Manager: Dropping session {} '{}' rejected after {} attempts: {}",
+                    op.local_session_id, op.op, op.attempts + 1, e
+                );
+                if op.op == SESSION_OP_START {
+                    storage.delete_session_ops_for(&op.local_session_id)?;
+                } else {
+                    storage.delete_session_op(op.id)?;
+                }
+            }
+        }
+    }
+}
+
+async fn send_session_op<R: Runtime>(
+    app: &AppHandle<R>,
+    comm_state: &BackendCommunicator,
+    storage: &StorageManager,
+    op: &SessionOutboxItem,
+) -> Result<(), UploadError> {
+    let response = match op.op.as_str() {
+        SESSION_OP_START => {
+            let body: SessionStartRequest = serde_json::from_str(&op.payload)
+                .map_err(|e| UploadError::Rejected(format!("Invalid payload: {}", e)))?;
+            let url = format!("{}/sessions/start", get_api_base_url());
+            comm_state
+                .send_authorized(app, |client, token| client.post(&url).bearer_auth(token).json(&body))
+                .await
+        }
+        SESSION_OP_END => {
+            let body: SessionEndRequest = serde_json::from_str(&op.payload)
+                .map_err(|e| UploadError::Rejected(format!("Invalid payload: {}", e)))?;
+            // start가 먼저 전송되어 있으므로 서버 ID 사용 (매핑이 없으면 서버가 client_session_id로 조회)
+            let server_id = storage
+                .get_server_session_id(&op.local_session_id)?
+                .unwrap_or_else(|| op.local_session_id.clone());
+            let url = format!("{}/sessions/{}", get_api_base_url(), server_id);
+            comm_state
+                .send_authorized(app, |client, token| client.put(&url).bearer_auth(token).json(&body))
+                .await
+        }
+        other => return Err(UploadError::Rejected(format!("Unknown session op '{}'", other))),
+    }?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(UploadError::from_status(status, &text));
+    }
+
+    if op.op == SESSION_OP_START {
+        let created: SessionStartResponse = response
+            .json()
+            .await
+            .map_err(|e| UploadError::Transient(format!("Session start JSON parse error: {}", e)))?;
+        storage
+            .save_session_id_mapping(&op.local_session_id, &created.session_id)?;
+    }
+    Ok(())
+}
+
+/// 서버 데이터 삭제 요청 전송 결과
+#[derive(Debug)]
+pub enum ErasureSend {
+    NotPending,
+    Sent(DataErasureResponse),
+    Dropped(String), // 인증 불가/거부되어 폐기
+}
+
+/// 대기 중인 서버 데이터 삭제 요청 전송 (delete_all_my_data 직후 + 주기 동기화)
+/// 전송되거나 폐기되면 남겨둔 토큰도 삭제. 일시 오류면 Err (다음 주기에 재시도)
+pub async fn send_pending_erasure<R: Runtime>(
+    app: &AppHandle<R>,
+    comm: &BackendCommunicator,
+    storage: &StorageManager,
+) -> Result<ErasureSend, String> {
+    if !storage.has_pending_erasure_request()? {
+        return Ok(ErasureSend::NotPending);
+    }
+
+    let outcome = match comm.access_token(app).await {
+        Ok(Some(_)) => comm.request_data_erasure(app).await,
+        Ok(None) => Err(UploadError::Rejected("Not logged in".to_string())),
+        Err(e) => Err(UploadError::from(e)),
+    };
+
+    match outcome {
+        Ok(response) => {
+            storage.complete_erasure_request()?;
+            println!("Sync Manager: Server data erasure completed {:?}", response.deleted);
+            Ok(ErasureSend::Sent(response))
+        }
+        Err(UploadError::Rejected(e)) => {
+            storage.complete_erasure_request()?;
+            eprintln!("Sync Manager: Dropping server erasure request: {}", e);
+            Ok(ErasureSend::Dropped(e))
+        }
+        Err(UploadError::Transient(e)) => {
+            storage.mark_erasure_request_failed(&e)?;
+            Err(format!("Server erasure request deferred: {}", e))
+        }
+    }
+}
+
+/// 로컬에서 생성/수정한 Task를 서버로 전송 (Last-Writer-Wins, 충돌은 task_conflicts에 기록)
+/// 서버 판정으로 로컬 데이터가 바뀌었으면 true
+async fn push_dirty_tasks<R: Runtime>(
+    app: &AppHandle<R>,
+    comm: &BackendCommunicator,
+    storage: &StorageManager,
+) -> Result<bool, String> {
+    let dirty = storage.get_dirty_tasks()?;
+    push_tasks_in_order(storage, dirty, |item| async move {
+        comm.push_task(app, &item.task, item.local_updated_ms)
+            .await
+            .map_err(UploadError::from)
+    })
+    .await
+}
+
+/// Task를 수정 순서대로 전송. 서버가 거부한 Task는 dead-letter로 옮기고 다음 Task로 진행
+async fn push_tasks_in_order<F, Fut>(storage: &StorageManager, dirty: Vec<DirtyTask>, mut send: F) -> Result<bool, String>
+where
+    F: FnMut(DirtyTask) -> Fut,
+    Fut: Future<Output = Result<TaskPushResponse, UploadError>>,
+{
+    let mut local_changed = false;
+    for item in dirty {
+        let result = match send(item.clone()).await {
+            Ok(result) => result,
+            Err(UploadError::Rejected(e)) => {
+                eprintln!("Sync Manager: Task {} rejected by server, moved to dead-letter: {}", item.task.id, e);
+                storage.dead_letter_rejected_task(&item, &e)?;
+                continue;
+            }
+            // 일시 오류면 중단 (나머지는 다음 주기에 순서대로 재시도)
+            Err(UploadError::Transient(e)) => return Err(e),
+        };
+        let server_task = LocalTask::from(result.task);
+
+        if result.conflict || !result.applied {
+            let resolution = if result.applied { TASK_CONFLICT_LOCAL_WON } else { TASK_CONFLICT_SERVER_WON };
+            storage.record_task_conflict(&item.task.id, resolution, &item.task, &server_task)?;
+            println!("Sync Manager: Task {} conflict resolved ({})", item.task.id, resolution);
+        }
+        if storage.apply_task_sync_result(&server_task, item.local_updated_ms)? && !result.applied {
+            local_changed = true; // 로컬 수정이 서버 버전으로 대체됨
+        }
+    }
+    Ok(local_changed)
+}
+
 /// 실제 동기화 로직 (1회 실행)
-async fn process_sync(app: &AppHandle) -> Result<(), String> {
+/// 단계별 실패는 모아서 마지막에 Err로 반환 (한 단계의 실패가 다른 단계를 막지 않음 -> 호출부에서 백오프)
+async fn process_sync(app: &AppHandle) -> Result<SyncRun, String> {
     // 1. LSN 상태 가져오기
-    let storage_state = app
-        .try_state::<StorageManagerArcMutex>()
+    let storage = app
+        .try_state::<StorageManagerArc>()
         .ok_or("StorageManager state not found in AppHandle")?;
 
     let comm_state = app
         .try_state::<Arc<BackendCommunicator>>()
         .ok_or("BackendCommunicator state not found")?;
+    let comm: &BackendCommunicator = &comm_state;
 
-    // 2. 토큰 확인 (로그인 여부)
-    let token = {
-        let storage = storage_state.lock().map_err(|e| e.to_string())?;
-        match storage.load_auth_token()? {
-            Some((access, _, _, _)) => access,
-            None => return Ok(()), // 토큰 없음 = 오프라인 모드 (동기화 전체 스킵)
-        }
-    }; // 여기서 storage Lock 해제
+    // 1-1. 대기 중인 서버 데이터 삭제 요청 (로컬은 이미 비워졌고 토큰은 전송용으로만 남아 있으므로 이것만 처리)
+    if !matches!(send_pending_erasure(app, comm, &storage).await?, ErasureSend::NotPending) {
+        return Ok(SyncRun::Offline);
+    }
+
+    // 2. 토큰 확인 (로그인 여부, 만료 임박 시 미리 갱신)
+    if comm.access_token(app).await?.is_none() {
+        return Ok(SyncRun::Offline); // 토큰 없음 = 오프라인 모드 (동기화 전체 스킵)
+    }
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // --- [0] Up-Sync: 세션 시작/종료 Outbox (이벤트보다 먼저, 서버 세션 ID 매핑 확보) ---
+    if let Err(e) = drain_session_outbox(app).await {
+        errors.push(e);
+    }
+
+    // --- [0-1] Up-Sync: 오프라인에서 생성/수정한 Task (Down-Sync보다 먼저, 서버 확정 버전을 받아오도록) ---
+    let mut tasks_changed_by_push = false;
+    match push_dirty_tasks(app, comm, &storage).await {
+        Ok(changed) => tasks_changed_by_push = changed,
+        Err(e) => errors.push(e),
+    }
 
     // --- [A] Down-Sync: 서버 데이터 가져오기 (스케줄 & 태스크) ---
+    // 마지막 ETag로 조건부 요청 -> 변경 없으면 304 (로컬 DB/프론트엔드 모두 그대로)
+    let tasks_etag = storage.get_sync_etag(COLLECTION_TASKS)?;
+    let schedules_etag = storage.get_sync_etag(COLLECTION_SCHEDULES)?;
+
     // 2-1. Task 다운로드
-    let fetched_tasks = match comm_state.fetch_tasks(&token).await {
-        Ok(t) => Some(t),
+    let fetched_tasks = match comm.fetch_tasks(app, tasks_etag.as_deref()).await {
+        Ok(t) => t,
         Err(e) => {
-            eprintln!("Sync Manager: Failed to fetch tasks: {}", e);
-            None
+            errors.push(format!("Failed to fetch tasks: {}", e));
+            Fetched::NotModified
         }
     };
 
     // 2-2. Schedule 다운로드
-    let fetched_schedules = match comm_state.fetch_schedules(&token).await {
-        Ok(s) => Some(s),
+    let fetched_schedules = match comm.fetch_schedules(app, schedules_etag.as_deref()).await {
+        Ok(s) => s,
         Err(e) => {
-            eprintln!("Sync Manager: Failed to fetch schedules: {}", e);
-            None
+            errors.push(format!("Failed to fetch schedules: {}", e));
+            Fetched::NotModified
         }
     };
 
-    // 3. 로컬 DB 저장 (Lock 필요)
-    {
-        let storage = storage_state.lock().map_err(|e| e.to_string())?;
-
-        if let Some(tasks) = fetched_tasks {
-            if let Err(e) = storage.sync_tasks(tasks) {
-                eprintln!("Sync Manager: Failed to sync tasks to DB: {}", e);
+    // 3. 로컬 DB 반영. 실제로 바뀐 컬렉션만 모아서 data-changed 알림
+    let mut changed_collections: Vec<&str> = Vec::new();
+    if tasks_changed_by_push {
+        changed_collections.push(COLLECTION_TASKS);
+    }
+    if let Fetched::Modified { items, etag } = fetched_tasks {
+        match storage.sync_tasks(items) {
+            Ok(changed) => {
+                if changed && !changed_collections.contains(&COLLECTION_TASKS) {
+                    changed_collections.push(COLLECTION_TASKS);
+                }
+                if let Some(etag) = etag {
+                    storage.save_sync_etag(COLLECTION_TASKS, &etag)?;
+                }
             }
+            Err(e) => eprintln!("Sync Manager: Failed to sync tasks to DB: {}", e),
         }
+    }
 
-        if let Some(schedules) = fetched_schedules {
-            if let Err(e) = storage.sync_schedules(schedules) {
-                eprintln!("Sync Manager: Failed to sync schedules to DB: {}", e);
+    if let Fetched::Modified { items, etag } = fetched_schedules {
+        match storage.sync_schedules(items) {
+            Ok(changed) => {
+                if changed {
+                    changed_collections.push(COLLECTION_SCHEDULES);
+                }
+                if let Some(etag) = etag {
+                    storage.save_sync_etag(COLLECTION_SCHEDULES, &etag)?;
+                }
             }
+            Err(e) => eprintln!("Sync Manager: Failed to sync schedules to DB: {}", e),
         }
-    } // lock 해제
+    }
+
+    if !changed_collections.is_empty() {
+        app.emit("data-changed", &changed_collections).ok();
+    }
 
     // --- [B] Up-Sync: 로컬 데이터 올리기 (이벤트) ---
-    // 4. 전송할 데이터 조회 (Lock)
-    let events = {
-        let storage = storage_state.lock().map_err(|e| e.to_string())?;
-        storage.get_unsynced_events(50)?
-    };
+    // 4. 전송할 데이터 조회
+    let events = take_batch_by_size(storage.get_unsynced_events(EVENT_FETCH_LIMIT)?, MAX_EVENT_BATCH_BYTES);
 
     if !events.is_empty() {
-        let event_ids: Vec<i64> = events.iter().map(|e| e.id).collect();
-        let count = event_ids.len();
+        // 5. 업로드 시도 표시 후 서버 전송 (Async). 거부된 배치는 분할하여 문제 행만 격리
+        let ids: Vec<i64> = events.iter().map(|e| e.id).collect();
+        storage.mark_events_upload_attempted(&ids)?;
+        let outcome = upload_isolating_rejects(events, |batch: Vec<CachedEvent>| async move {
+            let confirmed = comm.sync_events_batch(batch.clone(), app).await?;
+            Ok(batch.into_iter().filter(|e| confirmed.contains(&e.client_event_id)).collect())
+        })
+        .await;
 
-        // 5. 서버 전송 (Async, No Lock)
-        comm_state.sync_events_batch(events, &token).await?;
+        // 6. 전송 성공분 삭제 / 거부 행 기록
+        let accepted_ids: Vec<i64> = outcome.accepted.iter().map(|e| e.id).collect();
+        storage.delete_events_by_ids(&accepted_ids)?;
 
-        // 6. 전송 성공 시 삭제 (Lock)
-        {
-            let storage = storage_state.lock().map_err(|e| e.to_string())?;
-            storage.delete_events_by_ids(&event_ids)?;
+        for (event, reason) in &outcome.rejected {
+            if storage.record_event_rejection(event.id, reason, MAX_UPLOAD_REJECTIONS)? {
+                eprintln!("Sync Manager: Event {} moved to dead-letter: {}", event.client_event_id, reason);
+            }
+        }
+        if !accepted_ids.is_empty() {
+            println!("Sync Manager: Successfully uploaded {} events.", accepted_ids.len());
+        }
+        if let Some(e) = outcome.transient_error {
+            errors.push(format!("Event upload failed: {}", e));
         }
-        println!("Sync Manager: Successfully uploaded {} events.", count);
     }
 
     // --- [C] Up-Sync: 사용자 피드백  ---
 
     // 1. 미전송 데이터 조회 (최대 50개)
-    let feedbacks = {
-        let storage = storage_state.lock().map_err(|e| e.to_string())?;
-        storage.get_unsynced_feedbacks(50)?
-    };
+    let feedbacks = storage.get_unsynced_feedbacks(50)?;
 
     if !feedbacks.is_empty() {
-        let feedback_ids: Vec<i64> = feedbacks.iter().map(|f| f.id).collect();
-        let count = feedbacks.len();
-
-        // 2. [매핑] DB 구조체 -> API Payload 변환
-        let payloads: Vec<FeedbackPayload> = feedbacks.into_iter().map(|f| {
+        // 2. [매핑] DB 구조체 -> API Payload 변환 (로컬 id와 짝지어 보관)
+        let payloads: Vec<(i64, FeedbackPayload)> = feedbacks.into_iter().map(|f| {
              let dt = DateTime::<Utc>::from_timestamp(f.timestamp, 0).unwrap_or(Utc::now());
-             
-             FeedbackPayload {
+
+             (f.id, FeedbackPayload {
                 client_event_id: f.event_id,
                 feedback_type: f.feedback_type,
                 timestamp: dt.to_rfc3339(),
-            }
+            })
         }).collect();
 
         // 3. 서버 전송
-        comm_state.send_feedback_batch(payloads, &token).await?;
+        let outcome = upload_isolating_rejects(payloads, |batch: Vec<(i64, FeedbackPayload)>| async move {
+            comm.send_feedback_batch(batch.iter().map(|(_, p)| p.clone()).collect(), app).await?;
+            Ok(batch)
+        })
+        .await;
 
         // 4. 전송 성공 시 로컬 삭제 (Transactional Delete)
-        {
-            let storage = storage_state.lock().map_err(|e| e.to_string())?;
-            storage.delete_feedbacks_by_ids(&feedback_ids)?;
+        let accepted_ids: Vec<i64> = outcome.accepted.iter().map(|(id, _)| *id).collect();
+        storage.delete_feedbacks_by_ids(&accepted_ids)?;
+
+        for ((id, payload), reason) in &outcome.rejected {
+            if storage.record_feedback_rejection(*id, reason, MAX_UPLOAD_REJECTIONS)? {
+                eprintln!("Sync Manager: Feedback {} moved to dead-letter: {}", payload.client_event_id, reason);
+            }
+        }
+        if !accepted_ids.is_empty() {
+            println!("✅ Sync Manager: Uploaded and cleaned up {} feedbacks.", accepted_ids.len());
+        }
+        if let Some(e) = outcome.transient_error {
+            errors.push(format!("Feedback upload failed: {}", e));
         }
-        
-        println!("✅ Sync Manager: Uploaded and cleaned up {} feedbacks.", count);
     }
 
-    Ok(())
+    if errors.is_empty() {
+        Ok(SyncRun::Done)
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::api::{ApiTask, MAX_EVENT_BYTES};
+
+    #[test]
+    fn test_backoff_grows_with_jitter_and_caps() {
+        let mut backoff = SyncBackoff::default();
+        assert_eq!(backoff.next_delay_s(0.0), 60); // 1회: [60, 120]
+        assert_eq!(backoff.next_delay_s(1.0), 240); // 2회: [120, 240]
+        for _ in 0..20 {
+            backoff.next_delay_s(0.5);
+        }
+        assert!(backoff.next_delay_s(1.0) <= MAX_BACKOFF_SEC);
+
+        backoff.reset();
+        assert_eq!(backoff.consecutive_failures(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_upload_isolates_poison_row() {
+        // 3번 행이 포함된 배치는 항상 거부
+        let mut calls = 0;
+        let outcome = upload_isolating_rejects((1..=8).collect::<Vec<i32>>(), |batch| {
+            calls += 1;
+            async move {
+                if batch.contains(&3) {
+                    Err(UploadError::Rejected("HTTP 422".to_string()))
+                } else {
+                    Ok(batch)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(outcome.accepted, vec![1, 2, 4, 5, 6, 7, 8]);
+        assert_eq!(outcome.rejected, vec![(3, "HTTP 422".to_string())]);
+        assert!(outcome.transient_error.is_none());
+        assert!(calls < 8); // 행 단위 전송보다 적은 요청
+
+        // 일시 오류는 분할하지 않고 즉시 중단
+        let outcome = upload_isolating_rejects(vec![1, 2], |_| async {
+            Err(UploadError::Transient("timeout".to_string()))
+        })
+        .await;
+        assert!(outcome.accepted.is_empty() && outcome.rejected.is_empty());
+        assert_eq!(outcome.transient_error.as_deref(), Some("timeout"));
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_404_does_not_split_batch() {
+        // 경로 오류(프록시 404 등)는 데이터와 무관하므로 분할/격리 없이 다음 주기에 재시도
+        let not_found = UploadError::from_status(reqwest::StatusCode::NOT_FOUND, "Not Found");
+        assert!(matches!(not_found, UploadError::Transient(_)));
+        assert!(matches!(
+            UploadError::from_status(reqwest::StatusCode::UNPROCESSABLE_ENTITY, ""),
+            UploadError::Rejected(_)
+        ));
+
+        let mut calls = 0;
+        let outcome = upload_isolating_rejects((1..=8).collect::<Vec<i32>>(), |_| {
+            calls += 1;
+            let err = not_found.clone();
+            async move { Err(err) }
+        })
+        .await;
+
+        assert_eq!(calls, 1);
+        assert!(outcome.accepted.is_empty() && outcome.rejected.is_empty());
+        assert!(outcome.transient_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_task_does_not_block_later_tasks() {
+        let storage = StorageManager::new_in_memory().unwrap();
+        let task = |id: &str| LocalTask {
+            id: id.to_string(),
+            user_id: "user-1".to_string(),
+            task_name: format!("Task {}", id),
+            description: None,
+            target_executable: None,
+            target_arguments: None,
+            status: "pending".to_string(),
+            updated_at: None,
+        };
+        storage.save_local_task(&task("t-bad")).unwrap();
+        std::thread::sleep(Duration::from_millis(5)); // 수정 순서 (local_updated_ms) 보장
+        storage.save_local_task(&task("t-good")).unwrap();
+
+        let accept = |item: DirtyTask| TaskPushResponse {
+            applied: true,
+            conflict: false,
+            task: ApiTask {
+                id: item.task.id,
+                user_id: item.task.user_id,
+                name: item.task.task_name,
+                description: None,
+                status: item.task.status,
+                target_executable: None,
+                target_arguments: None,
+                updated_at: Some("2026-01-01T00:00:00".to_string()),
+            },
+        };
+
+        // 일시 오류는 중단하고 dirty 유지
+        let err = push_tasks_in_order(&storage, storage.get_dirty_tasks().unwrap(), |_| async {
+            Err(UploadError::Transient("timeout".to_string()))
+        })
+        .await;
+        assert_eq!(err, Err("timeout".to_string()));
+        assert_eq!(storage.get_dirty_tasks().unwrap().len(), 2);
+
+        // 앞의 Task가 거부되어도 뒤의 Task는 전송됨
+        let mut sent = Vec::new();
+        let changed = push_tasks_in_order(&storage, storage.get_dirty_tasks().unwrap(), |item| {
+            sent.push(item.task.id.clone());
+            let result = if item.task.id == "t-bad" {
+                Err(UploadError::Rejected("HTTP 422".to_string()))
+            } else {
+                Ok(accept(item))
+            };
+            async move { result }
+        })
+        .await
+        .unwrap();
+
+        assert!(!changed);
+        assert_eq!(sent, vec!["t-bad".to_string(), "t-good".to_string()]);
+        assert!(storage.get_dirty_tasks().unwrap().is_empty());
+        assert_eq!(storage.count_dead_letters().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_take_batch_by_size() {
+        let event = |id: i64, vector_bytes: usize| CachedEvent {
+            id,
+            session_id: "s".to_string(),
+            client_event_id: format!("evt-{}", id),
+            timestamp: 0,
+            app_name: "app.exe".to_string(),
+            window_title: "title".to_string(),
+            activity_vector: "x".repeat(vector_bytes),
+        };
+
+        // 크기 합이 한도를 넘기 전까지만 (순서 유지)
+        let events: Vec<CachedEvent> = (1..=20).map(|i| event(i, 50_000)).collect();
+        let ids: Vec<i64> = take_batch_by_size(events, MAX_EVENT_BATCH_BYTES).iter().map(|e| e.id).collect();
+        assert_eq!(ids, (1..=10).collect::<Vec<i64>>());
+
+        // 한도를 넘는 단일 이벤트도 단독 배치로 전송
+        let batch = take_batch_by_size(vec![event(1, 10_000), event(2, 10)], 1_000);
+        assert_eq!(batch.len(), 1);
+
+        // 잘라낼 이벤트는 잘린 크기(MAX_EVENT_BYTES)로 계산
+        assert!(estimated_event_bytes(&event(1, 10_000_000)) < MAX_EVENT_BYTES + 1_000);
+    }
 }
@@ -0,0 +1,275 @@
+// 인증 토큰 암호화(Token Vault) 모듈
+// LSN(auth_token 테이블)에 저장되는 토큰을 AES-256-GCM(AEAD)으로 암호화한다.
+// 키는 설치마다 무작위로 생성하여 DB 밖(app_data_dir/token.key 또는 OS 키링)에 보관.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 암호문 접두사 (버전 포함, 이후 알고리즘 교체 시 구분용)
+const CIPHERTEXT_PREFIX: &str = "v1:";
+const KEY_FILE_NAME: &str = "token.key";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+// xor_obfuscate 시절 바이너리에 하드코딩되어 있던 키 (마이그레이션 전용)
+const LEGACY_XOR_KEY: &[u8] = b"force-focus-secret-key-2026-secure-vault";
+
+#[cfg(all(target_os = "linux", feature = "secret-service"))]
+const KEYRING_SERVICE: &str = "force-focus";
+#[cfg(all(target_os = "linux", feature = "secret-service"))]
+const KEYRING_USER: &str = "token-encryption-key";
+
+/// 키 보관 위치
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeySource {
+    File,
+    Keyring,
+    Ephemeral,
+}
+
+pub struct TokenVault {
+    cipher: Aes256Gcm,
+    source: KeySource,
+}
+
+impl TokenVault {
+    /// 설치별 키 로드 (없으면 생성)
+    /// secret-service 기능이 켜진 Linux에서는 Secret Service 키링을 우선 사용하고, 실패 시 키 파일로 대체
+    pub fn load_or_create(app_dir: &Path) -> Result<Self, String> {
+        #[cfg(all(target_os = "linux", feature = "secret-service"))]
+        match Self::load_or_create_keyring_key(app_dir) {
+            Ok(key) => return Ok(Self::from_key(&key, KeySource::Keyring)),
+            Err(e) => eprintln!("TokenVault: Secret Service unavailable ({}). Falling back to key file.", e),
+        }
+
+        let key = Self::load_or_create_key_file(&app_dir.join(KEY_FILE_NAME))?;
+        Ok(Self::from_key(&key, KeySource::File))
+    }
+
+    /// 메모리에만 존재하는 무작위 키 (테스트, 읽기 전용 DB: 저장된 토큰은 복호화되지 않음)
+    pub(crate) fn ephemeral() -> Self {
+        let key = Aes256Gcm::generate_key(OsRng);
+        Self::from_key(&key, KeySource::Ephemeral)
+    }
+
+    fn from_key(key: &[u8], source: KeySource) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+            source,
+        }
+    }
+
+    pub fn source(&self) -> KeySource {
+        self.source
+    }
+
+    /// 평문 -> "v1:" + hex(nonce || ciphertext+tag)
+    /// aad: 저장 컬럼 이름 (access/refresh 토큰을 서로 바꿔치기하면 복호화 실패)
+    pub fn encrypt(&self, plaintext: &str, aad: &str) -> Result<String, String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, Payload { msg: plaintext.as_bytes(), aad: aad.as_bytes() })
+            .map_err(|_| "Token encryption failed".to_string())?;
+
+        let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", CIPHERTEXT_PREFIX, to_hex(&out)))
+    }
+
+    /// 암호문 복호화 (키 불일치 / 변조 / aad 불일치 시 Err)
+    pub fn decrypt(&self, stored: &str, aad: &str) -> Result<String, String> {
+        let encoded = stored
+            .strip_prefix(CIPHERTEXT_PREFIX)
+            .ok_or("Not an encrypted token")?;
+        let bytes = from_hex(encoded).ok_or("Malformed encrypted token")?;
+        if bytes.len() <= NONCE_LEN {
+            return Err("Malformed encrypted token".to_string());
+        }
+
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: aad.as_bytes() })
+            .map_err(|_| "Token decryption failed (wrong key or tampered data)".to_string())?;
+
+        String::from_utf8(plaintext).map_err(|e| e.to_string())
+    }
+
+    pub fn is_encrypted(stored: &str) -> bool {
+        stored.starts_with(CIPHERTEXT_PREFIX)
+    }
+
+    /// 키 파일 로드/생성 (Unix: 소유자만 읽기/쓰기 0600)
+    /// 손상된 키 파일은 백업해 두고 새 키를 생성 (기존 토큰은 복호화 실패 -> 재로그인 필요)
+    fn load_or_create_key_file(path: &Path) -> Result<Vec<u8>, String> {
+        if path.exists() {
+            let content = fs::read(path).map_err(|e| format!("Failed to read token key file: {}", e))?;
+            let key = std::str::from_utf8(&content).ok().and_then(|hex| from_hex(hex.trim()));
+            match key {
+                Some(key) if key.len() == KEY_LEN => return Ok(key),
+                _ => {
+                    let backup = Self::backup_corrupt_key_file(path)?;
+                    eprintln!(
+                        "TokenVault: Token key file is corrupted (moved to {}). Generating a new key; login is required again.",
+                        backup.display()
+                    );
+                }
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create app directory: {}", e))?;
+        }
+
+        let key = Aes256Gcm::generate_key(OsRng).to_vec();
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(path)
+            .map_err(|e| format!("Failed to create token key file: {}", e))?;
+        file.write_all(to_hex(&key).as_bytes())
+            .map_err(|e| format!("Failed to write token key file: {}", e))?;
+
+        println!("TokenVault: Generated new per-install token key.");
+        Ok(key)
+    }
+
+    /// 손상된 키 파일을 token.key.corrupt-<unix초>로 이동
+    fn backup_corrupt_key_file(path: &Path) -> Result<PathBuf, String> {
+        let now_s = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let backup = path.with_file_name(format!("{}.corrupt-{}", KEY_FILE_NAME, now_s));
+        fs::rename(path, &backup).map_err(|e| format!("Failed to back up corrupted token key file: {}", e))?;
+        Ok(backup)
+    }
+
+    /// Secret Service 키링에서 키 로드/생성
+    /// 키 파일이 이미 있으면 그 키를 키링으로 옮겨 기존 암호문을 그대로 복호화할 수 있게 함
+    #[cfg(all(target_os = "linux", feature = "secret-service"))]
+    fn load_or_create_keyring_key(app_dir: &Path) -> Result<Vec<u8>, String> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(|e| e.to_string())?;
+
+        match entry.get_secret() {
+            Ok(key) if key.len() == KEY_LEN => Ok(key),
+            Ok(_) => Err("Keyring entry has an invalid key length".to_string()),
+            Err(keyring::Error::NoEntry) => {
+                let key_file = app_dir.join(KEY_FILE_NAME);
+                let key = if key_file.exists() {
+                    Self::load_or_create_key_file(&key_file)?
+                } else {
+                    Aes256Gcm::generate_key(OsRng).to_vec()
+                };
+                entry.set_secret(&key).map_err(|e| e.to_string())?;
+                println!("TokenVault: Stored token key in Secret Service keyring.");
+                Ok(key)
+            }
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// 구버전(xor_obfuscate) hex 문자열 여부 (평문 토큰과 구분)
+pub fn is_legacy_xor(stored: &str) -> bool {
+    !stored.is_empty() && stored.len().is_multiple_of(2) && stored.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// 구버전 hex-XOR 토큰 복원 (마이그레이션 전용)
+pub fn legacy_xor_deobfuscate(stored: &str) -> Option<String> {
+    let bytes = from_hex(stored)?;
+    let original: Vec<u8> = bytes
+        .into_iter()
+        .enumerate()
+        .map(|(i, b)| b ^ LEGACY_XOR_KEY[i % LEGACY_XOR_KEY.len()])
+        .collect();
+    String::from_utf8(original).ok()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| s.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip_and_tamper_detection() {
+        let vault = TokenVault::ephemeral();
+        let stored = vault.encrypt("access-token-value", "access_token").unwrap();
+
+        assert!(TokenVault::is_encrypted(&stored));
+        assert!(!stored.contains("access-token-value"));
+        assert_eq!(vault.decrypt(&stored, "access_token").unwrap(), "access-token-value");
+
+        // 다른 컬럼(aad)으로 복호화 시도 -> 실패
+        assert!(vault.decrypt(&stored, "refresh_token").is_err());
+
+        // 암호문 1바이트 변조 -> 실패
+        let mut tampered = stored.clone().into_bytes();
+        let last = tampered.len() - 1;
+        tampered[last] = if tampered[last] == b'0' { b'1' } else { b'0' };
+        assert!(vault.decrypt(&String::from_utf8(tampered).unwrap(), "access_token").is_err());
+
+        // 다른 설치의 키 -> 실패
+        assert!(TokenVault::ephemeral().decrypt(&stored, "access_token").is_err());
+    }
+
+    #[test]
+    fn test_key_file_is_reused() {
+        let dir = std::env::temp_dir().join(format!("ff-vault-{}", uuid::Uuid::new_v4()));
+        let first = TokenVault::load_or_create(&dir).unwrap();
+        let stored = first.encrypt("refresh", "refresh_token").unwrap();
+
+        // 재시작 후 같은 키 파일로 복호화 가능
+        let second = TokenVault::load_or_create(&dir).unwrap();
+        assert_eq!(second.decrypt(&stored, "refresh_token").unwrap(), "refresh");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_corrupted_key_file_is_backed_up_and_replaced() {
+        let dir = std::env::temp_dir().join(format!("ff-vault-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(KEY_FILE_NAME), "0123abcd").unwrap(); // 잘린 키
+
+        let vault = TokenVault::load_or_create(&dir).unwrap();
+        let stored = vault.encrypt("access", "access_token").unwrap();
+
+        // 새 키가 저장되어 재시작 후에도 사용됨
+        let reopened = TokenVault::load_or_create(&dir).unwrap();
+        assert_eq!(reopened.decrypt(&stored, "access_token").unwrap(), "access");
+
+        // 손상된 파일은 백업으로 남음
+        let backups: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("token.key.corrupt-"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(fs::read_to_string(backups[0].path()).unwrap(), "0123abcd");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
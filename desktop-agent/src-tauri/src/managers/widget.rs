@@ -77,7 +77,7 @@ pub fn setup_widget_listeners<R: Runtime>(
 }
 
 /// [추가] '위젯'을 띄우는 'Get-or-Create' 헬퍼 함수
-fn show_widget_window<R: Runtime>(app_handle: &AppHandle<R>) {
+pub(crate) fn show_widget_window<R: Runtime>(app_handle: &AppHandle<R>) {
     if let Some(widget_window) = app_handle.get_webview_window("widget") {
         // [개선] 이미 보인다면 show() 호출 안 함 (포커스 뺏기 방지)
         if !widget_window.is_visible().unwrap_or(false) {
@@ -1,19 +1,31 @@
 // 파일 위치: src-tauri/src/utils/api.rs
 // backend_comm.rs에서 분리된 순수 네트워크 코어 (U-3 해결)
 
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex as AsyncMutex;
 use futures_util::StreamExt;
 use anyhow::Result;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use chrono::{DateTime, SecondsFormat, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::Write;
 
 use dotenv::dotenv;
 use std::env;
 
 use crate::managers::storage::{CachedEvent, LocalSchedule, LocalTask};
+use crate::utils::error::{AppError, AppResult};
+use crate::StorageManagerArc;
 
 // --- 1. 상수 정의 ---
 
@@ -28,8 +40,39 @@ pub fn get_api_base_url() -> String {
     env::var("API_BASE_URL").unwrap_or_else(|_| "http://127.0.0.1:8000/api/v1".to_string())
 }
 
+// Access Token 만료 몇 초 전부터 미리 갱신할지
+const TOKEN_REFRESH_LEEWAY_SEC: u64 = 60;
+
+// Refresh Token까지 거부되어 재로그인이 필요한 경우의 에러 메시지
+pub const AUTH_EXPIRED_ERROR: &str = "Authentication expired. Please log in again.";
+
 // --- 2. API 요청/응답 구조체 (DTO) ---
 
+#[derive(Debug, Serialize)]
+pub struct RefreshTokenRequest<'a> {
+    pub refresh_token: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TokenPairResponse {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoginCodeExchangeRequest<'a> {
+    pub code: &'a str,
+    pub code_verifier: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoginTokenResponse {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub email: String,
+    pub user_id: String,
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct FeedbackPayload {
     pub client_event_id: String,
@@ -37,21 +80,29 @@ pub struct FeedbackPayload {
     pub timestamp: String,
 }
 
-#[derive(Debug, Serialize)]
-pub struct SessionStartRequest<'a> {
-    pub task_id: Option<&'a str>,
+// session_outbox에 JSON으로 보관되었다가 전송되므로 Deserialize도 필요
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionStartRequest {
+    #[serde(rename = "id")]
+    pub client_session_id: String, // 로컬 세션 ID (서버 client_session_id로 매핑)
+    pub task_id: Option<String>,
     pub goal_duration: u32,
+    pub start_time: String, // RFC3339 (오프라인 세션도 실제 시작 시각으로 기록)
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SessionStartResponse {
+    #[serde(alias = "id")]
     pub session_id: String,
     pub start_time: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SessionEndRequest {
     pub user_evaluation_score: u8,
+    pub paused_duration: u64, // 일시정지 누적 시간 (초)
+    pub end_time: String,     // RFC3339 (지연 전송되어도 실제 종료 시각 유지)
+    pub status: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -69,6 +120,12 @@ pub struct EventData {
     pub activity_vector: serde_json::Value,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct EventBatchResponse {
+    pub count: Option<usize>,
+    pub accepted_ids: Option<Vec<String>>, // 저장되었거나 이미 저장되어 있던 client_event_id
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ApiTask {
     pub id: String,
@@ -78,6 +135,43 @@ pub struct ApiTask {
     pub status: String,
     pub target_executable: Option<String>,
     pub target_arguments: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+impl From<ApiTask> for LocalTask {
+    fn from(t: ApiTask) -> Self {
+        LocalTask {
+            id: t.id, user_id: t.user_id, task_name: t.name, description: t.description,
+            target_executable: t.target_executable, target_arguments: t.target_arguments, status: t.status,
+            updated_at: t.updated_at,
+        }
+    }
+}
+
+// 로컬에서 수정한 Task 업로드 (PUT /desktop/data/tasks/{id}, 없으면 생성)
+#[derive(Debug, Serialize)]
+pub struct TaskPushRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub target_executable: Option<String>,
+    pub target_arguments: Option<String>,
+    pub status: String,
+    pub client_updated_at: String,       // 로컬 수정 시각 (RFC3339, Last-Writer-Wins 비교)
+    pub base_updated_at: Option<String>, // 수정 전 서버 버전 (다르면 서버에서도 수정된 것 = 충돌)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TaskPushResponse {
+    pub applied: bool,  // false: 서버 쪽 수정이 더 최근이라 로컬 수정이 버려짐
+    pub conflict: bool, // 마지막 동기화 이후 서버에서도 수정됨
+    pub task: ApiTask,  // 확정된 서버 버전
+}
+
+/// 서버 데이터 삭제 결과 (컬렉션별 삭제 문서 수)
+#[derive(Debug, Clone, Deserialize)]
+pub struct DataErasureResponse {
+    pub deleted: HashMap<String, u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -106,31 +200,323 @@ pub struct ModelDownloadUrls {
     pub scaler: String,
 }
 
+/// 업로드 실패 유형 (sync 루프의 재시도/격리 판단용)
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadError {
+    Transient(String), // 네트워크 오류, 5xx, 인증/권한/경로 오류(401/403/404 등): 같은 데이터로 나중에 재시도
+    Rejected(String),  // 400/413/422: 데이터 자체가 거부됨 (배치 분할로 원인 행 격리)
+}
+
+impl UploadError {
+    pub fn from_status(status: StatusCode, body: &str) -> Self {
+        let message = format!("Server returned error {}: {}", status, body);
+        if is_payload_rejection(status.as_u16()) {
+            UploadError::Rejected(message)
+        } else {
+            UploadError::Transient(message)
+        }
+    }
+}
+
+// 요청 본문 때문에 거부된 경우만 행 단위 격리 대상.
+// 엔드포인트/권한 오류는 모든 행에 똑같이 실패하므로 분할하거나 dead-letter로 보내지 않음
+fn is_payload_rejection(status: u16) -> bool {
+    matches!(status, 400 | 413 | 422)
+}
+
+// 공통 에러 -> 업로드 재시도 판단 (요청 자체가 잘못된 경우만 Rejected)
+impl From<AppError> for UploadError {
+    fn from(e: AppError) -> Self {
+        match e {
+            AppError::Server { status, message } if is_payload_rejection(status) => UploadError::Rejected(message),
+            AppError::InvalidInput(message) => UploadError::Rejected(message),
+            other => UploadError::Transient(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for UploadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UploadError::Transient(e) | UploadError::Rejected(e) => f.write_str(e),
+        }
+    }
+}
+
+/// 조건부 GET(If-None-Match) 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fetched<T> {
+    NotModified,                                   // 304: 마지막 동기화 이후 변경 없음
+    Modified { items: Vec<T>, etag: Option<String> }, // 200: 전체 목록 + 새 ETag
+}
+
 // --- 3. BackendCommunicator ---
 
 pub struct BackendCommunicator {
     pub client: Client,
+    // 여러 루프가 동시에 401을 받아도 토큰 갱신 요청은 한 번만 수행
+    refresh_lock: AsyncMutex<()>,
+}
+
+/// JWT payload의 exp (Unix timestamp, 초)
+/// 서명은 검증하지 않음 (검증은 서버 몫, 여기서는 갱신 시점 판단용)
+pub fn jwt_expiry_s(token: &str) -> Option<u64> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    claims.get("exp")?.as_u64()
+}
+
+// 이벤트 1건의 activity_vector 최대 크기 (직렬화 기준, 초과 시 visible_windows부터 잘라서 전송)
+pub const MAX_EVENT_BYTES: usize = 64 * 1024;
+// 창 제목 최대 길이 (문자 수)
+const MAX_TITLE_CHARS: usize = 512;
+
+/// 업로드 시 이벤트 1건의 대략적인 직렬화 크기 (잘라낸 뒤 기준, 배치 구성용)
+pub fn estimated_event_bytes(e: &CachedEvent) -> usize {
+    const FIELD_OVERHEAD: usize = 128; // 키 이름, 따옴표, timestamp 등
+    e.activity_vector.len().min(MAX_EVENT_BYTES)
+        + e.window_title.len().min(MAX_TITLE_CHARS * 4)
+        + e.app_name.len()
+        + e.session_id.len()
+        + e.client_event_id.len()
+        + FIELD_OVERHEAD
+}
+
+/// activity_vector를 max_bytes 이하로 축소. 바뀌었으면 true
+/// 1) 창 제목 자르기 2) visible_windows 뒤쪽부터 제거 (제거 개수 기록) 3) 그래도 크면 숫자 필드만 남김
+pub fn fit_activity_vector(value: &mut serde_json::Value, max_bytes: usize) -> bool {
+    let size = |v: &serde_json::Value| serde_json::to_vec(v).map(|b| b.len()).unwrap_or(usize::MAX);
+    if size(value) <= max_bytes {
+        return false;
+    }
+
+    if let Some(windows) = value.get_mut("visible_windows").and_then(|w| w.as_array_mut()) {
+        for window in windows.iter_mut() {
+            if let Some(title) = window.get_mut("title") {
+                if let Some(s) = title.as_str() {
+                    *title = serde_json::Value::String(truncate_chars(s, MAX_TITLE_CHARS));
+                }
+            }
+        }
+    }
+    if size(value) <= max_bytes {
+        return true;
+    }
+
+    let mut dropped = 0usize;
+    while size(value) > max_bytes {
+        let windows = match value.get_mut("visible_windows").and_then(|w| w.as_array_mut()) {
+            Some(w) if !w.is_empty() => w,
+            _ => break,
+        };
+        windows.pop();
+        dropped += 1;
+        value["visible_windows_truncated"] = serde_json::Value::from(dropped);
+    }
+    if size(value) <= max_bytes {
+        return true;
+    }
+
+    let numeric: serde_json::Map<String, serde_json::Value> = value
+        .as_object()
+        .map(|o| o.iter().filter(|(_, v)| v.is_number()).map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default();
+    *value = serde_json::Value::Object(numeric);
+    value["truncated"] = serde_json::Value::Bool(true);
+    true
+}
+
+fn truncate_chars(s: &str, max_chars: usize) -> String {
+    match s.char_indices().nth(max_chars) {
+        Some((idx, _)) => s[..idx].to_string(),
+        None => s.to_string(),
+    }
+}
+
+/// JSON 직렬화 후 gzip 압축 (Content-Encoding: gzip 요청 본문)
+pub fn gzip_json<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_vec(value).map_err(|e| format!("JSON serialize error: {}", e))?;
+    let mut encoder = GzEncoder::new(Vec::with_capacity(json.len() / 4), Compression::default());
+    encoder.write_all(&json).map_err(|e| format!("Gzip error: {}", e))?;
+    encoder.finish().map_err(|e| format!("Gzip error: {}", e))
+}
+
+/// 배치 멱등성 키: hex(sha256(client_event_id들을 '\n'으로 연결))
+pub fn batch_idempotency_key(client_event_ids: &[String]) -> String {
+    let digest = Sha256::digest(client_event_ids.join("\n").as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn not_logged_in() -> AppError {
+    AppError::Unauthorized("Not logged in".to_string())
+}
+
+fn now_s() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// LSN에서 (access, refresh) 토큰 쌍 로드. 로그인 안 됨 = None
+fn load_token_pair<R: Runtime>(app: &AppHandle<R>) -> AppResult<Option<(String, String)>> {
+    let storage = app
+        .try_state::<StorageManagerArc>()
+        .ok_or(AppError::Internal("StorageManager state not found".to_string()))?;
+    Ok(storage.load_auth_token()?.map(|(access, refresh, _, _)| (access, refresh)))
 }
 
 impl BackendCommunicator {
     pub fn new() -> Self {
-        Self { client: Client::new() }
+        Self {
+            client: Client::new(),
+            refresh_lock: AsyncMutex::new(()),
+        }
+    }
+
+    /// 사용할 Access Token 반환 (만료 임박 시 미리 갱신). 로그인 안 됨 = Ok(None)
+    pub async fn access_token<R: Runtime>(&self, app: &AppHandle<R>) -> AppResult<Option<String>> {
+        let (access, _) = match load_token_pair(app)? {
+            Some(pair) => pair,
+            None => return Ok(None),
+        };
+
+        match jwt_expiry_s(&access) {
+            Some(exp) if exp <= now_s() + TOKEN_REFRESH_LEEWAY_SEC => {
+                println!("Auth: Access token expires soon. Refreshing ahead of time.");
+                self.refresh_tokens(app, &access).await.map(Some)
+            }
+            _ => Ok(Some(access)),
+        }
+    }
+
+    /// 인증이 필요한 요청 전송
+    /// build: (client, access_token) -> RequestBuilder. 401 응답 시 토큰 갱신 후 한 번 재시도
+    pub async fn send_authorized<R, F>(&self, app: &AppHandle<R>, build: F) -> AppResult<Response>
+    where
+        R: Runtime,
+        F: Fn(&Client, &str) -> RequestBuilder,
+    {
+        let token = self.access_token(app).await?.ok_or_else(not_logged_in)?;
+        let response = build(&self.client, &token)
+            .send()
+            .await
+            .map_err(|e| AppError::from(e).context("Request failed"))?;
+
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        println!("Auth: Received 401. Refreshing access token and retrying.");
+        let new_token = self.refresh_tokens(app, &token).await?;
+        build(&self.client, &new_token)
+            .send()
+            .await
+            .map_err(|e| AppError::from(e).context("Request failed"))
+    }
+
+    /// refresh_token으로 새 토큰 쌍을 받아 LSN에 저장하고 새 Access Token 반환
+    /// stale_access: 만료(거부)된 Access Token. 대기 중 다른 작업이 이미 갱신했다면 그 토큰을 그대로 사용
+    /// Refresh Token이 만료/거부되면 토큰을 삭제하고 'auth-expired' 이벤트 방송
+    async fn refresh_tokens<R: Runtime>(&self, app: &AppHandle<R>, stale_access: &str) -> AppResult<String> {
+        let _guard = self.refresh_lock.lock().await;
+
+        let (access, refresh) = load_token_pair(app)?.ok_or_else(not_logged_in)?;
+        if access != stale_access {
+            return Ok(access);
+        }
+
+        if jwt_expiry_s(&refresh).is_some_and(|exp| exp <= now_s()) {
+            return Err(self.expire_auth(app, "refresh token expired"));
+        }
+
+        let url = format!("{}/auth/desktop/refresh", get_api_base_url());
+        // 네트워크 오류는 인증 만료가 아님 (오프라인) -> 토큰 유지
+        let response = self
+            .client
+            .post(&url)
+            .json(&RefreshTokenRequest { refresh_token: &refresh })
+            .send()
+            .await
+            .map_err(|e| AppError::from(e).context("Token refresh request failed"))?;
+
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(self.expire_auth(app, "refresh token rejected by server"));
+        }
+        if !status.is_success() {
+            return Err(AppError::from_status(status, "").context("Token refresh failed"));
+        }
+
+        let pair: TokenPairResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::Internal(format!("Token refresh JSON parse error: {}", e)))?;
+
+        let storage = app
+            .try_state::<StorageManagerArc>()
+            .ok_or(AppError::Internal("StorageManager state not found".to_string()))?;
+        let (_, _, email, user_id) = storage.load_auth_token()?.ok_or_else(not_logged_in)?;
+        storage.save_auth_token(&pair.access_token, &pair.refresh_token, &email, &user_id)?;
+
+        println!("Auth: Access token refreshed.");
+        Ok(pair.access_token)
     }
 
-    pub async fn check_latest_model_version(&self, token: &str) -> Result<ModelVersionResponse> {
+    /// 딥 링크로 받은 1회용 로그인 code를 PKCE code_verifier와 함께 토큰으로 교환
+    pub async fn exchange_login_code(&self, code: &str, code_verifier: &str) -> AppResult<LoginTokenResponse> {
+        let url = format!("{}/auth/desktop/token", get_api_base_url());
+        let response = self
+            .client
+            .post(&url)
+            .json(&LoginCodeExchangeRequest { code, code_verifier })
+            .send()
+            .await
+            .map_err(|e| AppError::from(e).context("Login code exchange request failed"))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Unauthorized(format!("Login code exchange rejected: {}", response.status())));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Internal(format!("Login code exchange JSON parse error: {}", e)))
+    }
+
+    /// 재로그인 필요: 로컬 토큰 삭제 + UI에 'auth-expired' 방송
+    fn expire_auth<R: Runtime>(&self, app: &AppHandle<R>, reason: &str) -> AppError {
+        eprintln!("Auth: Session expired ({}). Login required.", reason);
+        if let Some(storage) = app.try_state::<StorageManagerArc>() {
+            if let Err(e) = storage.delete_auth_token() {
+                eprintln!("Auth: Failed to delete expired token: {}", e);
+            }
+        }
+        app.emit("auth-expired", ()).ok();
+        AppError::Unauthorized(AUTH_EXPIRED_ERROR.to_string())
+    }
+
+    pub async fn check_latest_model_version<R: Runtime>(&self, app: &AppHandle<R>) -> Result<ModelVersionResponse> {
         let url = format!("{}/desktop/models/latest", get_api_base_url());
-        let resp = self.client.get(&url).bearer_auth(token).send().await?.error_for_status()?;
+        let resp = self
+            .send_authorized(app, |client, token| client.get(&url).bearer_auth(token))
+            .await?
+            .error_for_status()?;
         let info: ModelVersionResponse = resp.json().await?;
         Ok(info)
     }
 
-    pub async fn download_file(&self, endpoint: &str, save_path: &PathBuf, token: &str) -> Result<()> {
+    pub async fn download_file<R: Runtime>(&self, endpoint: &str, save_path: &PathBuf, app: &AppHandle<R>) -> Result<()> {
         let url = if endpoint.starts_with("http") {
             endpoint.to_string()
         } else {
             format!("{}{}", get_api_base_url(), endpoint)
         };
-        let resp = self.client.get(&url).bearer_auth(token).send().await?.error_for_status()?;
+        let resp = self
+            .send_authorized(app, |client, token| client.get(&url).bearer_auth(token))
+            .await
+            .map_err(anyhow::Error::msg)?
+            .error_for_status()?;
         let mut file = File::create(save_path).await?;
         let mut stream = resp.bytes_stream();
         while let Some(item) = stream.next().await {
@@ -141,81 +527,246 @@ impl BackendCommunicator {
         Ok(())
     }
 
-    pub async fn send_feedback_batch(&self, feedbacks: Vec<FeedbackPayload>, token: &str) -> Result<(), String> {
+    pub async fn send_feedback_batch<R: Runtime>(&self, feedbacks: Vec<FeedbackPayload>, app: &AppHandle<R>) -> Result<(), UploadError> {
         let url = format!("{}/desktop/feedback/batch", get_api_base_url());
         if feedbacks.is_empty() { return Ok(()); }
-        let response = self.client.post(&url).bearer_auth(token).json(&feedbacks).send().await
-            .map_err(|e| format!("Request failed: {}", e))?;
+        let response = self
+            .send_authorized(app, |client, token| client.post(&url).bearer_auth(token).json(&feedbacks))
+            .await?;
         if response.status().is_success() {
             println!("✅ Feedback batch sent successfully.");
             Ok(())
         } else {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            Err(format!("Server returned error {}: {}", status, text))
+            Err(UploadError::from_status(status, &text))
         }
     }
 
-    pub async fn sync_events_batch(&self, events: Vec<CachedEvent>, token: &str) -> Result<(), String> {
+    /// 이벤트 배치 업로드. 서버가 저장(또는 이미 보유)을 확인한 client_event_id 목록 반환
+    /// Idempotency-Key: 배치 구성(client_event_id 목록)의 해시 -> 같은 배치를 재전송하면 서버가 중복 처리하지 않음
+    pub async fn sync_events_batch<R: Runtime>(&self, events: Vec<CachedEvent>, app: &AppHandle<R>) -> Result<Vec<String>, UploadError> {
         let url = format!("{}/events/batch", get_api_base_url());
-        let event_data_list: Vec<EventData> = events.into_iter().filter_map(|e| {
-            match serde_json::from_str(&e.activity_vector) {
-                Ok(json_val) => Some(EventData {
-                    session_id: e.session_id,
-                    client_event_id: e.client_event_id,
-                    timestamp: e.timestamp,
-                    app_name: e.app_name,
-                    window_title: e.window_title,
-                    activity_vector: json_val,
-                }),
-                Err(err) => {
-                    eprintln!("Failed to parse activity_vector JSON for event {}: {}", e.id, err);
-                    None
-                }
+        let mut event_data_list: Vec<EventData> = Vec::with_capacity(events.len());
+        for e in events {
+            // 깨진 JSON은 서버로 보내지 않고 거부 처리 (sync 루프가 배치 분할 후 dead-letter로 격리)
+            let mut json_val = serde_json::from_str(&e.activity_vector).map_err(|err| {
+                UploadError::Rejected(format!("Invalid activity_vector JSON for event {}: {}", e.client_event_id, err))
+            })?;
+            // 너무 큰 이벤트는 잘라서 전송 (배치 전체가 거부되지 않도록)
+            if fit_activity_vector(&mut json_val, MAX_EVENT_BYTES) {
+                println!("Sync: Event {} activity_vector truncated ({} bytes)", e.client_event_id, e.activity_vector.len());
             }
-        }).collect();
-        if event_data_list.is_empty() { return Ok(()); }
-        let request_body = EventBatchRequest { events: event_data_list };
-        let response = self.client.post(&url).bearer_auth(token).json(&request_body).send().await
-            .map_err(|e| format!("Request failed: {}", e))?;
-        if response.status().is_success() {
-            println!("Sync success!");
-            Ok(())
-        } else {
+            event_data_list.push(EventData {
+                session_id: e.session_id,
+                client_event_id: e.client_event_id,
+                timestamp: e.timestamp,
+                app_name: e.app_name,
+                window_title: truncate_chars(&e.window_title, MAX_TITLE_CHARS),
+                activity_vector: json_val,
+            });
+        }
+        if event_data_list.is_empty() { return Ok(Vec::new()); }
+
+        let sent_ids: Vec<String> = event_data_list.iter().map(|e| e.client_event_id.clone()).collect();
+        let idempotency_key = batch_idempotency_key(&sent_ids);
+        let request_body = gzip_json(&EventBatchRequest { events: event_data_list }).map_err(UploadError::Rejected)?;
+        let response = self
+            .send_authorized(app, |client, token| {
+                client
+                    .post(&url)
+                    .bearer_auth(token)
+                    .header("Idempotency-Key", &idempotency_key)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .header(reqwest::header::CONTENT_ENCODING, "gzip")
+                    .body(request_body.clone())
+            })
+            .await?;
+
+        if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            Err(format!("Server returned error {}: {}", status, text))
+            return Err(UploadError::from_status(status, &text));
         }
+
+        let body: EventBatchResponse = response
+            .json()
+            .await
+            .map_err(|e| UploadError::Transient(format!("Event batch response parse error: {}", e)))?;
+
+        // 보낸 id 중 서버가 확인한 것만 삭제 대상
+        let accepted = match body.accepted_ids {
+            Some(ids) => sent_ids.into_iter().filter(|id| ids.contains(id)).collect(),
+            // 구버전 서버: 개수가 일치할 때만 전체 수락으로 간주
+            None if body.count == Some(sent_ids.len()) => sent_ids,
+            None => return Err(UploadError::Transient("Server did not confirm uploaded events".to_string())),
+        };
+        println!("Sync success! ({} events confirmed)", accepted.len());
+        Ok(accepted)
     }
 
-    pub async fn fetch_tasks(&self, token: &str) -> Result<Vec<LocalTask>, String> {
+    /// 서버 Task 목록 (etag: 마지막으로 반영한 ETag, 변경 없으면 NotModified)
+    pub async fn fetch_tasks<R: Runtime>(&self, app: &AppHandle<R>, etag: Option<&str>) -> AppResult<Fetched<LocalTask>> {
         let url = format!("{}/desktop/data/tasks", get_api_base_url());
-        let response = self.client.get(&url).bearer_auth(token).send().await
-            .map_err(|e| format!("Failed to fetch tasks: {}", e))?;
-        if response.status().is_success() {
-            let api_tasks: Vec<ApiTask> = response.json().await.map_err(|e| format!("JSON parse error: {}", e))?;
-            Ok(api_tasks.into_iter().map(|t| LocalTask {
-                id: t.id, user_id: t.user_id, task_name: t.name, description: t.description,
-                target_executable: t.target_executable, target_arguments: t.target_arguments, status: t.status,
-            }).collect())
-        } else {
-            Err(format!("Server error (Tasks): {}", response.status()))
+        let fetched: Fetched<ApiTask> = self.fetch_conditional(app, &url, etag).await
+            .map_err(|e| e.context("Failed to fetch tasks"))?;
+        Ok(match fetched {
+            Fetched::NotModified => Fetched::NotModified,
+            Fetched::Modified { items, etag } => Fetched::Modified {
+                items: items.into_iter().map(LocalTask::from).collect(),
+                etag,
+            },
+        })
+    }
+
+    /// 로컬 수정 Task 업로드. 충돌 시 서버가 Last-Writer-Wins로 판정한 결과를 반환
+    pub async fn push_task<R: Runtime>(&self, app: &AppHandle<R>, task: &LocalTask, local_updated_ms: i64) -> AppResult<TaskPushResponse> {
+        let url = format!("{}/desktop/data/tasks/{}", get_api_base_url(), task.id);
+        let client_updated_at = DateTime::<Utc>::from_timestamp_millis(local_updated_ms)
+            .ok_or(AppError::InvalidInput("Invalid local update time".to_string()))?
+            .to_rfc3339_opts(SecondsFormat::Millis, true);
+        let request_body = TaskPushRequest {
+            name: task.task_name.clone(),
+            description: task.description.clone(),
+            target_executable: task.target_executable.clone(),
+            target_arguments: task.target_arguments.clone(),
+            status: task.status.clone(),
+            client_updated_at,
+            base_updated_at: task.updated_at.clone(),
+        };
+
+        let response = self
+            .send_authorized(app, |client, token| client.put(&url).bearer_auth(token).json(&request_body))
+            .await
+            .map_err(|e| e.context("Failed to push task"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(AppError::from_status(status, &text).context("Task push"));
+        }
+        response.json().await.map_err(|e| AppError::Internal(format!("JSON parse error: {}", e)))
+    }
+
+    /// 서버에 저장된 내 활동 데이터 전체 삭제 요청 (이벤트, 피드백, 세션, Task, 스케줄)
+    pub async fn request_data_erasure<R: Runtime>(&self, app: &AppHandle<R>) -> Result<DataErasureResponse, UploadError> {
+        let url = format!("{}/desktop/data/me", get_api_base_url());
+        let response = self
+            .send_authorized(app, |client, token| client.delete(&url).bearer_auth(token))
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(UploadError::from_status(status, &text));
         }
+        response
+            .json()
+            .await
+            .map_err(|e| UploadError::Transient(format!("Erasure response parse error: {}", e)))
     }
 
-    pub async fn fetch_schedules(&self, token: &str) -> Result<Vec<LocalSchedule>, String> {
+    /// 서버 스케줄 목록 (etag: 마지막으로 반영한 ETag, 변경 없으면 NotModified)
+    pub async fn fetch_schedules<R: Runtime>(&self, app: &AppHandle<R>, etag: Option<&str>) -> AppResult<Fetched<LocalSchedule>> {
         let url = format!("{}/desktop/data/schedules", get_api_base_url());
-        let response = self.client.get(&url).bearer_auth(token).send().await
-            .map_err(|e| format!("Failed to fetch schedules: {}", e))?;
-        if response.status().is_success() {
-            let api_schedules: Vec<ApiSchedule> = response.json().await.map_err(|e| format!("JSON parse error: {}", e))?;
-            Ok(api_schedules.into_iter().map(|s| LocalSchedule {
-                id: s.id, user_id: s.user_id, task_id: s.task_id, name: s.name,
-                start_time: s.start_time, end_time: s.end_time, days_of_week: s.days_of_week,
-                start_date: s.start_date, is_active: s.is_active,
-            }).collect())
-        } else {
-            Err(format!("Server error (Schedules): {}", response.status()))
+        let fetched: Fetched<ApiSchedule> = self.fetch_conditional(app, &url, etag).await
+            .map_err(|e| e.context("Failed to fetch schedules"))?;
+        Ok(match fetched {
+            Fetched::NotModified => Fetched::NotModified,
+            Fetched::Modified { items, etag } => Fetched::Modified {
+                items: items.into_iter().map(|s| LocalSchedule {
+                    id: s.id, user_id: s.user_id, task_id: s.task_id, name: s.name,
+                    start_time: s.start_time, end_time: s.end_time, days_of_week: s.days_of_week,
+                    start_date: s.start_date, is_active: s.is_active,
+                }).collect(),
+                etag,
+            },
+        })
+    }
+
+    async fn fetch_conditional<R: Runtime, T: serde::de::DeserializeOwned>(
+        &self,
+        app: &AppHandle<R>,
+        url: &str,
+        etag: Option<&str>,
+    ) -> AppResult<Fetched<T>> {
+        let response = self
+            .send_authorized(app, |client, token| {
+                let request = client.get(url).bearer_auth(token);
+                match etag {
+                    Some(tag) => request.header(reqwest::header::IF_NONE_MATCH, tag),
+                    None => request,
+                }
+            })
+            .await?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            return Ok(Fetched::NotModified);
         }
+        if !status.is_success() {
+            return Err(AppError::from_status(status, ""));
+        }
+        let new_etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        let items: Vec<T> = response.json().await.map_err(|e| AppError::Internal(format!("JSON parse error: {}", e)))?;
+        Ok(Fetched::Modified { items, etag: new_etag })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jwt_expiry_from_payload() {
+        // header.payload.signature (payload = {"exp":1700000000,"sub":"user-1","type":"access"})
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(r#"{"exp":1700000000,"sub":"user-1","type":"access"}"#);
+        let token = format!("eyJhbGciOiJIUzI1NiJ9.{}.signature", payload);
+        assert_eq!(jwt_expiry_s(&token), Some(1700000000));
+
+        // JWT 형식이 아닌 토큰 -> 만료 시각 모름 (서버 401에 의존)
+        assert_eq!(jwt_expiry_s("opaque-token"), None);
+        assert_eq!(jwt_expiry_s("a.!!!.c"), None);
+    }
+
+    #[test]
+    fn test_fit_activity_vector_truncates_oversized_event() {
+        let window = |i: usize| serde_json::json!({ "title": "w".repeat(2000), "app_name": format!("app{}.exe", i) });
+        let mut value = serde_json::json!({
+            "meaningful_input_events": 3,
+            "visible_windows": (0..100).map(window).collect::<Vec<_>>(),
+        });
+
+        // 작은 값은 그대로
+        let mut small = serde_json::json!({ "meaningful_input_events": 1, "visible_windows": [] });
+        assert!(!fit_activity_vector(&mut small, MAX_EVENT_BYTES));
+
+        assert!(fit_activity_vector(&mut value, 8 * 1024));
+        assert!(serde_json::to_vec(&value).unwrap().len() <= 8 * 1024);
+        assert_eq!(value["meaningful_input_events"], 3);
+        let kept = value["visible_windows"].as_array().unwrap();
+        assert!(!kept.is_empty());
+        assert_eq!(kept[0]["title"].as_str().unwrap().chars().count(), MAX_TITLE_CHARS);
+        assert_eq!(value["visible_windows_truncated"].as_u64().unwrap() as usize, 100 - kept.len());
+
+        // 멀티바이트 문자 경계에서 안전하게 자름
+        assert_eq!(truncate_chars("가나다라", 2), "가나");
+    }
+
+    #[test]
+    fn test_gzip_json_has_gzip_header() {
+        let body = gzip_json(&serde_json::json!({ "events": [] })).unwrap();
+        assert_eq!(&body[..2], &[0x1f, 0x8b]);
+    }
+
+    #[test]
+    fn test_batch_idempotency_key_is_stable_per_batch() {
+        let batch = vec!["evt-1".to_string(), "evt-2".to_string()];
+        assert_eq!(batch_idempotency_key(&batch), batch_idempotency_key(&batch.clone()));
+        assert_eq!(batch_idempotency_key(&batch).len(), 64);
+        assert_ne!(batch_idempotency_key(&batch), batch_idempotency_key(&batch[..1]));
     }
 }
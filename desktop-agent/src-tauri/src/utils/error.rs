@@ -0,0 +1,242 @@
+// 파일 위치: src-tauri/src/utils/error.rs
+// 크레이트 공통 에러 타입
+// Tauri 커맨드는 { code, message } 형태로 직렬화되어 프론트엔드가 code로 분기할 수 있다.
+// code 문자열은 프론트엔드(types/index.ts AppErrorCode)와 맞춰야 하므로 바꾸지 말 것.
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+pub type AppResult<T> = Result<T, AppError>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    // --- 저장소 (LSN) ---
+    DbLocked(String), // SQLITE_BUSY/LOCKED: 잠시 후 재시도하면 성공할 수 있음
+    Storage(String),
+    NotFound(String),
+
+    // --- 네트워크 / 서버 ---
+    Offline(String), // 연결 실패, 타임아웃
+    Server { status: u16, message: String },
+
+    // --- 인증 ---
+    Unauthorized(String), // 로그인 안 됨 / 토큰 만료 -> 재로그인 필요
+
+    // --- ML 모델 ---
+    Model(String),
+
+    // --- OS / 플랫폼 (창 정보, 프로세스, 파일 시스템) ---
+    Platform(String),
+
+    // --- 요청 값 검증 ---
+    InvalidInput(String),
+
+    Internal(String),
+}
+
+impl AppError {
+    /// 프론트엔드가 분기에 사용하는 안정적인 코드
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::DbLocked(_) => "db_locked",
+            AppError::Storage(_) => "storage",
+            AppError::NotFound(_) => "not_found",
+            AppError::Offline(_) => "offline",
+            AppError::Server { .. } => "server",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Model(_) => "model",
+            AppError::Platform(_) => "platform",
+            AppError::InvalidInput(_) => "invalid_input",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::DbLocked(m)
+            | AppError::Storage(m)
+            | AppError::NotFound(m)
+            | AppError::Offline(m)
+            | AppError::Unauthorized(m)
+            | AppError::Model(m)
+            | AppError::Platform(m)
+            | AppError::InvalidInput(m)
+            | AppError::Internal(m) => m,
+            AppError::Server { message, .. } => message,
+        }
+    }
+
+    /// 종류는 유지하고 메시지 앞에 맥락 추가 (예: "Failed to prune cached_events: database is locked")
+    pub fn context(self, context: impl fmt::Display) -> Self {
+        let wrap = |m: String| format!("{}: {}", context, m);
+        match self {
+            AppError::DbLocked(m) => AppError::DbLocked(wrap(m)),
+            AppError::Storage(m) => AppError::Storage(wrap(m)),
+            AppError::NotFound(m) => AppError::NotFound(wrap(m)),
+            AppError::Offline(m) => AppError::Offline(wrap(m)),
+            AppError::Server { status, message } => AppError::Server { status, message: wrap(message) },
+            AppError::Unauthorized(m) => AppError::Unauthorized(wrap(m)),
+            AppError::Model(m) => AppError::Model(wrap(m)),
+            AppError::Platform(m) => AppError::Platform(wrap(m)),
+            AppError::InvalidInput(m) => AppError::InvalidInput(wrap(m)),
+            AppError::Internal(m) => AppError::Internal(wrap(m)),
+        }
+    }
+
+    /// 서버 응답 상태 코드 분류 (401 -> Unauthorized, 404 -> NotFound)
+    pub fn from_status(status: reqwest::StatusCode, body: &str) -> Self {
+        let message = format!("Server returned error {}: {}", status, body);
+        match status {
+            reqwest::StatusCode::UNAUTHORIZED => AppError::Unauthorized(message),
+            reqwest::StatusCode::NOT_FOUND => AppError::NotFound(message),
+            _ => AppError::Server { status: status.as_u16(), message },
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", self.message())?;
+        state.end()
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        match &e {
+            rusqlite::Error::QueryReturnedNoRows => AppError::NotFound(e.to_string()),
+            rusqlite::Error::SqliteFailure(err, _)
+                if matches!(err.code, rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked) =>
+            {
+                AppError::DbLocked(e.to_string())
+            }
+            _ => AppError::Storage(e.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_connect() || e.is_timeout() {
+            return AppError::Offline(e.to_string());
+        }
+        match e.status() {
+            Some(status) => AppError::from_status(status, ""),
+            None => AppError::Internal(e.to_string()),
+        }
+    }
+}
+
+impl<T> From<std::sync::PoisonError<T>> for AppError {
+    fn from(e: std::sync::PoisonError<T>) -> Self {
+        AppError::Internal(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        AppError::Internal(e.to_string())
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::Platform(e.to_string())
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(e: tauri::Error) -> Self {
+        AppError::Platform(e.to_string())
+    }
+}
+
+impl From<std::time::SystemTimeError> for AppError {
+    fn from(e: std::time::SystemTimeError) -> Self {
+        AppError::Platform(e.to_string())
+    }
+}
+
+// Linux 시각 센서 (X 서버 연결 없음 / 요청 실패)
+#[cfg(target_os = "linux")]
+impl From<x11rb::errors::ConnectError> for AppError {
+    fn from(e: x11rb::errors::ConnectError) -> Self {
+        AppError::Platform(format!("X11 connection failed: {}", e))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<x11rb::errors::ConnectionError> for AppError {
+    fn from(e: x11rb::errors::ConnectionError) -> Self {
+        AppError::Platform(format!("X11 connection error: {}", e))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<x11rb::errors::ReplyError> for AppError {
+    fn from(e: x11rb::errors::ReplyError) -> Self {
+        AppError::Platform(format!("X11 request failed: {}", e))
+    }
+}
+
+// 아직 String 에러를 쓰는 내부 함수와의 상호 변환 (분류할 수 없는 에러는 Internal)
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Internal(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::Internal(message.to_string())
+    }
+}
+
+impl From<AppError> for String {
+    fn from(e: AppError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_to_code_and_message() {
+        let json = serde_json::to_value(AppError::Offline("connection refused".to_string())).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": "offline", "message": "connection refused" }));
+
+        let json = serde_json::to_value(AppError::Server { status: 503, message: "down".to_string() }).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": "server", "message": "down" }));
+    }
+
+    #[test]
+    fn test_sqlite_errors_are_classified() {
+        let busy = rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY), None);
+        assert_eq!(AppError::from(busy).code(), "db_locked");
+        assert_eq!(AppError::from(rusqlite::Error::QueryReturnedNoRows).code(), "not_found");
+
+        let err = AppError::from(rusqlite::Error::QueryReturnedNoRows).context("Task 'abc'");
+        assert_eq!(err.code(), "not_found");
+        assert!(err.message().starts_with("Task 'abc': "));
+    }
+
+    #[test]
+    fn test_status_classification() {
+        assert_eq!(AppError::from_status(reqwest::StatusCode::UNAUTHORIZED, "").code(), "unauthorized");
+        assert_eq!(AppError::from_status(reqwest::StatusCode::NOT_FOUND, "").code(), "not_found");
+        assert_eq!(AppError::from_status(reqwest::StatusCode::BAD_GATEWAY, "").code(), "server");
+    }
+}
@@ -7,12 +7,13 @@ use serde_json;
 use std::env; // 환경 변수 접근
 use std::fs::{self, OpenOptions}; // 파일 시스템 작업
 use std::io::prelude::*; // 파일 쓰기
-use std::path::PathBuf; // 경로 관리
+use std::path::{Path, PathBuf}; // 경로 관리
 use std::thread; // 백그라운드 스레드 생성
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use tauri::Manager;
 
-use crate::InputStatsArcMutex;
+use crate::core::sensor::WindowSensor;
+use crate::{InputStatsArcMutex, WindowSensorArc};
 
 // 로그에 저장할 데이터 형식 정의 ---
 // 주기적으로 수집한 정보 담는 구조체
@@ -59,10 +60,102 @@ pub fn get_log_file_path(log_dir: &PathBuf, date: &DateTime<Local>) -> PathBuf {
     }
 }
 
+// 로그 디렉토리의 *.jsonl 파일 목록 (경로, 크기, 수정 시각) - 오래된 순
+fn list_log_files(log_dir: &Path) -> Result<Vec<(PathBuf, u64, SystemTime)>, String> {
+    let entries = match fs::read_dir(log_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read log directory {:?}: {}", log_dir, e)),
+    };
+
+    let mut files: Vec<(PathBuf, u64, SystemTime)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|path| {
+            let meta = fs::metadata(&path).ok()?;
+            let modified = meta.modified().ok()?;
+            Some((path, meta.len(), modified))
+        })
+        .collect();
+    files.sort_by(|a, b| a.2.cmp(&b.2).then_with(|| a.0.cmp(&b.0)));
+    Ok(files)
+}
+
+// 로그 파일 개수와 전체 크기 (bytes)
+pub fn log_dir_usage(log_dir: &Path) -> Result<(u64, u64), String> {
+    let files = list_log_files(log_dir)?;
+    Ok((files.len() as u64, files.iter().map(|(_, size, _)| size).sum()))
+}
+
+// 보관 기간이 지난 로그 파일 삭제 후, 전체 크기가 한도를 넘으면 오래된 파일부터 삭제
+// 가장 최근 파일은 현재 기록 중일 수 있으므로 삭제하지 않음. 삭제한 파일 수 반환
+pub fn prune_log_files(
+    log_dir: &Path,
+    max_age: Duration,
+    max_total_bytes: u64,
+    now: SystemTime,
+) -> Result<u64, String> {
+    let mut files = list_log_files(log_dir)?;
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    // 최신 파일은 보호
+    if files.pop().is_none() {
+        return Ok(0);
+    }
+
+    let mut deleted = 0;
+    for (path, size, modified) in files {
+        let expired = now.duration_since(modified).map(|age| age > max_age).unwrap_or(false);
+        if !expired && total <= max_total_bytes {
+            continue;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                total = total.saturating_sub(size);
+                deleted += 1;
+            }
+            Err(e) => eprintln!("Failed to delete log file {:?}: {}", path, e),
+        }
+    }
+    Ok(deleted)
+}
+
+// 로그 엔트리 1건 수집 (활성 창은 주입된 창 센서에서)
+fn collect_log_entry(
+    window_sensor: &dyn WindowSensor,
+    input_stats_arc_mutex: &InputStatsArcMutex,
+    current_time: &DateTime<Local>,
+) -> ActivityLogEntry {
+    // 로깅할 엔트리 초기화
+    let mut log_entry = ActivityLogEntry {
+        timestamp: current_time.to_rfc3339(), // ISO 8601 형식의 현재 시간
+        active_window: None,
+        input_stats: None,
+    };
+
+    // 1. 활성 창 정보 수집
+    match window_sensor.active_window() {
+        Ok(active_window_info) => {
+            log_entry.active_window = Some(active_window_info);
+        }
+        Err(e) => eprintln!("Logging: Failed to get active window info: {}", e),
+    }
+
+    // 2. 입력 통계 수집 (InputStatsArcMutex에서 직접 읽어오기)
+    if let Ok(stats_guard) = input_stats_arc_mutex.lock() {
+        log_entry.input_stats = Some(stats_guard.clone());
+    } else {
+        eprintln!("Logging: Failed to lock input_stats_arc_mutex");
+    }
+
+    log_entry
+}
+
 // 주기적으로 데이터를 수집하고 파일에 로깅하는 함수
 pub fn start_data_collection_and_logging<R: tauri::Runtime + Send + 'static>(
     app_handle: tauri::AppHandle<R>,
     input_stats_arc_mutex: InputStatsArcMutex,
+    window_sensor: WindowSensorArc,
     interval_secs: u64,
 ) {
     let log_dir_result = get_log_dir(&app_handle); // 로그 디렉토리 경로 가져오기
@@ -87,27 +180,8 @@ pub fn start_data_collection_and_logging<R: tauri::Runtime + Send + 'static>(
             let current_time: DateTime<Local> = Local::now();
             let log_file_path = get_log_file_path(&log_dir, &current_time); // 현재 날짜의 로그 파일 경로
 
-            // 로깅할 엔트리 초기화
-            let mut log_entry = ActivityLogEntry {
-                timestamp: current_time.to_rfc3339(), // ISO 8601 형식의 현재 시간
-                active_window: None,
-                input_stats: None,
-            };
-
-            // 1. 활성 창 정보 수집
-            match crate::commands::vision::_get_active_window_info_internal() {
-                Ok(active_window_info) => {
-                    log_entry.active_window = Some(active_window_info);
-                }
-                Err(e) => eprintln!("Logging: Failed to get active window info: {}", e),
-            }
-
-            // 2. 입력 통계 수집 (InputStatsArcMutex에서 직접 읽어오기)
-            if let Ok(stats_guard) = input_stats_arc_mutex.lock() {
-                log_entry.input_stats = Some(stats_guard.clone());
-            } else {
-                eprintln!("Logging: Failed to lock input_stats_arc_mutex");
-            }
+            // 1~2. 활성 창 정보 + 입력 통계 수집
+            let log_entry = collect_log_entry(window_sensor.as_ref(), &input_stats_arc_mutex, &current_time);
 
             // 3. 로그 엔트리 파일에 기록 (JSON Lines 형식)
             if let Ok(json_line) = serde_json::to_string(&log_entry) {
@@ -136,3 +210,73 @@ pub fn start_data_collection_and_logging<R: tauri::Runtime + Send + 'static>(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_log_files_by_age_and_size() {
+        let log_dir = env::temp_dir().join(format!("ff-log-prune-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&log_dir);
+        fs::create_dir_all(&log_dir).unwrap();
+
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        // (파일명, 크기, 경과 일수)
+        let files = [
+            ("2026-01-01.jsonl", 10, 40),
+            ("2026-01-20.jsonl", 100, 5),
+            ("2026-01-21.jsonl", 100, 4),
+            ("2026-01-22.jsonl", 100, 3),
+            ("2026-01-24.jsonl", 500, 1),
+        ];
+        for (name, size, age_days) in files {
+            let path = log_dir.join(name);
+            fs::write(&path, vec![b'x'; size]).unwrap();
+            let file = OpenOptions::new().write(true).open(&path).unwrap();
+            file.set_modified(now - day * age_days).unwrap();
+        }
+        fs::write(log_dir.join("notes.txt"), b"keep").unwrap();
+
+        // 기간 초과 1개 + 용량 초과(800 -> 700 이하) 1개 삭제
+        let deleted = prune_log_files(&log_dir, day * 14, 700, now).unwrap();
+        assert_eq!(deleted, 2);
+        assert!(!log_dir.join("2026-01-01.jsonl").exists());
+        assert!(!log_dir.join("2026-01-20.jsonl").exists());
+        assert!(log_dir.join("2026-01-21.jsonl").exists());
+        assert!(log_dir.join("notes.txt").exists());
+        assert_eq!(log_dir_usage(&log_dir).unwrap(), (3, 700));
+
+        // 한도가 아무리 작아도 가장 최근 파일은 남김
+        prune_log_files(&log_dir, day * 14, 1, now).unwrap();
+        assert_eq!(log_dir_usage(&log_dir).unwrap(), (1, 500));
+
+        fs::remove_dir_all(&log_dir).unwrap();
+    }
+
+    #[test]
+    fn test_log_entry_uses_window_sensor() {
+        use crate::commands::vision::WinRect;
+        use crate::core::sensor::fake::FakeSensor;
+        use std::sync::{Arc, Mutex};
+
+        let input_stats: InputStatsArcMutex = Arc::new(Mutex::new(commands::input::InputStats {
+            meaningful_input_events: 7,
+            ..Default::default()
+        }));
+        let now = Local::now();
+        let rect = WinRect { left: 0, top: 0, right: 800, bottom: 600 };
+
+        let sensor = FakeSensor::new(vec![vec![FakeSensor::window(1, "Editor", rect)]]);
+        let entry = collect_log_entry(&sensor, &input_stats, &now);
+        assert_eq!(entry.timestamp, now.to_rfc3339());
+        assert_eq!(entry.active_window.unwrap().title, "Editor");
+        assert_eq!(entry.input_stats.unwrap().meaningful_input_events, 7);
+
+        // 활성 창을 얻지 못해도 입력 통계는 기록
+        let entry = collect_log_entry(&FakeSensor::new(vec![]), &input_stats, &now);
+        assert!(entry.active_window.is_none());
+        assert!(entry.input_stats.is_some());
+    }
+}
@@ -45,6 +45,7 @@ function App() {
   // 2. [이벤트] 실시간 로그인 성공 감지
   useEffect(() => {
     let unlisten: (() => void) | null = null;
+    let unlistenExpired: (() => void) | null = null;
     const setupAuthListener = async () => {
       try {
         unlisten = await listen<string>('login-success', (event) => {
@@ -52,12 +53,21 @@ function App() {
           setUserEmail(event.payload);
           setCurrentView('main');
         });
+        // Refresh Token까지 만료/거부됨 -> Rust가 토큰을 삭제했으므로 재로그인 화면으로
+        unlistenExpired = await listen('auth-expired', () => {
+          console.log("[App] Auth expired. Login required.");
+          setUserEmail(null);
+          setCurrentView('login');
+        });
       } catch (e) {
         console.error("Failed to setup auth listener:", e);
       }
     };
     setupAuthListener();
-    return () => { if (unlisten) unlisten(); };
+    return () => {
+      if (unlisten) unlisten();
+      if (unlistenExpired) unlistenExpired();
+    };
   }, []);
 
   // --- 핸들러 ---
@@ -1,10 +1,12 @@
 import React, { useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event'; // [필수] 이벤트 리스너
+import { RestRecommendationPayload } from '../types';
 
 const InterventionOverlay: React.FC = () => {
   // 현재 모드 상태 관리 ('hidden'은 부모 차원에서 처리되므로 여기선 notification/blocking만)
-  const [mode, setMode] = useState<'notification' | 'blocking'>('notification');
+  const [mode, setMode] = useState<'notification' | 'blocking' | 'rest'>('notification');
+  const [restInfo, setRestInfo] = useState<RestRecommendationPayload | null>(null);
 
   useEffect(() => {
     // Rust 백엔드(app_core.rs)에서 보내는 'intervention-trigger' 이벤트 수신
@@ -18,8 +20,16 @@ const InterventionOverlay: React.FC = () => {
       }
     });
 
+    // 긍정적 개입: 장시간 몰입 후 휴식 권장 (core/state.rs REST_RECOMMENDED)
+    const unlistenRestPromise = listen<RestRecommendationPayload>('rest-recommendation', (event) => {
+      console.log("Rest Recommendation Received:", event.payload);
+      setRestInfo(event.payload);
+      setMode('rest');
+    });
+
     return () => {
       unlistenPromise.then(unlisten => unlisten());
+      unlistenRestPromise.then(unlisten => unlisten());
     };
   }, []);
 
@@ -102,6 +112,34 @@ const InterventionOverlay: React.FC = () => {
     );
   }
 
+  // [UI 분기 1-1] Rest 모드 (휴식 권장)
+  // 작업을 막지 않도록 Click-Through, 붉은 경고 대신 부드러운 그린 톤
+  if (mode === 'rest') {
+    const focusMin = restInfo ? Math.floor(restInfo.continuous_focus_sec / 60) : 0;
+    const breakMin = restInfo ? Math.max(1, Math.round(restInfo.suggested_break_sec / 60)) : 5;
+    return (
+      <div style={{
+        position: 'fixed', top: 0, left: 0, width: '100vw', height: '100vh',
+        border: '6px solid rgba(52, 199, 89, 0.45)', // 은은한 그린 테두리
+        boxSizing: 'border-box',
+        pointerEvents: 'none',
+        zIndex: 9999,
+        display: 'flex', justifyContent: 'center', alignItems: 'flex-start'
+      }}>
+        <div style={{
+          marginTop: '20px',
+          padding: '8px 16px',
+          backgroundColor: 'rgba(52, 199, 89, 0.9)',
+          color: 'white', borderRadius: '20px', fontWeight: 'bold',
+          fontSize: '14px',
+          boxShadow: '0 4px 12px rgba(0,0,0,0.3)'
+        }}>
+          🌿 {focusMin}분 동안 집중했어요. {breakMin}분 정도 쉬어가세요
+        </div>
+      </div>
+    );
+  }
+
   // [UI 분기 2] Blocking 모드 (차단 단계)
   // 클릭을 받아야 하므로 pointerEvents: 'auto' (기본값)
   return (
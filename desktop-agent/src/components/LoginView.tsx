@@ -1,9 +1,10 @@
-import { useEffect } from 'react';
+import { useEffect, useState } from 'react';
 // 분리된 스타일 파일 import
 import { styles } from './LoginView.styles';
 
 import { open } from '@tauri-apps/plugin-shell';
 import { listen } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/core';
 
 interface LoginViewProps {
   onLoginSuccess: (email: string) => void;
@@ -11,10 +12,14 @@ interface LoginViewProps {
 }
 
 const LoginView: React.FC<LoginViewProps> = ({ onLoginSuccess, onOfflineClick }) => {
+  // 딥 링크 콜백이 거부된 경우 (state 불일치/만료 등) 안내 메시지
+  const [loginError, setLoginError] = useState<string | null>(null);
+
   // --- 핸들러 로직 ---
   // Rust(lib.rs)의 로그인 성공 이벤트 수신
   useEffect(() => {
     let unlisten: (() => void) | null = null;
+    let unlistenFailed: (() => void) | null = null;
 
     const setupListener = async () => {
       try {
@@ -25,6 +30,11 @@ const LoginView: React.FC<LoginViewProps> = ({ onLoginSuccess, onOfflineClick })
           onLoginSuccess(event.payload);
         });
         unlisten = unlistenFn;
+        // payload: 거부 사유 (security_events.kind)
+        unlistenFailed = await listen<string>('login-failed', (event) => {
+          console.warn(`Login callback rejected: ${event.payload}`);
+          setLoginError('로그인 요청을 확인할 수 없습니다. 다시 시도해 주세요.');
+        });
       } catch (e) {
         console.error("Failed to setup login listener:", e);
       }
@@ -34,17 +44,18 @@ const LoginView: React.FC<LoginViewProps> = ({ onLoginSuccess, onOfflineClick })
 
     return () => {
       if (unlisten) unlisten();
+      if (unlistenFailed) unlistenFailed();
     };
   }, [onLoginSuccess]);
 
   // 구글 로그인 버튼 핸들러
   const handleGoogleLogin = async () => {
+    setLoginError(null);
     try {
-      // 환경 변수(.env)에서 API 주소 로드
-      const apiBaseUrl = import.meta.env.VITE_API_BASE_URL || 'http://127.0.0.1:8000/api/v1';
-      const targetUrl = `${apiBaseUrl}/auth/desktop/google/login`;
+      // Rust가 1회용 state + PKCE challenge를 포함한 로그인 URL 생성 (콜백 검증용)
+      const targetUrl = await invoke<string>('begin_login');
 
-      console.log(`Opening Google Login page: ${targetUrl}`);
+      console.log("Opening Google Login page.");
       
       // 시스템 브라우저로 열기 (임시 함수 호출)
       await open(targetUrl);
@@ -118,6 +129,9 @@ const LoginView: React.FC<LoginViewProps> = ({ onLoginSuccess, onOfflineClick })
       </div>
 
       <div style={styles.footer}>
+        {loginError && (
+          <p style={{ ...styles.footerText, color: '#f87171' }}>{loginError}</p>
+        )}
         <p style={styles.footerText}>
           로그인 시 데이터 동기화 및 고급 분석 기능이 활성화됩니다.
         </p>
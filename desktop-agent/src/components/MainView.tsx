@@ -2,6 +2,8 @@ import React, { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { styles } from './MainView.styles';
+import type { SyncStatusPayload, SyncedCollection } from '../types';
+import { errorMessage } from '../utils/appError';
 // --- 1. 타입 정의 ---
 // (types.ts 또는 유사 파일에서 가져오는 것이 좋으나, 여기서는 직접 정의)
 interface Task {
@@ -45,6 +47,9 @@ const MainView: React.FC<MainViewProps> = ({ onLogout, onOpenSettings, userEmail
 
   const [error, setError] = useState<string | null>(null);
 
+  // 백그라운드 동기화 상태 (sync-status, 재시도 대기 중이면 배지에 표시)
+  const [syncStatus, setSyncStatus] = useState<SyncStatusPayload | null>(null);
+
   // --- 3. 데이터 조회 (Mock API) ---
   // 컴포넌트 마운트 시 Mock API에서 Task 목록을 가져옵니다.
   useEffect(() => {
@@ -61,10 +66,10 @@ const MainView: React.FC<MainViewProps> = ({ onLogout, onOpenSettings, userEmail
         // React 상태와 DOM 상태가 일치
         setSelectedTaskId(BASIC_TASK_ID);
         
-      } catch (e: any) {
+      } catch (e) {
         // 오프라인이거나 서버 에러 시 조용히 처리 (로그만 남김)
         console.warn('Failed to load tasks:', e);
-        setError(userEmail ? (e.message || 'Failed to load data') : null);
+        setError(userEmail ? errorMessage(e) : null);
       }
     };
 
@@ -77,8 +82,8 @@ const MainView: React.FC<MainViewProps> = ({ onLogout, onOpenSettings, userEmail
           setActiveSession(sessionInfo); // [!] 꼬인 세션 복원
           // [!] (타이머 시작은 4단계 'listen'이 처리)
         }
-      } catch (e: any) {
-         setError(e.toString());
+      } catch (e) {
+         setError(errorMessage(e));
       }
     };
 
@@ -89,6 +94,8 @@ const MainView: React.FC<MainViewProps> = ({ onLogout, onOpenSettings, userEmail
   // --- 4. 타이머 로직 (Task 4.12: Rust PUSH 수신) ---
   useEffect(() => {
     let unlistenTick: (() => void) | null = null;
+    let unlistenSync: (() => void) | null = null;
+    let unlistenData: (() => void) | null = null;
     
     const setupListener = async () => {
       try {
@@ -98,14 +105,28 @@ const MainView: React.FC<MainViewProps> = ({ onLogout, onOpenSettings, userEmail
           setElapsedTime(e.payload); // Rust가 보낸 경과 시간(u64)으로 상태 업데이트
         });
         unlistenTick = unlistenTickFn;
-      } catch (e: any) {
-         setError(e.toString());
+        unlistenSync = await listen<SyncStatusPayload>("sync-status", (e) => {
+          setSyncStatus(e.payload);
+        });
+        // 서버 데이터가 실제로 바뀐 경우에만 Task 목록 다시 로드 (선택 중인 Task는 유지)
+        unlistenData = await listen<SyncedCollection[]>("data-changed", async (e) => {
+          if (!e.payload.includes('tasks')) return;
+          try {
+            setTasks(await invoke<Task[]>('get_tasks'));
+          } catch (err) {
+            console.warn('Failed to reload tasks:', err);
+          }
+        });
+      } catch (e) {
+         setError(errorMessage(e));
       }
     };
     setupListener();
 
     return () => {
       if (unlistenTick) unlistenTick();
+      if (unlistenSync) unlistenSync();
+      if (unlistenData) unlistenData();
     };
   }, []); // 'listen'은 마운트 시 1회만
 
@@ -122,8 +143,8 @@ const MainView: React.FC<MainViewProps> = ({ onLogout, onOpenSettings, userEmail
         goalDuration: 60,
       });
       setActiveSession(sessionInfo);
-    } catch (e: any) {
-      setError(e.toString());
+    } catch (e) {
+      setError(errorMessage(e));
     }
   }, [selectedTaskId]); // selectedTaskId가 변경될 때마다 함수 재생성
 
@@ -139,8 +160,8 @@ const MainView: React.FC<MainViewProps> = ({ onLogout, onOpenSettings, userEmail
       // 세션 상태를 비활성화합니다.
       setActiveSession(null);
       setElapsedTime(0); // (Rust PUSH('widget-tick', 0)이 1초 안에 덮어쓸 것임)
-    } catch (e: any) {
-      setError(e.toString());
+    } catch (e) {
+      setError(errorMessage(e));
     }
   }, []);
 
@@ -168,12 +189,19 @@ const MainView: React.FC<MainViewProps> = ({ onLogout, onOpenSettings, userEmail
         
         <div style={styles.statusContainer}>
           {/* 상태 배지 */}
-          <div style={styles.statusBadge}>
+          <div
+            style={styles.statusBadge}
+            title={syncStatus?.state === 'backoff'
+              ? `동기화 실패 (${syncStatus.next_retry_in_sec ?? 0}초 후 재시도): ${syncStatus.last_error ?? ''}`
+              : undefined}
+          >
             <span style={{
               ...styles.statusDot,
-              backgroundColor: userEmail ? '#4ade80' : '#9ca3af'
+              backgroundColor: !userEmail
+                ? '#9ca3af'
+                : syncStatus?.state === 'backoff' ? '#fbbf24' : '#4ade80'
             }} />
-            {userEmail ? 'Online' : 'Offline'}
+            {!userEmail ? 'Offline' : syncStatus?.state === 'backoff' ? 'Sync Retry' : 'Online'}
           </div>
 
           {/* 로그아웃 버튼 */}
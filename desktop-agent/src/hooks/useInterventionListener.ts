@@ -2,6 +2,7 @@ import { useState, useEffect } from 'react';
 import { listen } from '@tauri-apps/api/event';
 
 import { isPermissionGranted, requestPermission, sendNotification } from '@tauri-apps/plugin-notification';
+import { errorMessage } from '../utils/appError';
 
 // Rust에서 오는 페이로드 타입
 type InterventionPayload = "notification" | "overlay";
@@ -40,7 +41,7 @@ export function useInterventionListener() {
       }
     } catch (e) {
       console.error("Failed to send OS notification:", e);
-      setBackendError(`OS 알림 전송 실패: ${e}`);
+      setBackendError(`OS 알림 전송 실패: ${errorMessage(e)}`);
     }
   };
 
@@ -64,7 +65,7 @@ export function useInterventionListener() {
         unlistenFn = unlisten;
       } catch (e) {
         console.error("Failed to setup Rust listener:", e);
-        setBackendError(`이벤트 리스너 설정 실패: ${e}`);
+        setBackendError(`이벤트 리스너 설정 실패: ${errorMessage(e)}`);
       }
     };
 
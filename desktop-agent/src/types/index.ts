@@ -15,11 +15,144 @@ export interface Task {
   updated_at: string;
 }
 
+// Rust의 utils/error.rs AppError (모든 커맨드의 실패 응답: invoke가 이 객체로 reject)
+export type AppErrorCode =
+  | 'db_locked'
+  | 'storage'
+  | 'not_found'
+  | 'offline'
+  | 'server'
+  | 'unauthorized'
+  | 'model'
+  | 'platform'
+  | 'invalid_input'
+  | 'internal';
+
+export interface AppError {
+  code: AppErrorCode;
+  message: string;
+}
+
 // Rust의 lib.rs ActiveSessionInfo와 일치
 export interface ActiveSessionInfo {
   session_id: string;
   task_id: string | null;
   start_time_s: number;
+  intent: string | null; // 인텐트 프리셋 id (예: "research")
+  scheduled_end_s: number | null; // 스케줄 세션 자동 종료 시각 (수동 세션은 null)
+  paused_total_s: number; // 누적 일시정지 시간 (초)
+  paused_at_s: number | null; // 일시정지 중이면 시작 시각
+  goal_duration_min: number | null; // 목표 집중 시간 (분)
+  goal_reached_s: number | null; // 목표 달성 시각 (이후 overtime)
+}
+
+// Rust의 core/state.rs RestRecommendationPayload와 일치 ('rest-recommendation' 이벤트)
+export interface RestRecommendationPayload {
+  continuous_focus_sec: number;
+  suggested_break_sec: number;
+}
+
+// Rust의 managers/schedule.rs SessionEndWarningPayload와 일치 ('session-end-warning' 이벤트)
+export interface SessionEndWarningPayload {
+  session_id: string;
+  scheduled_end_s: number;
+  remaining_sec: number;
+  extend_min: number;
+}
+
+// Rust의 core/goal.rs 페이로드와 일치 ('widget-progress' / 'session-goal-reached' 이벤트)
+export interface SessionProgressPayload {
+  elapsed_sec: number;
+  goal_sec: number | null;
+  progress_ratio: number; // 0.0 ~ 1.0
+  overtime_sec: number;
+}
+
+export interface SessionGoalReachedPayload {
+  session_id: string;
+  goal_duration_min: number;
+  action: 'overtime' | 'auto_end';
+}
+
+// Rust의 managers/history.rs SessionSummary와 일치 (get_session_summary 커맨드)
+export interface AppDistraction {
+  app_name: string;
+  distracted_sec: number;
+}
+
+export interface SessionSummary {
+  session_id: string;
+  task_id: string | null;
+  intent: string | null;
+  start_time_s: number;
+  end_time_s: number | null;
+  goal_duration_min: number;
+  user_evaluation_score: number | null;
+  paused_sec: number;
+  state_durations_sec: Record<string, number>;
+  notification_count: number;
+  overlay_count: number;
+  rest_recommendation_count: number;
+  top_distracting_apps: AppDistraction[];
+}
+
+// Rust의 managers/sync.rs SyncStatusPayload와 일치 ('sync-status' 이벤트)
+export interface SyncStatusPayload {
+  state: 'syncing' | 'idle' | 'backoff' | 'offline';
+  pending_events: number;
+  dead_letter_count: number;
+  consecutive_failures: number;
+  next_retry_in_sec: number | null;
+  last_error: string | null;
+}
+
+// Rust의 managers/sync.rs COLLECTION_* ('data-changed' 이벤트 페이로드: 실제로 바뀐 컬렉션 목록)
+export type SyncedCollection = 'tasks' | 'schedules';
+
+// Rust의 commands/storage.rs StorageUsage ('get_storage_usage' 커맨드 응답)
+export interface TableUsage {
+  table: string;
+  rows: number;
+  bytes: number;
+}
+
+export interface StorageUsage {
+  tables: TableUsage[];
+  db_file_bytes: number;
+  log_files: number;
+  log_bytes: number;
+}
+
+// Rust의 managers/export.rs ExportReport ('export_data' 커맨드 응답)
+export type ExportFormat = 'jsonl' | 'csv';
+
+export interface ExportReport {
+  from_s: number;
+  to_s: number;
+  format: ExportFormat;
+  files: { dataset: 'events' | 'sessions' | 'feedback' | 'schedules'; path: string; rows: number }[];
+}
+
+// Rust의 managers/erasure.rs ErasureReport ('delete_all_my_data' 커맨드 응답)
+export interface FilesRemoved {
+  files: number;
+  bytes: number;
+}
+
+export type ServerErasure =
+  | { status: 'not_logged_in' }
+  | { status: 'completed'; deleted: Record<string, number> }
+  | { status: 'handled_by_sync' }
+  | { status: 'queued'; error: string }
+  | { status: 'dropped'; error: string };
+
+export interface ErasureReport {
+  database: { table: string; rows: number }[];
+  logs: FilesRemoved;
+  exports: FilesRemoved;
+  models: { removed: FilesRemoved; restored_bundled: string[]; learned_cache_entries: number };
+  settings_reset: boolean;
+  server: ServerErasure;
 }
 
 // F-6: User, Profile, Session (MSW 전용) 삭제
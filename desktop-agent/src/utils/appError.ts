@@ -0,0 +1,20 @@
+// 파일 위치: Force-Focus/desktop-agent/src/utils/appError.ts
+// invoke 실패 값 처리 헬퍼 (Rust 커맨드는 { code, message }, JS 예외는 Error)
+
+import type { AppError, AppErrorCode } from '../types';
+
+export function isAppError(e: unknown): e is AppError {
+  return typeof e === 'object' && e !== null && 'code' in e && 'message' in e;
+}
+
+/** 특정 코드의 AppError인지 확인 (예: 'unauthorized'면 로그인 화면으로) */
+export function hasErrorCode(e: unknown, code: AppErrorCode): boolean {
+  return isAppError(e) && e.code === code;
+}
+
+/** 화면 표시용 메시지 */
+export function errorMessage(e: unknown): string {
+  if (isAppError(e)) return e.message;
+  if (e instanceof Error) return e.message;
+  return String(e);
+}
@@ -4,6 +4,8 @@ import ReactDOM from 'react-dom/client';
 import { core } from '@tauri-apps/api';
 import { listen } from '@tauri-apps/api/event';
 import './App.css';
+import type { ActiveSessionInfo, SessionEndWarningPayload, SessionProgressPayload } from './types';
+import { errorMessage } from './utils/appError';
 // // [유지] Rust ActiveSessionInfo (PULL 타입)
 // interface ActiveSessionInfo {
 //   session_id: string;
@@ -18,13 +20,29 @@ import './App.css';
 const WidgetApp: React.FC = () => {
   const [elapsedTime, setElapsedTime] = useState<number>(0);
   const [error, setError] = useState<string | null>(null);
+  // 스케줄 세션 종료 경고 (연장 버튼 표시용)
+  const [endWarning, setEndWarning] = useState<SessionEndWarningPayload | null>(null);
+  // 세션 일시정지 여부 (타이머는 Rust가 순수 집중 시간으로 멈춰서 보냄)
+  const [isPaused, setIsPaused] = useState<boolean>(false);
+  // 목표 시간 대비 진행률 (widget-progress, 목표 없는 세션은 goal_sec = null)
+  const [progress, setProgress] = useState<SessionProgressPayload | null>(null);
 
   // [수정] Task 4.12 (P1): Rust(app_core)로부터 1초마다 '틱' 이벤트를 PUSH 수신
   useEffect(() => {
     let unlistenTick: (() => void) | null = null;
+    let unlistenProgress: (() => void) | null = null;
+    let unlistenWarning: (() => void) | null = null;
+    let unlistenExtended: (() => void) | null = null;
+    let unlistenEnded: (() => void) | null = null;
+    let unlistenPaused: (() => void) | null = null;
+    let unlistenResumed: (() => void) | null = null;
 
     const setupListeners = async () => {
       try {
+        // 0. 위젯이 (재)생성된 시점의 일시정지 상태 복원
+        const sessionInfo = await core.invoke<ActiveSessionInfo | null>('get_current_session_info');
+        setIsPaused(!!sessionInfo?.paused_at_s);
+
         // 1. "widget-tick" 이벤트 (1초마다 Rust가 보냄)
         // [!] (ACL 오류 해결) tauri.conf.json의 'widget' 창에 'event-listen' 권한 필요
         const unlistenTickFn = await listen<number>("widget-tick", (e) => {
@@ -32,10 +50,27 @@ const WidgetApp: React.FC = () => {
           setError(null);
         });
         unlistenTick = unlistenTickFn;
+        unlistenProgress = await listen<SessionProgressPayload>("widget-progress", (e) => {
+          setProgress(e.payload);
+        });
 
-      } catch (e: any) {
+        // 2. 스케줄 종료 경고 / 연장 / 종료
+        unlistenWarning = await listen<SessionEndWarningPayload>("session-end-warning", (e) => {
+          setEndWarning(e.payload);
+        });
+        unlistenExtended = await listen("session-extended", () => setEndWarning(null));
+        unlistenEnded = await listen("session-ended", () => {
+          setEndWarning(null);
+          setIsPaused(false);
+        });
+
+        // 3. 일시정지 / 재개
+        unlistenPaused = await listen("session-paused", () => setIsPaused(true));
+        unlistenResumed = await listen("session-resumed", () => setIsPaused(false));
+
+      } catch (e) {
         console.error("Widget listener setup failed:", e);
-        setError(e.toString());
+        setError(errorMessage(e));
       }
     };
 
@@ -45,6 +80,12 @@ const WidgetApp: React.FC = () => {
 
     return () => {
       if (unlistenTick) unlistenTick();
+      if (unlistenProgress) unlistenProgress();
+      if (unlistenWarning) unlistenWarning();
+      if (unlistenExtended) unlistenExtended();
+      if (unlistenEnded) unlistenEnded();
+      if (unlistenPaused) unlistenPaused();
+      if (unlistenResumed) unlistenResumed();
     };
   }, []); // 마운트 시 1회 실행
 
@@ -57,11 +98,33 @@ const WidgetApp: React.FC = () => {
     try {
       await core.invoke('end_session', { userEvaluationScore: 0 }); 
       setElapsedTime(0); // 즉시 0초로 리셋
-    } catch (e: any) {
-      setError(e.toString());
+    } catch (e) {
+      setError(errorMessage(e));
     }
   }, []);
 
+  // 스케줄 세션 연장 (minutes 미지정 시 사용자 설정값)
+  const handleExtendSession = useCallback(async () => {
+    setError(null);
+    try {
+      await core.invoke('extend_session', { minutes: null });
+      setEndWarning(null);
+    } catch (e) {
+      setError(errorMessage(e));
+    }
+  }, []);
+
+  // 일시정지 <-> 재개 토글
+  const handleTogglePause = useCallback(async () => {
+    setError(null);
+    try {
+      await core.invoke(isPaused ? 'resume_session' : 'pause_session');
+      setIsPaused(!isPaused);
+    } catch (e) {
+      setError(errorMessage(e));
+    }
+  }, [isPaused]);
+
   // [유지] 4. 헬퍼 함수
   const formatTime = (seconds: number): string => {
     const h = Math.floor(seconds / 3600).toString().padStart(2, '0');
@@ -120,13 +183,19 @@ const WidgetApp: React.FC = () => {
       >
         <span style={{ 
           fontSize: '11px', 
-          color: '#10B981', // 에메랄드 색상
+          color: isPaused ? '#F59E0B' : '#10B981', // 에메랄드 색상 (일시정지: 앰버)
           fontWeight: 'bold', 
           letterSpacing: '1px',
           marginBottom: '-2px',
           textTransform: 'uppercase'
         }}>
-          FOCUSING
+          {endWarning
+            ? `${Math.ceil(endWarning.remaining_sec / 60)}분 후 종료`
+            : isPaused
+              ? 'PAUSED'
+              : progress && progress.overtime_sec > 0
+                ? `OVERTIME +${formatTime(progress.overtime_sec)}`
+                : 'FOCUSING'}
         </span>
         <span style={{ 
           fontSize: '28px', 
@@ -137,8 +206,61 @@ const WidgetApp: React.FC = () => {
         }}>
           {formatTime(elapsedTime)}
         </span>
+        {/* 목표 진행률 바 (목표가 있는 세션만) */}
+        {!!progress?.goal_sec && (
+          <div style={{ height: '3px', backgroundColor: '#E2E8F0', borderRadius: '9999px', marginTop: '2px' }}>
+            <div style={{
+              width: `${Math.round(progress.progress_ratio * 100)}%`,
+              height: '100%',
+              backgroundColor: progress.overtime_sec > 0 ? '#6366F1' : '#10B981',
+              borderRadius: '9999px',
+              transition: 'width 0.5s linear'
+            }} />
+          </div>
+        )}
       </div>
       
+      {/* 일시정지 / 재개 버튼 */}
+      <button
+        onClick={handleTogglePause}
+        title={isPaused ? '재개' : '일시정지'}
+        style={{
+          backgroundColor: isPaused ? '#FEF3C7' : '#F1F5F9',
+          color: isPaused ? '#D97706' : '#475569',
+          padding: '8px 10px',
+          border: 'none',
+          borderRadius: '9999px',
+          fontWeight: '600',
+          cursor: 'pointer',
+          fontSize: '13px',
+          outline: 'none',
+          marginLeft: '8px'
+        }}
+      >
+        {isPaused ? '▶' : '❚❚'}
+      </button>
+
+      {/* 연장 버튼 (스케줄 종료 경고 시에만 표시) */}
+      {endWarning && (
+        <button
+          onClick={handleExtendSession}
+          style={{
+            backgroundColor: '#D1FAE5',
+            color: '#059669',
+            padding: '8px 10px',
+            border: 'none',
+            borderRadius: '9999px',
+            fontWeight: '600',
+            cursor: 'pointer',
+            fontSize: '13px',
+            outline: 'none',
+            marginLeft: '8px'
+          }}
+        >
+          +{endWarning.extend_min}분
+        </button>
+      )}
+
       {/* 종료 버튼 (아이콘 대신 텍스트 유지하되, Pill 형태의 버튼으로 변경) */}
       <button
         onClick={handleEndSession}